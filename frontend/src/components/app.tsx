@@ -1,10 +1,11 @@
 import React from 'react'
 import { ServerConnection } from '../utils/websocket-client'
 import { Slide, Snackbar } from '@material-ui/core'
-import { ServerEventCode, ServerEvent } from '../utils/shared-types'
+import { ServerEventCode, ServerEvent, Character, EffectCode, EventCard, Expansion } from '../utils/shared-types'
 import { environment } from '../environment'
 
 import { useSessionData } from '../providers/session.provider'
+import { useGameData } from '../providers/game.provider'
 import { Screen, useScreen } from '../providers/screen.provider'
 import { useServerConnection } from '../providers/server-connecton.provider'
 
@@ -19,6 +20,7 @@ export default function AppComponent(): JSX.Element {
   const { getScreen, setScreen } = useScreen()
   const { setConnection } = useServerConnection()
   const { setSession, getUser, getUsers, setUsers } = useSessionData()
+  const { setGameData } = useGameData()
 
   const [notification, setNotification] = React.useState('')
 
@@ -74,6 +76,56 @@ export default function AppComponent(): JSX.Element {
         [ServerEventCode.LogicError]: (response: ServerEvent) => {
           setNotification(response.message || '')
         },
+        [ServerEventCode.GameUpdate]: (response: ServerEvent) => {
+          const gameData = response.data?.game_data
+          if (gameData) setGameData(gameData)
+        },
+        [ServerEventCode.PlayerEliminated]: (response: ServerEvent) => {
+          setNotification('User ' + response.data?.client_id + ' was eliminated!')
+        },
+        [ServerEventCode.GameOver]: (response: ServerEvent) => {
+          setNotification('Game Over! Winners: ' + response.data?.winner_ids?.join(', '))
+        },
+        [ServerEventCode.CardsRevealed]: (response: ServerEvent) => {
+          setNotification('User ' + response.data?.client_id + ' revealed ' + response.data?.cards?.length + ' card(s)')
+        },
+        [ServerEventCode.CharacterChoices]: (response: ServerEvent) => {
+          setNotification('Choose a character: ' + response.data?.characters?.map(character => Character[character]).join(' or '))
+        },
+        [ServerEventCode.DeckReshuffled]: (response: ServerEvent) => {
+          setNotification('The discard pile was shuffled into a new deck')
+        },
+        [ServerEventCode.DeckEmpty]: (response: ServerEvent) => {
+          setNotification('There are no cards left to draw')
+        },
+        [ServerEventCode.ResponseRequested]: (response: ServerEvent) => {
+          setNotification('Respond to ' + response.data?.client_id + ' or pass')
+        },
+        [ServerEventCode.EffectStarted]: (response: ServerEvent) => {
+          setNotification('User ' + response.data?.client_id + ' played ' + EffectCode[response.data?.effect_code ?? EffectCode.None])
+        },
+        [ServerEventCode.DynamiteExploded]: (response: ServerEvent) => {
+          setNotification('The Dynamite exploded on ' + response.data?.client_id + '!')
+        },
+        [ServerEventCode.DynamitePassed]: (response: ServerEvent) => {
+          setNotification('The Dynamite was passed to ' + response.data?.client_id)
+        },
+        [ServerEventCode.TurnSkipped]: (response: ServerEvent) => {
+          setNotification('User ' + response.data?.client_id + ' stayed in Jail and skipped their turn')
+        },
+        [ServerEventCode.EventRevealed]: (response: ServerEvent) => {
+          const event = response.data?.game_data?.current_event
+          setNotification('The Sheriff revealed the event ' + (event !== undefined ? EventCard[event] : ''))
+        },
+        [ServerEventCode.CardBought]: (response: ServerEvent) => {
+          setNotification('User ' + response.data?.client_id + ' bought a card from the shop')
+        },
+        [ServerEventCode.ExpansionsChanged]: (response: ServerEvent) => {
+          setNotification('Expansions in play: ' + (response.data?.expansions?.map((e) => Expansion[e]).join(', ') || 'none'))
+        },
+        [ServerEventCode.TakeBackVoted]: (response: ServerEvent) => {
+          setNotification('User ' + response.data?.client_id + ' voted to take back the last action')
+        },
       })
     )
   }, [])
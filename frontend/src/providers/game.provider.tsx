@@ -11,6 +11,7 @@ type GameDataStore = {
   getPlayerOrder: Accessor<GameData['player_order']>
   getTurnIndex: Accessor<GameData['turn_index']>
 
+  setGameData: Mutator<GameData>
   setCardEvents: Mutator<GameData['card_events']>
   setDiscard: Mutator<GameData['discard']>
   setPlayerOrder: Mutator<GameData['player_order']>
@@ -22,7 +23,8 @@ export const useGameData = create<GameDataStore>((set, get) => ({
     card_events: [],
     discard: [],
     player_order: [],
-    turn_index: 0
+    turn_index: 0,
+    shop: []
   },
 
   getCardEvents: () => get().data.card_events,
@@ -30,6 +32,7 @@ export const useGameData = create<GameDataStore>((set, get) => ({
   getPlayerOrder: () => get().data.player_order,
   getTurnIndex: () => get().data.turn_index,
 
+  setGameData: data => set(state => { state.data = data }),
   setCardEvents: cardEvents => set(state => { state.data.card_events = cardEvents }),
   setDiscard: discard => set(state => { state.data.discard = discard }),
   setPlayerOrder: playerOrder => set(state => { state.data.player_order = playerOrder }),
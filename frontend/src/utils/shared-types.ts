@@ -8,25 +8,61 @@ export type ServerEventData = {
   session_client_ids?: Array<string>,
   game_data?: GameData,
   player_data?: PlayerData,
+  roles?: Record<string, Role>,
+  winner_ids?: Array<string>,
+  cards?: Array<Card>,
+  characters?: Array<Character>,
+  response_data?: ResponseData,
+  effect_code?: EffectCode,
+  expansions?: Array<Expansion>,
 }
 
 export type PlayerData = {
   health: number,
+  max_health: number,
   hand: Array<Card>,
   field: Array<Card>,
   character: Character,
   role: Role,
+  /// the gold nuggets the player has to spend in the shop
+  gold: number,
+  /// the equipment bought from the shop that is in front of the player
+  equipment: Array<ShopCard>,
 }
 
 export type GameData = {
   turn_index: number,
+  turn_phase: TurnPhase,
   player_order: Array<string>,
   card_events: Array<CardName>,
   discard: Array<Card>,
+  /// the event card changing the rules of the game, if one has been revealed
+  current_event?: EventCard,
+  /// the shop cards that can be bought
+  shop: Array<ShopCard>,
+  /// the seed the game was dealt from, for reproducing it in bug reports
+  seed: number,
+  /// whether the players may vote to take back the last action
+  take_backs: boolean,
+  /// what happens once both the deck and the discard pile run out
+  empty_deck_rule: EmptyDeckRule,
+  /// the names of the cards in the game that are not built into the client
+  custom_cards: Array<CustomName>,
+  /// the names of the characters in the game that are not built into the client
+  custom_characters: Array<CustomName>,
+}
+
+/// The name of a card or character that clients only know by its code
+export type CustomName = {
+  code: number,
+  name: string,
 }
 
 export type ServerEvent = {
   event_code: ServerEventCode,
+  error_code?: ErrorCode,
+  /// the cards, players and numbers the error is about
+  error_params?: ErrorParams,
   message?: string,
   data?: ServerEventData,
 }
@@ -34,6 +70,8 @@ export type ServerEvent = {
 export enum EffectCode {
   GeneralStore = 1,
   None,
+  Gatling,
+  Indians,
 }
 
 export type Card = {
@@ -42,16 +80,58 @@ export type Card = {
   rank: CardRank,
 }
 
+/// The codes are sent to clients and written to game logs, so a new card takes the next free code
 export enum CardName {
   // Brown Cards
   Bang = 1,
-  Hatchet,
-  Missed,
+  Hatchet = 2,
+  Missed = 3,
+  Beer = 14,
+  Saloon = 15,
+  Stagecoach = 16,
+  WellsFargo = 17,
+  Panic = 18,
+  CatBalou = 19,
+  Duel = 20,
+  Indians = 21,
+  Gatling = 22,
+  GeneralStore = 23,
+  Punch = 28,
+  Dodge = 29,
+  Springfield = 30,
+  Whisky = 31,
+  Tequila = 32,
+  Brawl = 33,
+  RagTime = 34,
   // Blue Cards
-  Barrel,
-  Dynamite,
+  Barrel = 4,
+  Dynamite = 5,
+  Jail = 24,
+  Mustang = 7,
+  Scope = 8,
+  Volcanic = 9,
+  Schofield = 10,
+  Remington = 11,
+  RevCarabine = 12,
+  Winchester = 13,
+  Hideout = 35,
+  Silver = 36,
+  Binocular = 37,
   // Green Cards
-  PonyExpress,
+  PonyExpress = 6,
+  Bible = 25,
+  IronPlate = 26,
+  Canteen = 27,
+  Sombrero = 38,
+  TenGallonHat = 39,
+  BuffaloRifle = 40,
+  CanCan = 41,
+  Conestoga = 42,
+  Derringer = 43,
+  Howitzer = 44,
+  Knife = 45,
+  Pepperbox = 46,
+  // cards clients only know by the code and name sent with the game (ex. Valley of Shadows)
 }
 
 export enum CardSuit {
@@ -89,6 +169,93 @@ export enum ServerEventCode {
   // client_id
   TurnStart,
   LogicError,
+  // game_data, player_data
+  GameUpdate,
+  // client_id, roles
+  PlayerEliminated,
+  // roles, winner_ids
+  GameOver,
+  // client_id, cards
+  CardsRevealed,
+  // client_id, characters
+  CharacterChoices,
+  // game_data
+  DeckReshuffled,
+  // game_data
+  DeckEmpty,
+  // client_id, game_data, response_data, effect_code, cards
+  ResponseRequested,
+  // client_id, effect_code, cards
+  EffectStarted,
+  // client_id
+  DynamiteExploded,
+  // client_id, game_data
+  DynamitePassed,
+  // client_id, game_data
+  TurnSkipped,
+  // session_id, expansions
+  ExpansionsChanged,
+  // client_id, game_data
+  EventRevealed,
+  // client_id, game_data
+  CardBought,
+  // client_id
+  TakeBackVoted,
+}
+
+export enum ErrorCode {
+  InvalidPhase = 1,
+  HandLimitExceeded,
+  NotYourTurn,
+  CardNotInHand,
+  InvalidSelection,
+  AbilityUnavailable,
+  CardNotReady,
+  CardDisabled,
+  NotEnoughGold,
+  InvalidLog,
+  TakeBackUnavailable,
+  // target_id, distance, range
+  OutOfRange,
+  // expected, given
+  WrongTargetCount,
+  // target_id
+  InvalidTarget,
+  // limit
+  BangLimitReached,
+  ResponseOnly,
+  FullHealth,
+  TooFewPlayers,
+  // target_id
+  SheriffTargeted,
+  // target_id
+  EmptyHand,
+  // target_id
+  FieldCardMissing,
+  // target_id, card
+  DuplicateEquipment,
+  ExtraDiscardRequired,
+  // target_id
+  NotEliminated,
+}
+
+/// The details of a rule error, for pointing out the cards and players involved
+export type ErrorParams = {
+  target_id?: string,
+  card?: CardName,
+  distance?: number,
+  range?: number,
+  expected?: number,
+  given?: number,
+  limit?: number,
+}
+
+export enum TurnPhase {
+  Start = 1,
+  Draw,
+  Play,
+  Discard,
+  End,
 }
 
 export type ClientEvent = {
@@ -96,6 +263,18 @@ export type ClientEvent = {
   target_ids?: Array<string>,
   cards?: Array<Card>,
   session_id?: string,
+  character?: Character,
+  field_card?: Card,
+  expansions?: Array<Expansion>,
+  shop_card?: ShopCard,
+  /// the seed to deal a new game from, only honored from the session owner
+  seed?: number,
+  /// the contents of a downloaded game log to play back
+  game_log?: string,
+  /// whether the players of a new game may vote to take back the last action, only honored from the session owner
+  take_backs?: boolean,
+  /// what a new game does once both the deck and the discard pile run out, only honored from the session owner
+  empty_deck_rule?: EmptyDeckRule,
 }
 
 export enum ClientEventCode {
@@ -108,6 +287,20 @@ export enum ClientEventCode {
   EndTurn,
   PlayCard,
   StateResponse,
+  DrawCards,
+  DiscardCards,
+  // cards, target_ids
+  UseAbility,
+  // character
+  ChooseCharacter,
+  // expansions
+  SetExpansions,
+  // shop_card
+  BuyCard,
+  // game_log, target_ids
+  LoadReplay,
+  ReplayStep,
+  VoteTakeBack,
 }
 
 export enum Role {
@@ -118,10 +311,94 @@ export enum Role {
 }
 
 export enum Character {
-  BillyTheKid = 1,
+  BartCassidy = 1,
+  BlackJack,
+  CalamityJanet,
+  ElGringo,
+  JesseJones,
+  Jourdonnais,
+  KitCarlson,
+  LuckyDuke,
+  PaulRegret,
+  PedroRamirez,
+  RoseDoolan,
+  SidKetchum,
+  SlabTheKiller,
+  SuzyLafayette,
+  VultureSam,
+  WillyTheKid,
+  // Dodge City
+  ApacheKid,
+  BelleStar,
+  BillNoface,
+  ChuckWengam,
+  DocHolyday,
+  ElenaFuente,
+  GregDigger,
+  HerbHunter,
+  JoseDelgado,
+  MollyStark,
+  PatBrennan,
+  PixiePete,
+  SeanMallory,
+  TequilaJoe,
+  VeraCuster,
+  // Gold Rush
+  DonBell,
+  DutchWill,
+  JackyMurieta,
+  JoshMcCloud,
+  MadamYto,
+  PrettyLuzena,
+  RaddieSnake,
+  SimeonPicos,
+  // characters clients only know by the code and name sent with the game (ex. Valley of Shadows)
+}
+
+export enum Expansion {
+  DodgeCity = 1,
+  HighNoon,
+  FistfulOfCards,
+  GoldRush,
+  ValleyOfShadows,
+}
+
+/// What happens when a card must be drawn but both the deck and the discard pile are empty
+export enum EmptyDeckRule {
+  /// the card is not drawn
+  SkipDraw = 1,
+  /// a fresh deck of the cards not in play is shuffled and play continues
+  NewDeck,
+}
+
+export enum EventCard {
+  // High Noon
+  Thirst = 1,
+  Hangover,
+  Sermon,
+  Shootout,
+  TheDoctor,
+  GhostTown,
+  HighNoon,
+  // A Fistful of Cards
+  Ambush,
+  Lasso,
+  TheJudge,
 }
 
 export type ResponseData = {
   cards: Array<CardName>,
   characters: Array<Character>,
 }
+
+export enum ShopCard {
+  // Brown
+  UnionPacific = 1,
+  Rum,
+  // Black
+  Belt,
+  Boots,
+  Calumet,
+  Horseshoe,
+  Pickaxe,
+}
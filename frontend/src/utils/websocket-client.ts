@@ -1,6 +1,6 @@
 import { IMessageEvent, w3cwebsocket as W3CWebSocket } from 'websocket'
 import { environment } from '../environment'
-import { Card, ClientEvent, ClientEventCode, ServerEvent, ServerEventCode } from './shared-types'
+import { Card, ClientEvent, ClientEventCode, ServerEvent, ServerEventCode, Expansion, ShopCard } from './shared-types'
 
 
 export class ServerConnection {
@@ -56,11 +56,19 @@ export class ServerConnection {
     return !!this.socket && this.socket.readyState == this.socket.OPEN
   }
 
-  public play_card(cards: Card[], targets: string[]): void {
+  public play_card(cards: Card[], targets: string[], field_card?: Card): void {
     this.send_message({
       event_code: ClientEventCode.PlayCard,
       target_ids: targets,
       cards,
+      field_card,
+    })
+  }
+
+  public buy_card(shop_card: ShopCard): void {
+    this.send_message({
+      event_code: ClientEventCode.BuyCard,
+      shop_card,
     })
   }
 
@@ -76,12 +84,39 @@ export class ServerConnection {
     })
   }
 
+  public set_expansions(expansions: Expansion[]): void {
+    this.send_message({
+      event_code: ClientEventCode.SetExpansions,
+      expansions,
+    })
+  }
+
   public startGame(): void {
     this.send_message({
       event_code: ClientEventCode.StartGame,
     })
   }
 
+  public load_replay(game_log: string, player?: string): void {
+    this.send_message({
+      event_code: ClientEventCode.LoadReplay,
+      game_log,
+      target_ids: player ? [player] : undefined,
+    })
+  }
+
+  public replay_step(): void {
+    this.send_message({
+      event_code: ClientEventCode.ReplayStep,
+    })
+  }
+
+  public vote_take_back(): void {
+    this.send_message({
+      event_code: ClientEventCode.VoteTakeBack,
+    })
+  }
+
 
   public join_session(session_id: string, errorCallback?: (err: string) => void): void {
     const error = this.verifySessionID(session_id)
@@ -0,0 +1,615 @@
+use crate::shared_types;
+use nanorand::{WyRand, RNG};
+use std::collections::HashMap;
+
+pub mod abilities;
+pub mod actions;
+pub mod card_definitions;
+pub mod data;
+pub mod dodge_city;
+pub mod draft;
+pub mod draw_check;
+pub mod elimination;
+pub mod equipment;
+pub mod events;
+pub mod expansions;
+pub mod gold_rush;
+pub mod green_cards;
+pub mod replay;
+pub mod response;
+pub mod take_backs;
+pub mod turn;
+pub mod types;
+pub mod valley_of_shadows;
+
+#[cfg(any(test, feature = "test-utils"))]
+pub mod test_utils;
+
+// Helper constructors for different kinds of ServerEvents
+impl shared_types::ServerEvent {
+  pub fn from_error(message: &str) -> shared_types::ServerEvent {
+    println!("[ServerEventError] {}", message);
+    shared_types::ServerEvent {
+      event_code: shared_types::ServerEventCode::LogicError,
+      error_code: None,
+      error_params: None,
+      message: Some(message.to_string()),
+      data: None,
+    }
+  }
+  pub fn from_rule_error(error: &types::RuleError) -> shared_types::ServerEvent {
+    shared_types::ServerEvent {
+      event_code: shared_types::ServerEventCode::LogicError,
+      error_code: Some(error.get_code()),
+      error_params: Some(error.get_params()),
+      message: Some(error.get_message()),
+      data: None,
+    }
+  }
+  pub fn from_event(
+    event_code: shared_types::ServerEventCode,
+    data: shared_types::ServerEventData,
+  ) -> shared_types::ServerEvent {
+    shared_types::ServerEvent {
+      event_code,
+      error_code: None,
+      error_params: None,
+      data: Some(data),
+      message: None,
+    }
+  }
+  pub fn empty(event_code: shared_types::ServerEventCode) -> shared_types::ServerEvent {
+    shared_types::ServerEvent {
+      event_code,
+      error_code: None,
+      error_params: None,
+      data: None,
+      message: None,
+    }
+  }
+}
+
+impl types::RuleError {
+  /// The numeric code the error is sent to clients as
+  pub fn get_code(&self) -> shared_types::ErrorCode {
+    match self {
+      types::RuleError::InvalidPhase => shared_types::ErrorCode::InvalidPhase,
+      types::RuleError::HandLimitExceeded => shared_types::ErrorCode::HandLimitExceeded,
+      types::RuleError::NotYourTurn => shared_types::ErrorCode::NotYourTurn,
+      types::RuleError::CardNotInHand => shared_types::ErrorCode::CardNotInHand,
+      types::RuleError::InvalidSelection => shared_types::ErrorCode::InvalidSelection,
+      types::RuleError::AbilityUnavailable => shared_types::ErrorCode::AbilityUnavailable,
+      types::RuleError::CardNotReady => shared_types::ErrorCode::CardNotReady,
+      types::RuleError::CardDisabled => shared_types::ErrorCode::CardDisabled,
+      types::RuleError::NotEnoughGold => shared_types::ErrorCode::NotEnoughGold,
+      types::RuleError::InvalidLog => shared_types::ErrorCode::InvalidLog,
+      types::RuleError::TakeBackUnavailable => shared_types::ErrorCode::TakeBackUnavailable,
+      types::RuleError::OutOfRange { .. } => shared_types::ErrorCode::OutOfRange,
+      types::RuleError::WrongTargetCount { .. } => shared_types::ErrorCode::WrongTargetCount,
+      types::RuleError::InvalidTarget { .. } => shared_types::ErrorCode::InvalidTarget,
+      types::RuleError::BangLimitReached { .. } => shared_types::ErrorCode::BangLimitReached,
+      types::RuleError::ResponseOnly => shared_types::ErrorCode::ResponseOnly,
+      types::RuleError::FullHealth => shared_types::ErrorCode::FullHealth,
+      types::RuleError::TooFewPlayers => shared_types::ErrorCode::TooFewPlayers,
+      types::RuleError::SheriffTargeted { .. } => shared_types::ErrorCode::SheriffTargeted,
+      types::RuleError::EmptyHand { .. } => shared_types::ErrorCode::EmptyHand,
+      types::RuleError::FieldCardMissing { .. } => shared_types::ErrorCode::FieldCardMissing,
+      types::RuleError::DuplicateEquipment { .. } => shared_types::ErrorCode::DuplicateEquipment,
+      types::RuleError::ExtraDiscardRequired => shared_types::ErrorCode::ExtraDiscardRequired,
+      types::RuleError::NotEliminated { .. } => shared_types::ErrorCode::NotEliminated,
+    }
+  }
+
+  /// The cards, players and numbers the error is about, for clients to point out
+  pub fn get_params(&self) -> shared_types::ErrorParams {
+    match self {
+      types::RuleError::OutOfRange {
+        target,
+        distance,
+        range,
+      } => shared_types::ErrorParams {
+        target_id: Some(target.clone()),
+        distance: Some(*distance),
+        range: Some(*range),
+        ..Default::default()
+      },
+      types::RuleError::WrongTargetCount { expected, given } => shared_types::ErrorParams {
+        expected: Some(*expected),
+        given: Some(*given),
+        ..Default::default()
+      },
+      types::RuleError::BangLimitReached { limit } => shared_types::ErrorParams {
+        limit: Some(*limit),
+        ..Default::default()
+      },
+      types::RuleError::DuplicateEquipment { target, card } => shared_types::ErrorParams {
+        target_id: Some(target.clone()),
+        card: Some(card.clone()),
+        ..Default::default()
+      },
+      types::RuleError::InvalidTarget { target }
+      | types::RuleError::SheriffTargeted { target }
+      | types::RuleError::EmptyHand { target }
+      | types::RuleError::FieldCardMissing { target }
+      | types::RuleError::NotEliminated { target } => shared_types::ErrorParams {
+        target_id: Some(target.clone()),
+        ..Default::default()
+      },
+      _ => shared_types::ErrorParams::default(),
+    }
+  }
+
+  /// A readable description of the error for clients that do not handle the code
+  pub fn get_message(&self) -> String {
+    match self {
+      types::RuleError::InvalidPhase => {
+        String::from("That cannot be done during this phase of the turn.")
+      }
+      types::RuleError::HandLimitExceeded => {
+        String::from("Discard down to your health before ending the turn.")
+      }
+      types::RuleError::NotYourTurn => String::from("It is not your turn to act."),
+      types::RuleError::CardNotInHand => String::from("Lack the cards to play."),
+      types::RuleError::InvalidSelection => {
+        String::from("Those cards or players cannot be chosen.")
+      }
+      types::RuleError::AbilityUnavailable => {
+        String::from("Your character cannot do that right now.")
+      }
+      types::RuleError::CardNotReady => {
+        String::from("That card cannot be used until a later turn.")
+      }
+      types::RuleError::CardDisabled => {
+        String::from("That card cannot be played during the current event.")
+      }
+      types::RuleError::NotEnoughGold => String::from("Not enough gold nuggets to buy that card."),
+      types::RuleError::InvalidLog => String::from("That is not a game log."),
+      types::RuleError::TakeBackUnavailable => String::from("There is no action to take back."),
+      types::RuleError::OutOfRange {
+        target,
+        distance,
+        range,
+      } => format!(
+        "{} is out of range, at a distance of {} with a range of {}.",
+        target, distance, range
+      ),
+      types::RuleError::WrongTargetCount { expected, given } => format!(
+        "Wrong number of targets, this card takes {} rather than {}.",
+        expected, given
+      ),
+      types::RuleError::InvalidTarget { target } => {
+        format!("{} is not another player in the game.", target)
+      }
+      types::RuleError::BangLimitReached { limit } => {
+        format!("Only {} Bang! can be played each turn.", limit)
+      }
+      types::RuleError::ResponseOnly => String::from("This card can only be used as a response."),
+      types::RuleError::FullHealth => String::from("Already at full health."),
+      types::RuleError::TooFewPlayers => {
+        String::from("This card has no effect with two players left.")
+      }
+      types::RuleError::SheriffTargeted { target } => {
+        format!("{} is the Sheriff, who can not be put in Jail.", target)
+      }
+      types::RuleError::EmptyHand { target } => format!("{} has no cards in hand.", target),
+      types::RuleError::FieldCardMissing { target } => {
+        format!("{} does not have the chosen card in front of them.", target)
+      }
+      types::RuleError::DuplicateEquipment { target, card } => {
+        format!("{} already has a {:?} in front of them.", target, card)
+      }
+      types::RuleError::ExtraDiscardRequired => {
+        String::from("This card must be played with another card to discard.")
+      }
+      types::RuleError::NotEliminated { target } => {
+        format!("{} is not an eliminated player.", target)
+      }
+    }
+  }
+}
+
+impl shared_types::PlayerData {
+  pub fn card_iter(
+    &self,
+  ) -> std::iter::Chain<
+    std::slice::Iter<'_, shared_types::Card>,
+    std::slice::Iter<'_, shared_types::Card>,
+  > {
+    self.hand.iter().chain(self.field.iter())
+  }
+
+  /// Removes a single copy of each card from the hand or field,
+  /// since the deck can hold more than one card with the same name, suit and rank
+  pub fn remove_cards(&mut self, cards: &Vec<shared_types::Card>) {
+    for card in cards {
+      if let Some(index) = self.hand.iter().position(|owned| owned == card) {
+        self.hand.remove(index);
+      } else if let Some(index) = self.field.iter().position(|owned| owned == card) {
+        self.field.remove(index);
+      }
+    }
+  }
+
+  /// Whether every card is held in the hand or field, counting copies of the same card separately
+  pub fn has_cards(&self, cards: &Vec<shared_types::Card>) -> bool {
+    let mut owned_cards: Vec<&shared_types::Card> = self.card_iter().collect();
+    cards.iter().all(
+      |card| match owned_cards.iter().position(|owned| *owned == card) {
+        Some(index) => {
+          owned_cards.remove(index);
+          true
+        }
+        None => false,
+      },
+    )
+  }
+
+  /// Whether every card is held in the hand, counting copies of the same card separately
+  pub fn has_in_hand(&self, cards: &Vec<shared_types::Card>) -> bool {
+    let mut hand = self.hand.clone();
+    cards
+      .iter()
+      .all(|card| match hand.iter().position(|owned| owned == card) {
+        Some(index) => {
+          hand.remove(index);
+          true
+        }
+        None => false,
+      })
+  }
+
+  pub fn is_alive(&self) -> bool {
+    self.health > 0
+  }
+}
+
+/// The turn order, per-player data, deck and character choices of a newly started game
+type InitialGameData = (
+  Vec<String>,
+  HashMap<String, shared_types::PlayerData>,
+  Vec<shared_types::Card>,
+  HashMap<String, Vec<shared_types::Character>>,
+);
+
+fn initialize_game_data(
+  client_vec: &Vec<String>,
+  expansions: &Vec<shared_types::Expansion>,
+  game_dict: &types::GameDictionary,
+  rng: &mut WyRand,
+) -> Result<InitialGameData, String> {
+  let player_count = client_vec.len();
+  // some expansions make room for more players (ex. an eighth player with Dodge City)
+  let max_players = expansions::get_max_players(expansions, &game_dict.expansion_dict);
+  if !(expansions::MIN_PLAYERS..=max_players).contains(&player_count) {
+    return Err(format!(
+      "Cannot Play with less than {} Players or More than {}!",
+      expansions::MIN_PLAYERS,
+      max_players
+    ));
+  }
+
+  let mut role_vec: Vec<shared_types::Role> = Vec::with_capacity(player_count);
+  role_vec.extend(
+    [
+      shared_types::Role::Renegade,
+      shared_types::Role::Outlaw,
+      shared_types::Role::Outlaw,
+    ]
+    .iter()
+    .cloned(),
+  );
+  // 4 players: Sheriff, 1 Renegade, 2 Outlaws
+  // 5 players: Sheriff, 1 Renegade, 2 Outlaws, 1 Deputy
+  // 6 players: Sheriff, 1 Renegade, 3 Outlaws, 1 Deputy
+  // 7 players: Sheriff, 1 Renegade, 3 Outlaws, 2 Deputy
+  // 8 players: Sheriff, 2 Renegades, 3 Outlaws, 2 Deputy
+  if player_count >= 5 {
+    role_vec.push(shared_types::Role::Deputy);
+  }
+  if player_count >= 6 {
+    role_vec.push(shared_types::Role::Outlaw);
+  }
+  if player_count >= 7 {
+    role_vec.push(shared_types::Role::Deputy);
+  }
+  if player_count >= 8 {
+    role_vec.push(shared_types::Role::Renegade);
+  }
+  // shuffle the order characters
+  rng.shuffle(&mut role_vec);
+  // set sheriff as first turn
+  role_vec.insert(0, shared_types::Role::Sheriff);
+  // deal random characters for each player to choose between
+  let mut character_vec: Vec<shared_types::Character> = game_dict
+    .character_dict
+    .keys()
+    .filter(|character| {
+      match expansions::get_character_expansion(character, &game_dict.expansion_dict) {
+        Some(expansion) => expansions.contains(&expansion),
+        None => true,
+      }
+    })
+    .cloned()
+    .collect();
+  if character_vec.len() < player_count * draft::CHARACTER_CHOICE_COUNT {
+    return Err(String::from("Not enough Characters for every Player!"));
+  }
+  // the dictionaries are unordered, so the characters are sorted for the seed to deal the same ones
+  character_vec.sort_by_key(|character| u8::from(character.clone()));
+  rng.shuffle(&mut character_vec);
+  let mut character_chunks = character_vec.chunks(draft::CHARACTER_CHOICE_COUNT);
+  // create client_vec copy that we will shuffle
+  let mut playerinfo_vec: Vec<String> = client_vec.clone();
+  playerinfo_vec.sort();
+  rng.shuffle(&mut playerinfo_vec);
+  // map the random client_vec to the
+  let character_choices = playerinfo_vec
+    .iter()
+    .filter_map(|id| Some((id.clone(), character_chunks.next()?.to_vec())))
+    .collect::<HashMap<String, Vec<shared_types::Character>>>();
+  // health and hands are dealt once each player has chosen their character
+  let player_data = playerinfo_vec
+    .iter()
+    .enumerate()
+    .map(|(i, id)| {
+      (
+        id.clone(),
+        shared_types::PlayerData {
+          health: 0,
+          max_health: 0,
+          field: Vec::new(),
+          hand: Vec::new(),
+          character: character_choices[id][0].clone(),
+          role: role_vec[i].clone(),
+          gold: 0,
+          equipment: Vec::new(),
+        },
+      )
+    })
+    .collect::<HashMap<String, shared_types::PlayerData>>();
+
+  let deck = data::generate_deck(expansions, game_dict, rng);
+
+  return Ok((playerinfo_vec, player_data, deck, character_choices));
+}
+
+impl types::GameState {
+  /// Deals a new game to the given clients, who are then left to choose their characters
+  ///
+  /// Every shuffle and random pick of the game follows from the seed,
+  /// which is drawn at random when none is given
+  pub fn new(
+    client_ids: &Vec<String>,
+    expansions: &Vec<shared_types::Expansion>,
+    seed: Option<u32>,
+    empty_deck_rule: shared_types::EmptyDeckRule,
+    game_dict: &types::GameDictionary,
+  ) -> Result<types::GameState, String> {
+    let seed: u32 = seed.unwrap_or_else(|| WyRand::new().generate());
+    let mut rng = WyRand::new_seed(seed as u64);
+    let (player_order, player_data, deck, character_choices) =
+      initialize_game_data(client_ids, expansions, game_dict, &mut rng)?;
+    let event_deck = events::generate_event_deck(expansions, &game_dict.expansion_dict, &mut rng);
+    let shop_deck = gold_rush::generate_shop_deck(expansions, &mut rng);
+    // logged in the order of their codes, so that the same game is always logged the same way
+    let mut card_definitions: Vec<card_definitions::CardDefinition> =
+      game_dict.card_definitions.values().cloned().collect();
+    card_definitions.sort_by_key(|definition| u8::from(definition.name.clone()));
+    // clients are sent the names of the cards and characters they do not know that are dealt
+    let mut custom_cards: Vec<shared_types::CustomName> = game_dict
+      .card_names
+      .iter()
+      .filter(|(name, _)| deck.iter().any(|card| card.name == **name))
+      .map(|(name, title)| shared_types::CustomName {
+        code: u8::from(name.clone()),
+        name: title.clone(),
+      })
+      .collect();
+    custom_cards.sort_by_key(|custom_card| custom_card.code);
+    let mut custom_characters: Vec<shared_types::CustomName> = game_dict
+      .character_names
+      .iter()
+      .filter(|(character, _)| character_choices.values().flatten().any(|c| c == *character))
+      .map(|(character, title)| shared_types::CustomName {
+        code: u8::from(character.clone()),
+        name: title.clone(),
+      })
+      .collect();
+    custom_characters.sort_by_key(|custom_character| custom_character.code);
+    let mut game_state = types::GameState {
+      turn_index: 0,
+      turn_count: 0,
+      ability_uses: 0,
+      expansions: expansions.clone(),
+      copied_characters: HashMap::new(),
+      turn_phase: shared_types::TurnPhase::Start,
+      bangs_played: 0,
+      empty_deck_rule: empty_deck_rule.clone(),
+      character_choices,
+      revealed_cards: Vec::new(),
+      field_selection: None,
+      usable_from: HashMap::new(),
+      event_deck,
+      current_event: None,
+      shop_deck,
+      shop: Vec::new(),
+      extra_turn: false,
+      seed,
+      rng,
+      log: replay::GameLog {
+        seed,
+        client_ids: client_ids.clone(),
+        expansions: expansions.clone(),
+        empty_deck_rule,
+        card_definitions,
+        entries: Vec::new(),
+      },
+      take_backs: false,
+      custom_cards,
+      custom_characters,
+      snapshot: None,
+      take_back_votes: Vec::new(),
+      seating: player_order.clone(),
+      ghosts: Vec::new(),
+      player_order,
+      player_data,
+      deck_list: deck.clone(),
+      deck,
+      discard: Vec::new(),
+      event_stack: Vec::new(),
+      response_queue: HashMap::new(),
+      trigger_queue: HashMap::new(),
+      card_events: Vec::new(),
+    };
+    game_state.refill_shop();
+    return Ok(game_state);
+  }
+}
+
+/// Shuffles a vector of cards
+pub fn shuffle_deck<T>(deck: &mut Vec<T>, rng: &mut WyRand) {
+  // the shuffle can not handle decks with fewer than two cards
+  if deck.len() > 1 {
+    rng.shuffle(deck);
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn each_player_is_dealt_different_characters_to_choose_from() {
+    let game_dict = test_utils::game_dict();
+    let client_ids: Vec<String> = (0..7).map(|i| format!("p{}", i)).collect();
+    let (_, player_data, _, character_choices) = initialize_game_data(
+      &client_ids,
+      &Vec::new(),
+      &game_dict,
+      &mut nanorand::WyRand::new_seed(0),
+    )
+    .unwrap();
+    // only the base game characters are dealt without expansions
+    assert!(character_choices.values().flatten().all(|character| {
+      expansions::get_character_expansion(character, &game_dict.expansion_dict).is_none()
+    }));
+
+    let mut dealt: Vec<u8> = character_choices
+      .values()
+      .flatten()
+      .map(|character| u8::from(character.clone()))
+      .collect();
+    dealt.sort_unstable();
+    dealt.dedup();
+    assert_eq!(dealt.len(), 7 * draft::CHARACTER_CHOICE_COUNT);
+    assert!(player_data
+      .values()
+      .all(|player_data| player_data.hand.is_empty()));
+  }
+
+  #[test]
+  fn an_eighth_player_needs_dodge_city() {
+    let game_dict = test_utils::game_dict();
+    let client_ids: Vec<String> = (0..8).map(|i| format!("p{}", i)).collect();
+    assert!(initialize_game_data(
+      &client_ids,
+      &Vec::new(),
+      &game_dict,
+      &mut nanorand::WyRand::new_seed(0)
+    )
+    .is_err());
+
+    let expansions = vec![shared_types::Expansion::DodgeCity];
+    let (_, player_data, deck, _) = initialize_game_data(
+      &client_ids,
+      &expansions,
+      &game_dict,
+      &mut nanorand::WyRand::new_seed(0),
+    )
+    .unwrap();
+    let count_role = |role: shared_types::Role| {
+      player_data
+        .values()
+        .filter(|player_data| player_data.role == role)
+        .count()
+    };
+    assert_eq!(count_role(shared_types::Role::Sheriff), 1);
+    assert_eq!(count_role(shared_types::Role::Renegade), 2);
+    assert_eq!(count_role(shared_types::Role::Outlaw), 3);
+    assert_eq!(count_role(shared_types::Role::Deputy), 2);
+    assert!(deck
+      .iter()
+      .any(|card| card.name == shared_types::CardName::Punch));
+  }
+
+  #[test]
+  fn a_seed_deals_the_same_game() {
+    let game_dict = test_utils::game_dict();
+    let client_ids: Vec<String> = (0..5).map(|i| format!("p{}", i)).collect();
+    let mut reordered_ids = client_ids.clone();
+    reordered_ids.reverse();
+    let expansions = vec![shared_types::Expansion::HighNoon];
+
+    let game_state = types::GameState::new(
+      &client_ids,
+      &expansions,
+      Some(42),
+      shared_types::EmptyDeckRule::default(),
+      &game_dict,
+    )
+    .unwrap();
+    let replayed = types::GameState::new(
+      &reordered_ids,
+      &expansions,
+      Some(42),
+      shared_types::EmptyDeckRule::default(),
+      &game_dict,
+    )
+    .unwrap();
+    assert_eq!(game_state.seed, 42);
+    assert_eq!(game_state.player_order, replayed.player_order);
+    assert_eq!(game_state.deck, replayed.deck);
+    assert_eq!(game_state.event_deck, replayed.event_deck);
+    assert_eq!(game_state.character_choices, replayed.character_choices);
+    for player in client_ids.iter() {
+      assert_eq!(
+        game_state.player_data[player].role,
+        replayed.player_data[player].role
+      );
+    }
+  }
+
+  #[test]
+  fn card_codes_stay_the_same_as_cards_are_added() {
+    let codes: Vec<serde_json::Value> = vec![
+      shared_types::CardName::Bang,
+      shared_types::CardName::Barrel,
+      shared_types::CardName::PonyExpress,
+      shared_types::CardName::Mustang,
+      valley_of_shadows::RATTLESNAKE,
+    ]
+    .into_iter()
+    .map(|card| serde_json::to_value(card).unwrap())
+    .collect();
+    assert_eq!(codes, vec![1, 4, 6, 7, 52]);
+  }
+
+  #[test]
+  fn codes_are_read_back_into_the_same_cards_and_characters() {
+    let game_dict = test_utils::game_dict();
+    for name in game_dict.card_dict.keys() {
+      let code = u8::from(name.clone());
+      assert_eq!(shared_types::CardName::from(code), *name);
+      assert_eq!(serde_json::to_value(name).unwrap(), code);
+    }
+    for character in game_dict.character_dict.keys() {
+      let code = u8::from(character.clone());
+      assert_eq!(shared_types::Character::from(code), *character);
+      assert_eq!(serde_json::to_value(character).unwrap(), code);
+    }
+    assert_eq!(
+      shared_types::CardName::from(200),
+      shared_types::CardName::Custom(200)
+    );
+  }
+}
@@ -0,0 +1,444 @@
+use crate::{game_engine, shared_types};
+
+/// Character Abilities
+///
+/// Abilities which react to something happening in the game are activated through
+/// the triggers of their CharacterData, while abilities that change the rules
+/// are checked directly by the game logic they affect.
+impl game_engine::types::GameState {
+  /// Whether a player is playing as the given character
+  pub fn has_character(&self, player: &str, character: shared_types::Character) -> bool {
+    return match self.player_data.get(player) {
+      Some(player_data) => player_data.character == character,
+      None => false,
+    };
+  }
+
+  /// The cards that a card can be used as by a player.
+  ///
+  /// This is only the card itself, except for Calamity Janet
+  /// who can use a Bang as a Missed and a Missed as a Bang,
+  /// Elena Fuente who can use any card as a Missed,
+  /// and for cards which stand in for another card (ex. Dodge, Bible).
+  /// Any card is also a Bang during the turn of a player with a Lemat in play.
+  pub fn get_card_uses(
+    &self,
+    player: &str,
+    card: &shared_types::CardName,
+  ) -> Vec<shared_types::CardName> {
+    let is_shot_card =
+      *card == shared_types::CardName::Bang || *card == shared_types::CardName::Missed;
+    let mut card_uses =
+      if is_shot_card && self.has_character(player, shared_types::Character::CalamityJanet) {
+        vec![
+          card.clone(),
+          match card {
+            shared_types::CardName::Bang => shared_types::CardName::Missed,
+            _ => shared_types::CardName::Bang,
+          },
+        ]
+      } else if *card != shared_types::CardName::Missed
+        && self.has_character(player, shared_types::Character::ElenaFuente)
+      {
+        vec![card.clone(), shared_types::CardName::Missed]
+      } else {
+        match game_engine::data::get_stand_in(card) {
+          Some(stand_in) => vec![card.clone(), stand_in],
+          None => vec![card.clone()],
+        }
+      };
+    if !card_uses.contains(&shared_types::CardName::Bang) && self.is_lemat_ready(player) {
+      card_uses.push(shared_types::CardName::Bang);
+    }
+    return card_uses;
+  }
+
+  /// Apache Kid and players with a Calumet are unaffected by Diamonds played by other players
+  pub fn is_unaffected_by(&self, target: &str, user: &str, card: &shared_types::Card) -> bool {
+    return target != user
+      && card.suit == shared_types::CardSuit::Diamonds
+      && (self.has_character(target, shared_types::Character::ApacheKid)
+        || self.has_shop_equipment(target, shared_types::ShopCard::Calumet));
+  }
+
+  /// The other players in seat order who are affected by a card that hits everyone (ex. Gatling)
+  pub fn get_affected_players(&self, user: &str, card: &shared_types::Card) -> Vec<String> {
+    return self
+      .get_other_players(user)
+      .into_iter()
+      .filter(|target| !self.is_unaffected_by(target, user, card))
+      .collect();
+  }
+
+  /// Whether a player can use a card as another card
+  pub fn counts_as(
+    &self,
+    player: &str,
+    card: &shared_types::CardName,
+    as_card: &shared_types::CardName,
+  ) -> bool {
+    return self.get_card_uses(player, card).contains(as_card);
+  }
+
+  /// Activates the abilities of any of the living players which react to the trigger
+  pub fn trigger_abilities(
+    &mut self,
+    players: &Vec<String>,
+    context: &game_engine::types::TriggerContext,
+    game_dict: &game_engine::types::GameDictionary,
+  ) -> game_engine::types::GameMessages {
+    let mut messages = Vec::new();
+    for player in players {
+      let character_data = match self.player_data.get(player) {
+        Some(player_data) if player_data.is_alive() => {
+          match game_dict.character_dict.get(&player_data.character) {
+            Some(character_data) => character_data,
+            None => continue,
+          }
+        }
+        _ => continue,
+      };
+      if character_data.triggers.contains(&context.trigger) {
+        if let Ok(ability_messages) = (character_data.effect)(player, context, self, game_dict) {
+          messages.extend(ability_messages);
+        }
+      }
+    }
+    return messages;
+  }
+
+  /// Activates the abilities of players who have run out of cards in their hand
+  pub fn resolve_empty_hands(
+    &mut self,
+    game_dict: &game_engine::types::GameDictionary,
+  ) -> game_engine::types::GameMessages {
+    let players: Vec<String> = self
+      .player_order
+      .iter()
+      .filter(|id| match self.player_data.get(id.as_str()) {
+        Some(player_data) => player_data.hand.is_empty(),
+        None => false,
+      })
+      .cloned()
+      .collect();
+
+    return self.trigger_abilities(
+      &players,
+      &game_engine::types::TriggerContext {
+        trigger: game_engine::types::EventTrigger::HandEmpty,
+        source: None,
+        amount: 0,
+        cards: &Vec::new(),
+        targets: &Vec::new(),
+      },
+      game_dict,
+    );
+  }
+
+  /// The player uses an ability of their character which they can activate at any time
+  pub fn use_ability(
+    &mut self,
+    player: &str,
+    cards: &Vec<shared_types::Card>,
+    targets: &Vec<String>,
+    game_dict: &game_engine::types::GameDictionary,
+  ) -> Result<game_engine::types::GameMessages, game_engine::types::RuleError> {
+    let character_data = match self.player_data.get(player) {
+      Some(player_data) if player_data.is_alive() => {
+        game_dict.character_dict.get(&player_data.character)
+      }
+      _ => None,
+    };
+    let effect = match character_data {
+      Some(character_data)
+        if character_data
+          .triggers
+          .contains(&game_engine::types::EventTrigger::Activated) =>
+      {
+        character_data.effect
+      }
+      _ => return Err(game_engine::types::RuleError::AbilityUnavailable),
+    };
+
+    let context = game_engine::types::TriggerContext {
+      trigger: game_engine::types::EventTrigger::Activated,
+      source: None,
+      amount: 0,
+      cards,
+      targets,
+    };
+    let mut messages = effect(player, &context, self, game_dict)?;
+    messages.extend(self.resolve_empty_hands(game_dict));
+    messages.extend(self.get_update_messages());
+    return Ok(messages);
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::game_engine::test_utils::{card, game_dict, game_state, game_with_sheriff};
+  use crate::game_engine::types::GameState;
+  use crate::shared_types::{Card, CardName, CardSuit, Character};
+
+  fn set_character(game_state: &mut GameState, player: &str, character: Character) {
+    game_state.player_data.get_mut(player).unwrap().character = character;
+  }
+
+  fn give(game_state: &mut GameState, player: &str, cards: &[Card]) {
+    let player_data = game_state.player_data.get_mut(player).unwrap();
+    player_data.hand.extend(cards.iter().cloned());
+  }
+
+  #[test]
+  fn willy_the_kid_plays_any_number_of_bangs() {
+    let mut game_state = game_state(4);
+    assert!(!game_state.has_unlimited_bangs("p0"));
+    set_character(&mut game_state, "p0", Character::WillyTheKid);
+    assert!(game_state.has_unlimited_bangs("p0"));
+  }
+
+  #[test]
+  fn bart_cassidy_draws_for_each_life_point_lost() {
+    let game_dict = game_dict();
+    let mut game_state = game_state(4);
+    game_state.deck = vec![card(CardName::Bang, CardSuit::Clubs); 5];
+    set_character(&mut game_state, "p1", Character::BartCassidy);
+
+    game_state.damage_player("p1", 2, Some("p0"), &game_dict);
+    assert_eq!(game_state.player_data["p1"].health, 2);
+    assert_eq!(game_state.player_data["p1"].hand.len(), 2);
+  }
+
+  #[test]
+  fn el_gringo_takes_a_card_from_the_attacker() {
+    let game_dict = game_dict();
+    let mut game_state = game_state(4);
+    set_character(&mut game_state, "p1", Character::ElGringo);
+    give(
+      &mut game_state,
+      "p0",
+      &[card(CardName::Beer, CardSuit::Hearts)],
+    );
+
+    game_state.damage_player("p1", 1, Some("p0"), &game_dict);
+    assert!(game_state.player_data["p0"].hand.is_empty());
+    assert_eq!(game_state.player_data["p1"].hand.len(), 1);
+  }
+
+  #[test]
+  fn calamity_janet_dodges_with_a_bang() {
+    let game_dict = game_dict();
+    let mut game_state = game_with_sheriff();
+    let bang = card(CardName::Bang, CardSuit::Clubs);
+    set_character(&mut game_state, "p1", Character::CalamityJanet);
+    give(&mut game_state, "p1", std::slice::from_ref(&bang));
+
+    let bang_data = &game_dict.card_dict[&CardName::Bang];
+    (bang_data.effect)(
+      "p0",
+      &vec![bang.clone()],
+      &vec![String::from("p1")],
+      &mut game_state,
+      &game_dict,
+    );
+    assert!(game_state.response_queue.contains_key("p1"));
+
+    (bang_data.update)(
+      "p0",
+      &vec![bang],
+      &vec![String::from("p1")],
+      &mut game_state,
+      &game_dict,
+    );
+    assert_eq!(game_state.player_data["p1"].health, 4);
+    assert!(game_state.player_data["p1"].hand.is_empty());
+  }
+
+  #[test]
+  fn slab_the_killer_needs_two_missed() {
+    let game_dict = game_dict();
+    let mut game_state = game_with_sheriff();
+    let missed = card(CardName::Missed, CardSuit::Clubs);
+    set_character(&mut game_state, "p0", Character::SlabTheKiller);
+    give(&mut game_state, "p1", &[missed.clone(), missed.clone()]);
+
+    let bang_data = &game_dict.card_dict[&CardName::Bang];
+    let targets = vec![String::from("p1")];
+    (bang_data.effect)(
+      "p0",
+      &vec![card(CardName::Bang, CardSuit::Clubs)],
+      &targets,
+      &mut game_state,
+      &game_dict,
+    );
+
+    (bang_data.update)(
+      "p0",
+      &vec![missed.clone()],
+      &targets,
+      &mut game_state,
+      &game_dict,
+    );
+    assert_eq!(game_state.player_data["p1"].health, 3);
+
+    (bang_data.update)(
+      "p0",
+      &vec![missed.clone(), missed],
+      &targets,
+      &mut game_state,
+      &game_dict,
+    );
+    assert_eq!(game_state.player_data["p1"].health, 3);
+    assert!(game_state.player_data["p1"].hand.is_empty());
+  }
+
+  #[test]
+  fn jourdonnais_avoids_shots_on_a_heart() {
+    let game_dict = game_dict();
+    let mut game_state = game_state(4);
+    game_state.deck = vec![card(CardName::Beer, CardSuit::Hearts)];
+    set_character(&mut game_state, "p1", Character::Jourdonnais);
+
+    let bang_data = &game_dict.card_dict[&CardName::Bang];
+    (bang_data.effect)(
+      "p0",
+      &vec![card(CardName::Bang, CardSuit::Clubs)],
+      &vec![String::from("p1")],
+      &mut game_state,
+      &game_dict,
+    );
+    assert_eq!(game_state.player_data["p1"].health, 4);
+    assert!(game_state.deck.is_empty());
+  }
+
+  #[test]
+  fn vulture_sam_takes_the_cards_of_eliminated_players() {
+    let game_dict = game_dict();
+    let mut game_state = game_state(4);
+    set_character(&mut game_state, "p2", Character::VultureSam);
+    give(
+      &mut game_state,
+      "p1",
+      &[card(CardName::Bang, CardSuit::Clubs)],
+    );
+    game_state
+      .player_data
+      .get_mut("p1")
+      .unwrap()
+      .field
+      .push(card(CardName::Mustang, CardSuit::Hearts));
+
+    game_state.damage_player("p1", 4, Some("p0"), &game_dict);
+    assert!(!game_state.player_data["p1"].is_alive());
+    assert_eq!(game_state.player_data["p2"].hand.len(), 2);
+    assert!(game_state.discard.is_empty());
+  }
+
+  #[test]
+  fn suzy_lafayette_draws_when_her_hand_is_empty() {
+    let game_dict = game_dict();
+    let mut game_state = game_state(4);
+    game_state.deck = vec![card(CardName::Bang, CardSuit::Clubs)];
+    set_character(&mut game_state, "p3", Character::SuzyLafayette);
+
+    game_state.resolve_empty_hands(&game_dict);
+    assert_eq!(game_state.player_data["p3"].hand.len(), 1);
+    assert_eq!(game_state.player_data["p0"].hand.len(), 0);
+  }
+
+  #[test]
+  fn sid_ketchum_discards_two_cards_to_heal() {
+    let game_dict = game_dict();
+    let mut game_state = game_state(4);
+    let cards = vec![
+      card(CardName::Bang, CardSuit::Clubs),
+      card(CardName::Missed, CardSuit::Clubs),
+    ];
+    give(&mut game_state, "p1", &cards);
+
+    assert!(game_state
+      .use_ability("p1", &cards, &Vec::new(), &game_dict)
+      .is_err());
+
+    game_state.player_data.get_mut("p1").unwrap().health = 2;
+    assert!(game_state
+      .use_ability("p1", &cards, &Vec::new(), &game_dict)
+      .is_ok());
+    assert_eq!(game_state.player_data["p1"].health, 3);
+    assert!(game_state.player_data["p1"].hand.is_empty());
+  }
+
+  #[test]
+  fn black_jack_draws_again_on_a_red_second_card() {
+    let game_dict = game_dict();
+    let mut game_state = game_state(4);
+    game_state.turn_phase = crate::shared_types::TurnPhase::Draw;
+    set_character(&mut game_state, "p0", Character::BlackJack);
+    give(
+      &mut game_state,
+      "p0",
+      &[card(CardName::Beer, CardSuit::Hearts)],
+    );
+    // the deck is drawn from the end, so the Bang of Hearts comes second
+    game_state.deck = vec![
+      card(CardName::Missed, CardSuit::Clubs),
+      card(CardName::Bang, CardSuit::Hearts),
+      card(CardName::Bang, CardSuit::Clubs),
+    ];
+
+    game_state
+      .draw_phase("p0", &Vec::new(), &Vec::new(), &game_dict)
+      .unwrap();
+    assert_eq!(game_state.player_data["p0"].hand.len(), 4);
+    assert!(game_state.deck.is_empty());
+  }
+
+  #[test]
+  fn black_jack_only_checks_a_card_that_was_drawn_second() {
+    let game_dict = game_dict();
+    let mut game_state = game_state(4);
+    game_state.turn_phase = crate::shared_types::TurnPhase::Draw;
+    set_character(&mut game_state, "p0", Character::BlackJack);
+    // with Thirst only one card is drawn, so even a red one is not worth another card
+    game_state.current_event = Some(crate::shared_types::EventCard::Thirst);
+    game_state.deck = vec![
+      card(CardName::Missed, CardSuit::Clubs),
+      card(CardName::Bang, CardSuit::Hearts),
+    ];
+
+    game_state
+      .draw_phase("p0", &Vec::new(), &Vec::new(), &game_dict)
+      .unwrap();
+    assert_eq!(game_state.player_data["p0"].hand.len(), 1);
+    assert_eq!(game_state.deck.len(), 1);
+    assert!(game_state.turn_phase == crate::shared_types::TurnPhase::Play);
+  }
+
+  #[test]
+  fn kit_carlson_keeps_two_of_the_top_three_cards() {
+    let game_dict = game_dict();
+    let mut game_state = game_state(4);
+    let deck = vec![
+      card(CardName::Bang, CardSuit::Clubs),
+      card(CardName::Missed, CardSuit::Clubs),
+      card(CardName::Beer, CardSuit::Hearts),
+    ];
+    game_state.deck = deck.clone();
+    game_state.turn_phase = crate::shared_types::TurnPhase::Draw;
+    set_character(&mut game_state, "p0", Character::KitCarlson);
+
+    game_state
+      .draw_phase("p0", &Vec::new(), &Vec::new(), &game_dict)
+      .unwrap();
+    assert_eq!(game_state.revealed_cards.len(), 3);
+    assert!(game_state.turn_phase == crate::shared_types::TurnPhase::Draw);
+
+    let kept = vec![deck[0].clone(), deck[2].clone()];
+    game_state
+      .draw_phase("p0", &kept, &Vec::new(), &game_dict)
+      .unwrap();
+    assert_eq!(game_state.player_data["p0"].hand, kept);
+    assert_eq!(game_state.deck, vec![deck[1].clone()]);
+    assert!(game_state.turn_phase == crate::shared_types::TurnPhase::Play);
+  }
+}
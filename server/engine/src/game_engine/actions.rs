@@ -0,0 +1,320 @@
+use crate::{game_engine, shared_types};
+use serde::{Deserialize, Serialize};
+
+/// A message for a single client, addressed by their id
+pub type Event = (String, shared_types::ServerEvent);
+
+/// The player the server takes its own actions as
+pub const SERVER_PLAYER: &str = "server";
+
+/// Everything a player can do in a game
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(rename_all = "snake_case")]
+pub enum Action {
+  DrawCards {
+    cards: Vec<shared_types::Card>,
+    targets: Vec<String>,
+  },
+  PlayCard {
+    cards: Vec<shared_types::Card>,
+    targets: Vec<String>,
+    field_card: Option<shared_types::Card>,
+  },
+  Respond {
+    cards: Vec<shared_types::Card>,
+  },
+  DiscardCards {
+    cards: Vec<shared_types::Card>,
+  },
+  EndTurn,
+  UseAbility {
+    cards: Vec<shared_types::Card>,
+    targets: Vec<String>,
+  },
+  ChooseCharacter {
+    character: shared_types::Character,
+  },
+  BuyCard {
+    shop_card: shared_types::ShopCard,
+  },
+  /// taken by the server for the players who have not chosen a character once the time is up
+  EndDraft,
+}
+
+impl game_engine::types::GameState {
+  /// Applies the action of a player to the game,
+  /// returning the messages for each client or the rule that refused it.
+  ///
+  /// Accepted actions are written to the log of the game along with their messages.
+  pub fn apply(
+    &mut self,
+    player: &str,
+    action: Action,
+    game_dict: &game_engine::types::GameDictionary,
+  ) -> Result<Vec<Event>, game_engine::types::RuleError> {
+    // the actions of the server are not up for a vote
+    let snapshot = if self.take_backs && player != SERVER_PLAYER {
+      Some(self.take_snapshot())
+    } else {
+      None
+    };
+    let result = match action.clone() {
+      Action::DrawCards { cards, targets } => self.draw_phase(player, &cards, &targets, game_dict),
+      Action::PlayCard {
+        cards,
+        targets,
+        field_card,
+      } => self.play_card(player, &cards, &targets, field_card, game_dict),
+      Action::Respond { cards } => self.respond(player, &cards, game_dict),
+      Action::DiscardCards { cards } => self.discard_phase(player, &cards, game_dict),
+      Action::EndTurn => self.end_play_phase(player, game_dict),
+      Action::UseAbility { cards, targets } => {
+        self.use_ability(player, &cards, &targets, game_dict)
+      }
+      Action::ChooseCharacter { character } => self.choose_character(player, &character, game_dict),
+      Action::BuyCard { shop_card } => self.buy_card(player, &shop_card, game_dict),
+      Action::EndDraft => {
+        if !self.is_drafting() {
+          return Err(game_engine::types::RuleError::InvalidPhase);
+        }
+        Ok(self.auto_choose_characters(game_dict))
+      }
+    };
+    if let Ok(events) = &result {
+      self.log.entries.push(game_engine::replay::LogEntry {
+        player: player.to_string(),
+        action,
+        events: events.clone(),
+      });
+      self.snapshot = snapshot.map(Box::new);
+      self.take_back_votes.clear();
+    }
+    return result;
+  }
+
+  /// Plays the first card from the hand or field of the player,
+  /// as the first card it can be used as whose preconditions pass
+  fn play_card(
+    &mut self,
+    player: &str,
+    cards: &Vec<shared_types::Card>,
+    targets: &Vec<String>,
+    field_card: Option<shared_types::Card>,
+    game_dict: &game_engine::types::GameDictionary,
+  ) -> Result<game_engine::types::GameMessages, game_engine::types::RuleError> {
+    if cards.is_empty() {
+      return Err(game_engine::types::RuleError::InvalidSelection);
+    }
+    self.check_turn_owner(player)?;
+    self.check_phase(shared_types::TurnPhase::Play)?;
+    //===========================================
+    // check that the cards sent in the request
+    // are actually in the hands of the player
+    //===========================================
+    let owned = match self.player_data.get(player) {
+      Some(player_data) => cards
+        .iter()
+        .all(|card| player_data.card_iter().any(|owned| owned == card)),
+      None => false,
+    };
+    if !owned {
+      return Err(game_engine::types::RuleError::CardNotInHand);
+    }
+    //=========================================================
+    // green cards are put in front of the player when played from their hand,
+    // and can only be activated from their field once they are ready
+    //=========================================================
+    if self.is_green_card(&cards[0].name, game_dict) {
+      let in_hand = match self.player_data.get(player) {
+        Some(player_data) => player_data.has_in_hand(&vec![cards[0].clone()]),
+        None => false,
+      };
+      if in_hand {
+        if self.is_card_disabled(player, &cards[0].name, game_dict) {
+          return Err(game_engine::types::RuleError::CardDisabled);
+        }
+        return self.place_green_card(player, &cards[0]);
+      }
+      if !self.is_card_usable(player, &cards[0]) {
+        return Err(game_engine::types::RuleError::CardNotReady);
+      }
+    }
+    if targets
+      .iter()
+      .any(|target| self.is_unaffected_by(target, player, &cards[0]))
+    {
+      return Err(game_engine::types::RuleError::InvalidSelection);
+    }
+    // a field card may be chosen for cards that take one from the target (ex. Panic!)
+    self.field_selection = field_card;
+    //=========================================================
+    // execute the preconditions check of each card the primary card can be used as,
+    // then execute the effect of the first one that passes and is not disabled by an event
+    //=========================================================
+    let card_uses: Vec<shared_types::CardName> = self
+      .get_card_uses(player, &cards[0].name)
+      .into_iter()
+      .filter(|name| !self.is_card_disabled(player, name, game_dict))
+      .collect();
+    // the card is refused for the reason its first use failed,
+    // or because every use of it is disabled by an event
+    let mut first_error: Option<game_engine::types::RuleError> = None;
+    let mut effect: Option<game_engine::types::CardEffect> = None;
+    for card_data in card_uses
+      .iter()
+      .filter_map(|name| game_dict.card_dict.get(name))
+    {
+      match (card_data.preconditions)(player, cards, targets, self, game_dict) {
+        Ok(()) => {
+          effect = Some(card_data.effect);
+          break;
+        }
+        Err(error) => {
+          first_error.get_or_insert(error);
+        }
+      }
+    }
+    let effect = match effect {
+      Some(effect) => effect,
+      None => {
+        self.field_selection = None;
+        return Err(first_error.unwrap_or(game_engine::types::RuleError::CardDisabled));
+      }
+    };
+    let mut messages: game_engine::types::GameMessages =
+      effect(player, cards, targets, self, game_dict);
+    self.field_selection = None;
+    messages.extend(self.resolve_empty_hands(game_dict));
+    messages.extend(self.get_update_messages());
+    return Ok(messages);
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::game_engine::test_utils;
+
+  fn mustang() -> shared_types::Card {
+    shared_types::Card {
+      name: shared_types::CardName::Mustang,
+      suit: shared_types::CardSuit::Hearts,
+      rank: shared_types::CardRank::N8,
+    }
+  }
+
+  #[test]
+  fn playing_a_card_that_is_not_owned_is_refused() {
+    let game_dict = test_utils::game_dict();
+    let mut game_state = test_utils::game_state(4);
+    game_state.turn_phase = shared_types::TurnPhase::Play;
+    let player = game_state.player_order[0].clone();
+
+    let result = game_state.apply(
+      &player,
+      Action::PlayCard {
+        cards: vec![mustang()],
+        targets: Vec::new(),
+        field_card: None,
+      },
+      &game_dict,
+    );
+    assert!(matches!(
+      result,
+      Err(game_engine::types::RuleError::CardNotInHand)
+    ));
+  }
+
+  #[test]
+  fn playing_an_owned_card_applies_its_effect() {
+    let game_dict = test_utils::game_dict();
+    let mut game_state = test_utils::game_state(4);
+    game_state.turn_phase = shared_types::TurnPhase::Play;
+    let player = game_state.player_order[0].clone();
+    if let Some(player_data) = game_state.player_data.get_mut(&player) {
+      player_data.hand.push(mustang());
+    }
+
+    let result = game_state.apply(
+      &player,
+      Action::PlayCard {
+        cards: vec![mustang()],
+        targets: Vec::new(),
+        field_card: None,
+      },
+      &game_dict,
+    );
+    assert!(result.is_ok());
+    assert_eq!(game_state.player_data[&player].field, vec![mustang()]);
+  }
+
+  fn play(
+    game_state: &mut game_engine::types::GameState,
+    name: shared_types::CardName,
+    targets: &[&str],
+  ) -> Result<(), game_engine::types::RuleError> {
+    let card = shared_types::Card {
+      name,
+      suit: shared_types::CardSuit::Spades,
+      rank: shared_types::CardRank::N8,
+    };
+    game_state
+      .player_data
+      .get_mut("p0")
+      .unwrap()
+      .hand
+      .push(card.clone());
+    game_state.apply(
+      "p0",
+      Action::PlayCard {
+        cards: vec![card],
+        targets: targets.iter().map(|target| target.to_string()).collect(),
+        field_card: None,
+      },
+      &test_utils::game_dict(),
+    )?;
+    return Ok(());
+  }
+
+  #[test]
+  fn a_bang_out_of_range_reports_the_distance_and_range() {
+    let mut game_state = test_utils::game_state(5);
+
+    let error = play(&mut game_state, shared_types::CardName::Bang, &["p2"]).unwrap_err();
+    assert_eq!(
+      error,
+      game_engine::types::RuleError::OutOfRange {
+        target: String::from("p2"),
+        distance: 2,
+        range: 1,
+      }
+    );
+    assert!(matches!(
+      error.get_code(),
+      shared_types::ErrorCode::OutOfRange
+    ));
+    let params = error.get_params();
+    assert_eq!(params.target_id, Some(String::from("p2")));
+    assert_eq!(params.distance, Some(2));
+    assert_eq!(params.range, Some(1));
+  }
+
+  #[test]
+  fn a_card_whose_preconditions_fail_is_refused_with_the_reason() {
+    let mut game_state = test_utils::game_state(4);
+
+    assert_eq!(
+      play(&mut game_state, shared_types::CardName::Beer, &[]).unwrap_err(),
+      game_engine::types::RuleError::FullHealth
+    );
+    assert_eq!(
+      play(&mut game_state, shared_types::CardName::Bang, &[]).unwrap_err(),
+      game_engine::types::RuleError::WrongTargetCount {
+        expected: 1,
+        given: 0,
+      }
+    );
+    // refused actions are not logged
+    assert!(game_state.log.entries.is_empty());
+  }
+}
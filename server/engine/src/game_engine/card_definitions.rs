@@ -0,0 +1,760 @@
+use crate::{game_engine, shared_types};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::{fs, io};
+
+/// Card Definitions
+///
+/// Cards can be defined in a JSON file instead of in code, so that house rules can be tried out
+/// without recompiling the server. Each definition names a card, and describes
+/// how it is targeted and what it does with a small set of engine primitives:
+///
+/// ```json
+/// [
+///   {
+///     "name": "Whisky",
+///     "color": "Brown",
+///     "copies": [{ "suit": "Hearts", "rank": "Q" }],
+///     "preconditions": ["target_none", "require_wounded"],
+///     "effect": ["discard", { "heal": 2 }]
+///   }
+/// ]
+/// ```
+///
+/// A defined card takes the place of the built in rules of the card with the same name,
+/// and its copies are added to the deck on top of those of the base game and expansions.
+/// Cards of an expansion are only defined in games that have the expansion enabled.
+/// Definitions are validated when the server boots, which refuses to start with invalid ones.
+///
+/// A card that is not built in is a new card, which is given the next free code of
+/// `DEFINED_CARD_CODES` in the order of the file. Clients are sent its name along with the game,
+/// and game logs keep its definition, so that it is played back the same way.
+pub type CardDefinitions = HashMap<shared_types::CardName, CardDefinition>;
+
+/// A validated card definition, ready to be played
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct CardDefinition {
+  pub name: shared_types::CardName,
+  /// the name the card is written with in the definition file
+  #[serde(default)]
+  pub title: String,
+  pub color: game_engine::types::CardColor,
+  pub copies: Vec<(shared_types::CardSuit, shared_types::CardRank)>,
+  pub weapon_range: Option<u8>,
+  pub distance_modifier: game_engine::types::DistanceModifier,
+  pub triggers: Vec<game_engine::types::EventTrigger>,
+  pub preconditions: Vec<Precondition>,
+  pub effect: Vec<EffectPrimitive>,
+}
+
+/// The checks a card must pass before it can be played
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum Precondition {
+  /// the card is played without any Targets
+  TargetNone,
+  /// the card targets another player at any distance
+  TargetAny,
+  /// the card targets another player within the given distance
+  RequireRange(u8),
+  /// the card targets another player within the range of the weapon of the user
+  RequireWeaponRange,
+  /// the user has lost health
+  RequireWounded,
+  /// the card counts towards the Bangs the user can play this turn
+  BangLimit,
+  /// the card has no effect with only two players left (ex. Beer)
+  MoreThanTwoPlayers,
+}
+
+/// The building blocks of the effect of a card, applied in order
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum EffectPrimitive {
+  /// the played cards are discarded
+  Discard,
+  /// the played card is put in front of the user
+  Equip,
+  /// the user draws the given number of cards
+  Draw(usize),
+  /// the user regains the given amount of health
+  Heal(u8),
+  /// every player regains the given amount of health
+  HealEveryone(u8),
+  /// the Targets lose the given amount of health, with no chance to avoid it
+  DealDamage(u8),
+  /// the Targets are shot at, and lose a life point unless they respond with a Missed
+  Shoot,
+  /// every other player is shot at, like with a Gatling
+  ShootEveryone,
+  /// the card counts as the Bang of the turn
+  CountBang,
+}
+
+/// A card definition as it is written in the definition file
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct CardDefinitionEntry {
+  name: String,
+  color: game_engine::types::CardColor,
+  #[serde(default)]
+  copies: Vec<CardCopyEntry>,
+  #[serde(default)]
+  weapon_range: Option<u8>,
+  #[serde(default)]
+  distance_modifier: game_engine::types::DistanceModifier,
+  #[serde(default)]
+  triggers: Vec<game_engine::types::EventTrigger>,
+  #[serde(default)]
+  preconditions: Vec<Precondition>,
+  #[serde(default)]
+  effect: Vec<EffectPrimitive>,
+}
+
+/// The suit and rank of a copy of a card as it is printed on the card (ex. "Hearts", "10")
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct CardCopyEntry {
+  suit: String,
+  rank: String,
+}
+
+/// The codes given to new cards, clear of the codes of the built in cards
+pub const DEFINED_CARD_CODES: std::ops::RangeInclusive<u8> = 128..=255;
+
+/// Where the card definition file is read from when no other path is given
+pub const DEFAULT_CARD_DEFINITIONS_PATH: &str = "cards.json";
+
+/// Reads the card definition file at the given path, or at the default path when none is given.
+/// Only a missing file at the default path means there are no card definitions,
+/// any other file that can not be read is an error.
+pub fn read_card_definitions(
+  path: Option<&str>,
+  game_dict: &game_engine::types::GameDictionary,
+) -> Result<CardDefinitions, Vec<String>> {
+  return match (
+    fs::read_to_string(path.unwrap_or(DEFAULT_CARD_DEFINITIONS_PATH)),
+    path,
+  ) {
+    (Ok(json), _) => parse_card_definitions(&json, game_dict),
+    (Err(error), None) if error.kind() == io::ErrorKind::NotFound => Ok(HashMap::new()),
+    (Err(error), _) => Err(vec![error.to_string()]),
+  };
+}
+
+/// Parses and validates card definitions, collecting every problem found along the way.
+/// Cards are named the way clients know them, so the dictionary should hold every expansion.
+pub fn parse_card_definitions(
+  json: &str,
+  game_dict: &game_engine::types::GameDictionary,
+) -> Result<CardDefinitions, Vec<String>> {
+  let entries: Vec<CardDefinitionEntry> = match serde_json::from_str(json) {
+    Ok(entries) => entries,
+    Err(error) => return Err(vec![error.to_string()]),
+  };
+
+  let mut definitions = HashMap::new();
+  let mut errors = Vec::new();
+  let mut labels = HashSet::new();
+  let mut free_codes = DEFINED_CARD_CODES;
+  for entry in entries {
+    let label = entry.name.clone();
+    if !labels.insert(label.clone()) {
+      errors.push(format!("{}: the card is defined more than once", label));
+      continue;
+    }
+    // a card that is not in the game yet is a new card, identified by the next free code
+    let name = match game_dict
+      .card_dict
+      .keys()
+      .find(|name| get_card_title(name, game_dict) == entry.name)
+    {
+      Some(name) => name.clone(),
+      None => match free_codes.next() {
+        Some(code) => shared_types::CardName::Custom(code),
+        None => {
+          errors.push(format!("{}: there are no codes left for new cards", label));
+          continue;
+        }
+      },
+    };
+    match validate_entry(name, entry) {
+      Ok(definition) => {
+        definitions.insert(definition.name.clone(), definition);
+      }
+      Err(entry_errors) => {
+        errors.extend(
+          entry_errors
+            .into_iter()
+            .map(|error| format!("{}: {}", label, error)),
+        );
+      }
+    }
+  }
+  if !errors.is_empty() {
+    return Err(errors);
+  }
+  return Ok(definitions);
+}
+
+/// Replaces the card data of every defined card with the rules from its definition
+pub fn insert_definitions(
+  card_dict: &mut game_engine::types::CardDictionary,
+  definitions: &CardDefinitions,
+) {
+  for definition in definitions.values() {
+    let shoots = definition.effect.iter().any(|primitive| {
+      *primitive == EffectPrimitive::Shoot || *primitive == EffectPrimitive::ShootEveryone
+    });
+    card_dict.insert(
+      definition.name.clone(),
+      game_engine::types::CardData {
+        color: definition.color.clone(),
+        weapon_range: definition.weapon_range,
+        distance_modifier: definition.distance_modifier.clone(),
+        triggers: definition.triggers.clone(),
+        preconditions: check_definition,
+        effect: apply_definition,
+        update: match shoots {
+          true => game_engine::data::resolve_shot,
+          false => |_user_id, _cards, _targets, _game_state, _game_dict| Vec::new(),
+        },
+      },
+    );
+  }
+}
+
+/// The name a card is defined with, which is the name clients know it by
+fn get_card_title(
+  name: &shared_types::CardName,
+  game_dict: &game_engine::types::GameDictionary,
+) -> String {
+  return match game_dict.card_names.get(name) {
+    Some(title) => title.clone(),
+    None => format!("{:?}", name),
+  };
+}
+
+/// Checks a definition against the rules every card has to follow
+fn validate_entry(
+  name: shared_types::CardName,
+  entry: CardDefinitionEntry,
+) -> Result<CardDefinition, Vec<String>> {
+  let mut errors = Vec::new();
+
+  // a new card is only ever played from the copies it adds to the deck
+  if is_new_card(&name) && entry.copies.is_empty() {
+    errors.push(String::from(
+      "there is no card with this name, and a new card needs copies to be dealt",
+    ));
+  }
+
+  let mut copies = Vec::new();
+  for copy in entry.copies.iter() {
+    match (parse_suit(&copy.suit), parse_rank(&copy.rank)) {
+      (Some(suit), Some(rank)) => copies.push((suit, rank)),
+      _ => errors.push(format!("{} of {} is not a card", copy.rank, copy.suit)),
+    }
+  }
+
+  let placements = entry
+    .effect
+    .iter()
+    .filter(|primitive| {
+      **primitive == EffectPrimitive::Discard || **primitive == EffectPrimitive::Equip
+    })
+    .count();
+  match entry.color {
+    game_engine::types::CardColor::Brown if entry.effect.contains(&EffectPrimitive::Equip) => {
+      errors.push(String::from("Brown cards are discarded, not equipped"))
+    }
+    game_engine::types::CardColor::Blue if entry.effect.contains(&EffectPrimitive::Discard) => {
+      errors.push(String::from("Blue cards are equipped, not discarded"))
+    }
+    game_engine::types::CardColor::Green => {
+      errors.push(String::from("Green cards can not be defined"))
+    }
+    _ => {}
+  }
+  if placements != 1 {
+    errors.push(String::from(
+      "the effect must either discard or equip the card exactly once",
+    ));
+  }
+  if entry.weapon_range.is_some() && entry.color != game_engine::types::CardColor::Blue {
+    errors.push(String::from("only Blue cards can be weapons"));
+  }
+
+  let targeting = entry
+    .preconditions
+    .iter()
+    .filter(|precondition| is_targeting(precondition))
+    .count();
+  if targeting != 1 {
+    errors.push(String::from(
+      "the preconditions must say how the card is targeted exactly once",
+    ));
+  }
+  let targets_player = entry
+    .preconditions
+    .iter()
+    .any(|precondition| is_targeting(precondition) && *precondition != Precondition::TargetNone);
+  let hits_targets = entry.effect.iter().any(|primitive| {
+    *primitive == EffectPrimitive::Shoot || matches!(primitive, EffectPrimitive::DealDamage(_))
+  });
+  if hits_targets && !targets_player {
+    errors.push(String::from(
+      "the card hits its Targets without targeting a player",
+    ));
+  }
+
+  // a shot opens a response window, which has to be the last thing the card does
+  let shots = entry
+    .effect
+    .iter()
+    .filter(|primitive| {
+      **primitive == EffectPrimitive::Shoot || **primitive == EffectPrimitive::ShootEveryone
+    })
+    .count();
+  if shots > 1 {
+    errors.push(String::from("the card can only shoot once"));
+  } else if shots == 1
+    && !matches!(
+      entry.effect.last(),
+      Some(EffectPrimitive::Shoot) | Some(EffectPrimitive::ShootEveryone)
+    )
+  {
+    errors.push(String::from("shooting must be the last part of the effect"));
+  }
+
+  return match errors.is_empty() {
+    true => Ok(CardDefinition {
+      name,
+      title: entry.name,
+      color: entry.color,
+      copies,
+      weapon_range: entry.weapon_range,
+      distance_modifier: entry.distance_modifier,
+      triggers: entry.triggers,
+      preconditions: entry.preconditions,
+      effect: entry.effect,
+    }),
+    false => Err(errors),
+  };
+}
+
+/// Whether a card is a new one, only known from its definition
+pub fn is_new_card(name: &shared_types::CardName) -> bool {
+  return matches!(name, shared_types::CardName::Custom(code) if DEFINED_CARD_CODES.contains(code));
+}
+
+/// Whether a precondition decides who a card can target
+fn is_targeting(precondition: &Precondition) -> bool {
+  return matches!(
+    precondition,
+    Precondition::TargetNone
+      | Precondition::TargetAny
+      | Precondition::RequireRange(_)
+      | Precondition::RequireWeaponRange
+  );
+}
+
+fn parse_suit(suit: &str) -> Option<shared_types::CardSuit> {
+  return match suit {
+    "Clubs" => Some(shared_types::CardSuit::Clubs),
+    "Diamonds" => Some(shared_types::CardSuit::Diamonds),
+    "Hearts" => Some(shared_types::CardSuit::Hearts),
+    "Spades" => Some(shared_types::CardSuit::Spades),
+    _ => None,
+  };
+}
+
+fn parse_rank(rank: &str) -> Option<shared_types::CardRank> {
+  use shared_types::CardRank as R;
+  return match rank {
+    "2" => Some(R::N2),
+    "3" => Some(R::N3),
+    "4" => Some(R::N4),
+    "5" => Some(R::N5),
+    "6" => Some(R::N6),
+    "7" => Some(R::N7),
+    "8" => Some(R::N8),
+    "9" => Some(R::N9),
+    "10" => Some(R::N10),
+    "J" => Some(R::J),
+    "Q" => Some(R::Q),
+    "K" => Some(R::K),
+    "A" => Some(R::A),
+    _ => None,
+  };
+}
+
+/// The definition of the card being played, which is the card itself
+/// unless it is being used as a defined card (ex. any card as a Bang with a Lemat)
+fn find_definition<'a>(
+  user_id: &str,
+  cards: &Vec<shared_types::Card>,
+  game_state: &game_engine::types::GameState,
+  game_dict: &'a game_engine::types::GameDictionary,
+) -> Option<&'a CardDefinition> {
+  let card = cards.first()?;
+  return game_state
+    .get_card_uses(user_id, &card.name)
+    .iter()
+    .find_map(|name| game_dict.card_definitions.get(name));
+}
+
+/// Preconditions of a defined card
+fn check_definition(
+  user_id: &str,
+  cards: &Vec<shared_types::Card>,
+  targets: &Vec<String>,
+  game_state: &mut game_engine::types::GameState,
+  game_dict: &game_engine::types::GameDictionary,
+) -> Result<(), game_engine::types::RuleError> {
+  let definition = match find_definition(user_id, cards, game_state, game_dict) {
+    Some(definition) => definition,
+    None => return Err(game_engine::types::RuleError::InvalidSelection),
+  };
+  for precondition in definition.preconditions.iter() {
+    match precondition {
+      Precondition::TargetNone => game_engine::data::check_target_count(targets, 0)?,
+      Precondition::TargetAny => {
+        game_engine::data::check_target_count(targets, 1)?;
+        game_state.check_range(user_id, &targets[0], u8::MAX, game_dict)?;
+      }
+      Precondition::RequireRange(range) => {
+        game_engine::data::check_target_count(targets, 1)?;
+        game_state.check_range(user_id, &targets[0], *range, game_dict)?;
+      }
+      Precondition::RequireWeaponRange => {
+        game_engine::data::check_target_count(targets, 1)?;
+        let range = game_state.get_player_range(user_id, game_dict);
+        game_state.check_range(user_id, &targets[0], range, game_dict)?;
+      }
+      Precondition::RequireWounded => match game_state.player_data.get(user_id) {
+        Some(player_data) if player_data.health < player_data.max_health => {}
+        _ => return Err(game_engine::types::RuleError::FullHealth),
+      },
+      Precondition::BangLimit => {
+        if game_state.bangs_played >= game_state.get_bang_limit()
+          && !game_state.has_unlimited_bangs(user_id)
+        {
+          return Err(game_engine::types::RuleError::BangLimitReached {
+            limit: game_state.get_bang_limit(),
+          });
+        }
+      }
+      Precondition::MoreThanTwoPlayers => {
+        if game_state.player_order.len() <= 2 {
+          return Err(game_engine::types::RuleError::TooFewPlayers);
+        }
+      }
+    }
+  }
+  if definition.effect.contains(&EffectPrimitive::Equip) {
+    game_state.check_equipment(user_id, &cards[0])?;
+  }
+  return Ok(());
+}
+
+/// Effect of a defined card, applying each of its primitives in order
+fn apply_definition(
+  user_id: &str,
+  cards: &Vec<shared_types::Card>,
+  targets: &Vec<String>,
+  game_state: &mut game_engine::types::GameState,
+  game_dict: &game_engine::types::GameDictionary,
+) -> game_engine::types::GameMessages {
+  let definition = match find_definition(user_id, cards, game_state, game_dict) {
+    Some(definition) => definition,
+    None => return Vec::new(),
+  };
+  let mut messages = Vec::new();
+  for primitive in definition.effect.iter() {
+    match primitive {
+      EffectPrimitive::Discard => game_state.discard_cards(user_id, cards),
+      EffectPrimitive::Equip => game_state.equip_cards(user_id, cards, game_dict),
+      EffectPrimitive::Draw(count) => messages.extend(game_state.draw_cards(user_id, *count)),
+      EffectPrimitive::Heal(amount) => game_state.heal_player(user_id, *amount),
+      EffectPrimitive::HealEveryone(amount) => {
+        for player in game_state.player_order.clone() {
+          game_state.heal_player(&player, *amount);
+        }
+      }
+      EffectPrimitive::DealDamage(amount) => {
+        for target in targets.iter() {
+          messages.extend(game_state.damage_player(target, *amount, Some(user_id), game_dict));
+        }
+      }
+      EffectPrimitive::Shoot => messages.extend(game_state.shoot_players(
+        user_id,
+        &definition.name,
+        targets,
+        game_engine::types::EventTrigger::Damage,
+        game_dict,
+      )),
+      EffectPrimitive::ShootEveryone => {
+        let targets = game_state.get_affected_players(user_id, &cards[0]);
+        messages.extend(game_state.shoot_players(
+          user_id,
+          &definition.name,
+          &targets,
+          game_engine::types::EventTrigger::Damage,
+          game_dict,
+        ));
+      }
+      EffectPrimitive::CountBang => game_state.bangs_played += 1,
+    }
+  }
+  return messages;
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::game_engine::test_utils::{card, example_definitions, game_dict, game_state};
+  use crate::shared_types::{CardName, CardSuit, Expansion};
+
+  /// A game dictionary with the example card definitions in place of the built in cards
+  fn defined_game_dict() -> game_engine::types::GameDictionary {
+    return game_engine::expansions::get_game_dictionary(
+      &game_engine::expansions::get_registered_expansions(),
+      &example_definitions(),
+    );
+  }
+
+  #[test]
+  fn invalid_definitions_are_all_reported() {
+    let full_dict = game_dict();
+    let errors = parse_card_definitions(
+      r#"[
+        { "name": "Moonshine", "color": "Brown", "preconditions": ["target_none"], "effect": ["discard"] },
+        { "name": "Moonshine", "color": "Brown", "copies": [{ "suit": "Spades", "rank": "3" }], "preconditions": ["target_none"], "effect": ["discard"] },
+        { "name": "Beer", "color": "Blue", "copies": [{ "suit": "Stars", "rank": "1" }], "effect": ["discard"] },
+        { "name": "Bang", "color": "Brown", "preconditions": ["target_none"], "effect": ["shoot", "discard"] }
+      ]"#,
+      &full_dict,
+    )
+    .err()
+    .unwrap();
+    // a new card needs copies, and can only be defined once
+    assert_eq!(
+      errors
+        .iter()
+        .filter(|error| error.starts_with("Moonshine"))
+        .count(),
+      2
+    );
+    assert_eq!(
+      errors
+        .iter()
+        .filter(|error| error.starts_with("Beer"))
+        .count(),
+      3
+    );
+    assert_eq!(
+      errors
+        .iter()
+        .filter(|error| error.starts_with("Bang"))
+        .count(),
+      2
+    );
+
+    assert!(parse_card_definitions("{ \"name\": \"Beer\" }", &full_dict).is_err());
+  }
+
+  #[test]
+  fn only_a_missing_default_file_means_there_are_no_definitions() {
+    let full_dict = game_dict();
+    assert!(read_card_definitions(Some("cards.example.json"), &full_dict).is_ok());
+    assert!(read_card_definitions(Some("missing.json"), &full_dict).is_err());
+    // a directory can not be read as a definition file
+    assert!(read_card_definitions(Some("src"), &full_dict).is_err());
+  }
+
+  #[test]
+  fn defined_cards_replace_the_built_in_rules() {
+    let game_dict = defined_game_dict();
+    let mut game_state = game_state(4);
+    game_state.player_data.get_mut("p0").unwrap().health = 1;
+    let whisky = card(CardName::Whisky, CardSuit::Hearts);
+    game_state
+      .player_data
+      .get_mut("p0")
+      .unwrap()
+      .hand
+      .push(whisky.clone());
+
+    // a Whisky no longer needs another card to discard
+    let whisky_data = &game_dict.card_dict[&CardName::Whisky];
+    assert!((whisky_data.preconditions)(
+      "p0",
+      &vec![whisky.clone()],
+      &Vec::new(),
+      &mut game_state,
+      &game_dict
+    )
+    .is_ok());
+    (whisky_data.effect)(
+      "p0",
+      &vec![whisky],
+      &Vec::new(),
+      &mut game_state,
+      &game_dict,
+    );
+    assert_eq!(game_state.player_data["p0"].health, 3);
+    assert!(game_state.player_data["p0"].hand.is_empty());
+  }
+
+  #[test]
+  fn defined_shots_are_resolved_like_a_bang() {
+    let game_dict = defined_game_dict();
+    let mut game_state = game_state(7);
+    let tomahawk = card(game_engine::valley_of_shadows::TOMAHAWK, CardSuit::Hearts);
+    game_state
+      .player_data
+      .get_mut("p0")
+      .unwrap()
+      .hand
+      .push(tomahawk.clone());
+    let tomahawk_data = &game_dict.card_dict[&game_engine::valley_of_shadows::TOMAHAWK];
+    assert!((tomahawk_data.preconditions)(
+      "p0",
+      &vec![tomahawk.clone()],
+      &vec![String::from("p3")],
+      &mut game_state,
+      &game_dict
+    )
+    .is_err());
+    (tomahawk_data.effect)(
+      "p0",
+      &vec![tomahawk],
+      &vec![String::from("p2")],
+      &mut game_state,
+      &game_dict,
+    );
+    // with no Missed to respond with, the target takes the shot
+    assert_eq!(game_state.player_data["p2"].health, 3);
+  }
+
+  #[test]
+  fn large_amounts_in_definitions_stay_within_bounds() {
+    let definitions = parse_card_definitions(
+      r#"[
+        { "name": "Beer", "color": "Brown", "preconditions": ["target_none"], "effect": ["discard", { "heal_everyone": 255 }] },
+        { "name": "Mustang", "color": "Blue", "distance_modifier": { "defense": 255 }, "preconditions": ["target_none"], "effect": ["equip"] }
+      ]"#,
+      &game_dict(),
+    )
+    .unwrap_or_else(|errors| panic!("{:?}", errors));
+    let game_dict = game_engine::expansions::get_game_dictionary(&Vec::new(), &definitions);
+    let mut game_state = game_state(4);
+    game_state.player_data.get_mut("p1").unwrap().health = 3;
+    let beer = card(CardName::Beer, CardSuit::Hearts);
+    game_state
+      .player_data
+      .get_mut("p0")
+      .unwrap()
+      .hand
+      .push(beer.clone());
+    (game_dict.card_dict[&CardName::Beer].effect)(
+      "p0",
+      &vec![beer],
+      &Vec::new(),
+      &mut game_state,
+      &game_dict,
+    );
+    assert_eq!(game_state.player_data["p1"].health, 4);
+
+    // a Paul Regret with the defined Mustang can not be seen any further away than that
+    game_state.player_data.get_mut("p1").unwrap().character =
+      crate::shared_types::Character::PaulRegret;
+    game_state
+      .player_data
+      .get_mut("p1")
+      .unwrap()
+      .field
+      .push(card(CardName::Mustang, CardSuit::Hearts));
+    assert_eq!(
+      game_state.get_player_distance("p0", "p1", &game_dict),
+      Some(u8::MAX)
+    );
+  }
+
+  #[test]
+  fn new_cards_are_dealt_and_played() {
+    let definitions = parse_card_definitions(
+      r#"[
+        { "name": "Moonshine", "color": "Brown", "copies": [{ "suit": "Spades", "rank": "3" }], "preconditions": ["target_none"], "effect": ["discard", { "heal": 1 }, { "draw": 1 }] },
+        { "name": "Sawed Off", "color": "Brown", "copies": [{ "suit": "Clubs", "rank": "4" }], "preconditions": [{ "require_range": 1 }], "effect": ["discard", { "deal_damage": 1 }] }
+      ]"#,
+      &game_dict(),
+    )
+    .unwrap_or_else(|errors| panic!("{:?}", errors));
+    let moonshine = CardName::Custom(*DEFINED_CARD_CODES.start());
+    assert_eq!(definitions[&moonshine].title, "Moonshine");
+
+    // the new cards are dealt, and their names are sent along with the game
+    let game_dict = game_engine::expansions::get_game_dictionary(&Vec::new(), &definitions);
+    let client_ids: Vec<String> = (0..4).map(|i| format!("p{}", i)).collect();
+    let new_game = game_engine::types::GameState::new(
+      &client_ids,
+      &Vec::new(),
+      Some(0),
+      shared_types::EmptyDeckRule::default(),
+      &game_dict,
+    )
+    .unwrap();
+    assert!(new_game.deck.iter().any(|card| card.name == moonshine));
+    let custom_cards = new_game.to_game_data().custom_cards;
+    assert_eq!(custom_cards.len(), 2);
+    assert_eq!(custom_cards[0].name, "Moonshine");
+
+    let mut game_state = game_state(4);
+    game_state.deck.push(card(CardName::Bang, CardSuit::Hearts));
+    game_state.player_data.get_mut("p0").unwrap().health = 2;
+    let moonshine_card: shared_types::Card =
+      serde_json::from_str(r#"{ "name": 128, "suit": 4, "rank": 2 }"#).unwrap();
+    assert_eq!(moonshine_card.name, moonshine);
+    game_state
+      .player_data
+      .get_mut("p0")
+      .unwrap()
+      .hand
+      .push(moonshine_card.clone());
+    game_state
+      .apply(
+        "p0",
+        game_engine::actions::Action::PlayCard {
+          cards: vec![moonshine_card],
+          targets: Vec::new(),
+          field_card: None,
+        },
+        &game_dict,
+      )
+      .unwrap();
+    assert_eq!(game_state.player_data["p0"].health, 3);
+    assert_eq!(game_state.player_data["p0"].hand.len(), 1);
+    assert_eq!(game_state.discard[0].name, moonshine);
+  }
+
+  #[test]
+  fn defined_copies_are_only_added_with_their_expansion() {
+    let deck_size = |expansions: Vec<Expansion>, definitions: &CardDefinitions| {
+      let game_dict = game_engine::expansions::get_game_dictionary(&expansions, definitions);
+      return game_engine::data::generate_deck(
+        &expansions,
+        &game_dict,
+        &mut nanorand::WyRand::new_seed(0),
+      )
+      .len();
+    };
+    // the defined Tomahawk comes with Valley of Shadows, so the base game is left as it is
+    assert_eq!(deck_size(Vec::new(), &example_definitions()), 80);
+    assert_eq!(
+      deck_size(vec![Expansion::ValleyOfShadows], &example_definitions()),
+      deck_size(vec![Expansion::ValleyOfShadows], &HashMap::new()) + 1
+    );
+  }
+}
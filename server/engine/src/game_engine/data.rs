@@ -0,0 +1,1712 @@
+use crate::{game_engine, shared_types};
+use nanorand::{WyRand, RNG};
+use std::collections::HashMap;
+
+impl game_engine::types::GameState {
+  /// Removes cards from the hand or field of a player and puts them onto the discard pile
+  pub fn discard_cards(&mut self, player: &str, cards: &Vec<shared_types::Card>) {
+    if let Some(player_data) = self.player_data.get_mut(player) {
+      player_data.remove_cards(cards);
+      self.discard.extend(cards.iter().cloned());
+      for card in cards {
+        self.usable_from.remove(card);
+      }
+    }
+  }
+
+  /// Takes cards off the top of the deck and puts them into the hand of a player
+  pub fn draw_cards(&mut self, player: &str, count: usize) -> game_engine::types::GameMessages {
+    let mut messages = Vec::new();
+    for _ in 0..count {
+      messages.extend(self.refill_deck());
+      match self.deck.pop() {
+        Some(card) => {
+          if let Some(player_data) = self.player_data.get_mut(player) {
+            player_data.hand.push(card);
+          }
+        }
+        None => break,
+      }
+    }
+    return messages;
+  }
+
+  /// Makes a new deck once the deck has run out by shuffling the discard pile.
+  ///
+  /// If the discard pile is empty as well, the empty deck rule of the game decides
+  /// whether a fresh deck is opened or the cards are simply not drawn.
+  pub fn refill_deck(&mut self) -> game_engine::types::GameMessages {
+    if !self.deck.is_empty() {
+      return Vec::new();
+    }
+
+    let event_code = if !self.discard.is_empty() {
+      self.deck.append(&mut self.discard);
+      game_engine::shuffle_deck(&mut self.deck, &mut self.rng);
+      shared_types::ServerEventCode::DeckReshuffled
+    } else {
+      match self.empty_deck_rule {
+        shared_types::EmptyDeckRule::SkipDraw => shared_types::ServerEventCode::DeckEmpty,
+        shared_types::EmptyDeckRule::NewDeck => {
+          // the cards still in play stay where they are, so they are left out of the new deck
+          let mut new_deck = self.deck_list.clone();
+          let in_play = self
+            .player_data
+            .values()
+            .flat_map(|player_data| player_data.hand.iter().chain(player_data.field.iter()))
+            .chain(self.revealed_cards.iter());
+          for card in in_play {
+            if let Some(index) = new_deck.iter().position(|deck_card| deck_card == card) {
+              new_deck.remove(index);
+            }
+          }
+          self.deck = new_deck;
+          game_engine::shuffle_deck(&mut self.deck, &mut self.rng);
+          shared_types::ServerEventCode::DeckReshuffled
+        }
+      }
+    };
+
+    return self.broadcast(&shared_types::ServerEvent::from_event(
+      event_code,
+      shared_types::ServerEventData {
+        game_data: Some(self.to_game_data()),
+        ..Default::default()
+      },
+    ));
+  }
+
+  /// Gives health back to a living player, up to their maximum health
+  pub fn heal_player(&mut self, player: &str, amount: u8) {
+    if let Some(player_data) = self.player_data.get_mut(player) {
+      if player_data.is_alive() {
+        player_data.health = player_data
+          .health
+          .saturating_add(amount)
+          .min(player_data.max_health);
+      }
+    }
+  }
+
+  /// Removes a card at random from the hand of a player
+  pub fn take_random_hand_card(&mut self, player: &str) -> Option<shared_types::Card> {
+    let player_data = self.player_data.get_mut(player)?;
+    if player_data.hand.is_empty() {
+      return None;
+    }
+    let index: usize = self.rng.generate_range(0, player_data.hand.len());
+    return Some(player_data.hand.remove(index));
+  }
+
+  /// The living players in seat order, starting with the player after the given player
+  pub fn get_other_players(&self, player: &str) -> Vec<String> {
+    let seat = match self.player_order.iter().position(|id| id == player) {
+      Some(seat) => seat,
+      None => return Vec::new(),
+    };
+    return (1..self.player_order.len())
+      .map(|offset| self.player_order[(seat + offset) % self.player_order.len()].clone())
+      .collect();
+  }
+
+  /// Whether a player is able to play any number of Bangs during their turn,
+  /// either with a Volcanic or as Willy the Kid
+  pub fn has_unlimited_bangs(&self, player: &str) -> bool {
+    match self.player_data.get(player) {
+      Some(player_data) => {
+        player_data.character == shared_types::Character::WillyTheKid
+          || player_data
+            .field
+            .iter()
+            .any(|card| card.name == shared_types::CardName::Volcanic)
+      }
+      None => false,
+    }
+  }
+
+  /// A player with a Barrel in front of them avoids being shot if a draw check flips a Heart.
+  /// Jourdonnais gets a draw check of his own, on top of one from a Barrel.
+  pub fn avoids_shot(&mut self, player: &str) -> (bool, game_engine::types::GameMessages) {
+    let has_barrel =
+      self.has_equipment(player, shared_types::CardName::Barrel) && self.is_field_active(player);
+    let draw_checks = has_barrel as usize
+      + self.has_character(player, shared_types::Character::Jourdonnais) as usize;
+    let mut messages = Vec::new();
+    for _ in 0..draw_checks {
+      if let Some(draw_check) =
+        self.draw_check(player, |card| card.suit == shared_types::CardSuit::Hearts)
+      {
+        messages.extend(draw_check.messages);
+        if draw_check.passed {
+          return (true, messages);
+        }
+      }
+    }
+    return (false, messages);
+  }
+
+  /// Plays a card against each of the targets, who must respond to it or lose a point of health.
+  ///
+  /// The targets are prompted one at a time in seat order,
+  /// and those with no way of responding take the damage when their turn to respond comes.
+  pub fn shoot_players(
+    &mut self,
+    user_id: &str,
+    card: &shared_types::CardName,
+    targets: &Vec<String>,
+    trigger: game_engine::types::EventTrigger,
+    game_dict: &game_engine::types::GameDictionary,
+  ) -> game_engine::types::GameMessages {
+    return self.open_response_window(
+      game_engine::types::CardEvent {
+        user_id: user_id.to_string(),
+        card: card.clone(),
+        targets: targets.clone(),
+        trigger,
+      },
+      game_dict,
+    );
+  }
+
+  /// The total distance modifiers granted to a player by their field cards and character
+  fn get_distance_modifiers(
+    &self,
+    player: &str,
+    game_dict: &game_engine::types::GameDictionary,
+  ) -> game_engine::types::DistanceModifier {
+    let mut total = game_engine::types::DistanceModifier::default();
+    if let Some(player_data) = self.player_data.get(player) {
+      let card_modifiers = player_data
+        .field
+        .iter()
+        .filter(|_| self.is_field_active(player))
+        .filter_map(|card| game_dict.card_dict.get(&card.name))
+        .map(|card_data| &card_data.distance_modifier);
+      let character_modifier = game_dict
+        .character_dict
+        .get(&player_data.character)
+        .map(|character_data| &character_data.distance_modifier);
+
+      for modifier in card_modifiers.chain(character_modifier) {
+        total.defense = total.defense.saturating_add(modifier.defense);
+        total.offense = total.offense.saturating_add(modifier.offense);
+      }
+    }
+    return total;
+  }
+
+  /// The distance at which one player sees another.
+  ///
+  /// Seats are counted in both directions around the table using the shorter path,
+  /// skipping players who have been eliminated, and then adjusted by the modifiers
+  /// of both players. A player is never closer than 1 to someone else once modified.
+  ///
+  /// Returns `None` if either player is not alive at the table.
+  pub fn get_player_distance(
+    &self,
+    from: &str,
+    to: &str,
+    game_dict: &game_engine::types::GameDictionary,
+  ) -> Option<u8> {
+    let seats: Vec<&String> = self
+      .player_order
+      .iter()
+      .filter(|id| match self.player_data.get(id.as_str()) {
+        Some(player_data) => player_data.is_alive(),
+        None => false,
+      })
+      .collect();
+
+    let from_seat = seats.iter().position(|id| id.as_str() == from)?;
+    let to_seat = seats.iter().position(|id| id.as_str() == to)?;
+    if from_seat == to_seat {
+      return Some(0);
+    }
+    if self.is_distance_fixed() {
+      return Some(1);
+    }
+
+    let gap = from_seat.abs_diff(to_seat);
+    let seat_distance = gap.min(seats.len() - gap) as u8;
+
+    let defense = self.get_distance_modifiers(to, game_dict).defense;
+    let offense = self.get_distance_modifiers(from, game_dict).offense;
+
+    return Some(
+      seat_distance
+        .saturating_add(defense)
+        .saturating_sub(offense)
+        .max(1),
+    );
+  }
+
+  /// The range at which a player can reach others with their weapon.
+  ///
+  /// Players without a weapon equipped use the default range of the Colt .45
+  pub fn get_player_range(
+    &self,
+    player: &str,
+    game_dict: &game_engine::types::GameDictionary,
+  ) -> u8 {
+    return match self.player_data.get(player) {
+      Some(player_data) => player_data
+        .field
+        .iter()
+        .filter_map(|card| game_dict.card_dict.get(&card.name))
+        .filter_map(|card_data| card_data.weapon_range)
+        .max()
+        .unwrap_or(DEFAULT_WEAPON_RANGE),
+      None => DEFAULT_WEAPON_RANGE,
+    };
+  }
+
+  /// Checks that a target is another living player within the given reach of a player
+  pub fn check_range(
+    &self,
+    from: &str,
+    to: &str,
+    range: u8,
+    game_dict: &game_engine::types::GameDictionary,
+  ) -> Result<(), game_engine::types::RuleError> {
+    return match self.get_player_distance(from, to, game_dict) {
+      Some(distance) if distance > range => Err(game_engine::types::RuleError::OutOfRange {
+        target: to.to_string(),
+        distance,
+        range,
+      }),
+      Some(distance) if distance > 0 => Ok(()),
+      _ => Err(game_engine::types::RuleError::InvalidTarget {
+        target: to.to_string(),
+      }),
+    };
+  }
+
+  /// Whether a target is another living player within the given reach of a player
+  pub fn is_in_range(
+    &self,
+    from: &str,
+    to: &str,
+    range: u8,
+    game_dict: &game_engine::types::GameDictionary,
+  ) -> bool {
+    return self.check_range(from, to, range, game_dict).is_ok();
+  }
+
+  /// This could be a card response or a character ability response.
+  pub fn trigger_responses(
+    &mut self,
+    triggers: &Vec<game_engine::types::EventTrigger>,
+    targets: &Vec<String>,
+    game_dict: &game_engine::types::GameDictionary,
+  ) -> HashMap<String, shared_types::ResponseData> {
+    let mut responses: HashMap<String, shared_types::ResponseData> = HashMap::new();
+    // check what the possible actions of anyone in the lobby are when a card is played or effect is activated
+    // ex.. A targets B for Bang!, B has 2 missed cards and C can also choose to play a helping card to save B
+
+    for (player_id, player_data) in self.player_data.iter() {
+      let is_target = targets.contains(player_id);
+      // Search Character abilities that may activate
+      if let Some(character_data) = game_dict.character_dict.get(&player_data.character) {
+        // if the player is one of the targets
+        if triggers
+          .iter()
+          .any(|trigger| character_data.triggers.contains(trigger))
+        {
+          if is_target {
+            if character_data.effect_optional {
+              // let response_data: &mut shared_types::ResponseData = responses
+              //   .entry(player_id.clone())
+              //   .or_insert(shared_types::ResponseData {
+              //     cards: Vec::new(),
+              //     characters: Vec::new(),
+              //   });
+              // response_data.characters.push(character_data.)
+            } else {
+              // character_data.effect activate
+            }
+          } else {
+            // player not target code
+          }
+        }
+      }
+      // Search Character Hand & Field for possible Card responses
+      for card in player_data.card_iter() {
+        // green cards only respond from the field, once they are ready
+        if self.is_green_card(&card.name, game_dict)
+          && !(self.is_card_usable(player_id, card) && self.is_field_active(player_id))
+        {
+          continue;
+        }
+        let card_uses: Vec<&game_engine::types::CardData> = self
+          .get_card_uses(player_id, &card.name)
+          .iter()
+          .filter_map(|name| game_dict.card_dict.get(name))
+          .collect();
+        if !card_uses.is_empty() {
+          if is_target {
+            if card_uses.iter().any(|card_data| {
+              triggers
+                .iter()
+                .any(|trigger| card_data.triggers.contains(trigger))
+            }) {
+              let response_data: &mut shared_types::ResponseData = responses
+                .entry(player_id.clone())
+                .or_insert(shared_types::ResponseData {
+                  cards: Vec::new(),
+                  characters: Vec::new(),
+                });
+              response_data.cards.push(card.name.clone());
+            }
+          } else {
+            // player not target code
+            // another player could play a card to assist or counter a player's move
+          }
+        }
+      }
+    }
+
+    // send messages to players who have the option to use an ability,
+    // execute those that activate by default
+
+    // update all players on the activations that took place, then
+    // send a message to all players and wait for their responses...
+
+    return responses;
+  }
+}
+
+/// The card that a card can always be used as, on top of itself
+pub fn get_stand_in(card: &shared_types::CardName) -> Option<shared_types::CardName> {
+  return match card {
+    shared_types::CardName::Dodge
+    | shared_types::CardName::Bible
+    | shared_types::CardName::IronPlate
+    | shared_types::CardName::Sombrero
+    | shared_types::CardName::TenGallonHat => Some(shared_types::CardName::Missed),
+    _ => None,
+  };
+}
+
+/// The suit and rank of every card in the base game deck
+fn get_base_deck_distribution() -> game_engine::types::DeckDistribution {
+  use shared_types::{CardName, CardRank as R, CardSuit as S};
+  vec![
+    (
+      CardName::Bang,
+      vec![
+        (S::Spades, R::A),
+        (S::Hearts, R::Q),
+        (S::Hearts, R::K),
+        (S::Hearts, R::A),
+        (S::Diamonds, R::N2),
+        (S::Diamonds, R::N3),
+        (S::Diamonds, R::N4),
+        (S::Diamonds, R::N5),
+        (S::Diamonds, R::N6),
+        (S::Diamonds, R::N7),
+        (S::Diamonds, R::N8),
+        (S::Diamonds, R::N9),
+        (S::Diamonds, R::N10),
+        (S::Diamonds, R::J),
+        (S::Diamonds, R::Q),
+        (S::Diamonds, R::K),
+        (S::Diamonds, R::A),
+        (S::Clubs, R::N2),
+        (S::Clubs, R::N3),
+        (S::Clubs, R::N4),
+        (S::Clubs, R::N5),
+        (S::Clubs, R::N6),
+        (S::Clubs, R::N7),
+        (S::Clubs, R::N8),
+        (S::Clubs, R::N9),
+      ],
+    ),
+    (
+      CardName::Missed,
+      vec![
+        (S::Clubs, R::N10),
+        (S::Clubs, R::J),
+        (S::Clubs, R::Q),
+        (S::Clubs, R::K),
+        (S::Clubs, R::A),
+        (S::Spades, R::N2),
+        (S::Spades, R::N3),
+        (S::Spades, R::N4),
+        (S::Spades, R::N5),
+        (S::Spades, R::N6),
+        (S::Spades, R::N7),
+        (S::Spades, R::N8),
+      ],
+    ),
+    (
+      CardName::Beer,
+      vec![
+        (S::Hearts, R::N6),
+        (S::Hearts, R::N7),
+        (S::Hearts, R::N8),
+        (S::Hearts, R::N9),
+        (S::Hearts, R::N10),
+        (S::Hearts, R::J),
+      ],
+    ),
+    (CardName::Saloon, vec![(S::Hearts, R::N5)]),
+    (
+      CardName::Stagecoach,
+      vec![(S::Spades, R::N9), (S::Spades, R::N9)],
+    ),
+    (CardName::WellsFargo, vec![(S::Hearts, R::N3)]),
+    (
+      CardName::Panic,
+      vec![
+        (S::Hearts, R::J),
+        (S::Hearts, R::Q),
+        (S::Hearts, R::A),
+        (S::Diamonds, R::N8),
+      ],
+    ),
+    (
+      CardName::CatBalou,
+      vec![
+        (S::Hearts, R::K),
+        (S::Diamonds, R::N9),
+        (S::Diamonds, R::N10),
+        (S::Diamonds, R::J),
+      ],
+    ),
+    (
+      CardName::Duel,
+      vec![(S::Diamonds, R::Q), (S::Spades, R::J), (S::Clubs, R::N8)],
+    ),
+    (
+      CardName::Indians,
+      vec![(S::Diamonds, R::K), (S::Diamonds, R::A)],
+    ),
+    (CardName::Gatling, vec![(S::Hearts, R::N10)]),
+    (
+      CardName::GeneralStore,
+      vec![(S::Clubs, R::N9), (S::Spades, R::Q)],
+    ),
+    (
+      CardName::Jail,
+      vec![(S::Spades, R::J), (S::Spades, R::N10), (S::Hearts, R::N4)],
+    ),
+    (
+      CardName::Mustang,
+      vec![(S::Hearts, R::N8), (S::Hearts, R::N9)],
+    ),
+    (CardName::Scope, vec![(S::Spades, R::A)]),
+    (CardName::Barrel, vec![(S::Spades, R::Q), (S::Spades, R::K)]),
+    (CardName::Dynamite, vec![(S::Hearts, R::N2)]),
+    (
+      CardName::Volcanic,
+      vec![(S::Spades, R::N10), (S::Clubs, R::N10)],
+    ),
+    (
+      CardName::Schofield,
+      vec![(S::Clubs, R::J), (S::Clubs, R::Q), (S::Spades, R::K)],
+    ),
+    (CardName::Remington, vec![(S::Clubs, R::K)]),
+    (CardName::RevCarabine, vec![(S::Clubs, R::A)]),
+    (CardName::Winchester, vec![(S::Spades, R::N8)]),
+  ]
+}
+
+/// Creates a starting deck of Cards for the game from the base game, the expansions in play
+/// and any copies of cards added by the card definition file
+pub fn generate_deck(
+  expansions: &Vec<shared_types::Expansion>,
+  game_dict: &game_engine::types::GameDictionary,
+  rng: &mut WyRand,
+) -> Vec<shared_types::Card> {
+  let mut deck: Vec<shared_types::Card> = Vec::with_capacity(80);
+  let mut distribution = get_base_deck_distribution();
+  for expansion in expansions {
+    if let Some(expansion_data) = game_dict.expansion_dict.get(expansion) {
+      distribution.extend(expansion_data.deck_distribution.iter().cloned());
+    }
+  }
+  // added in the order of their codes, so that the same seed always deals the same deck
+  let mut definitions: Vec<&game_engine::card_definitions::CardDefinition> =
+    game_dict.card_definitions.values().collect();
+  definitions.sort_by_key(|definition| u8::from(definition.name.clone()));
+  for definition in definitions {
+    distribution.push((definition.name.clone(), definition.copies.clone()));
+  }
+  for (name, cards) in distribution {
+    for (suit, rank) in cards {
+      deck.push(shared_types::Card {
+        name: name.clone(),
+        suit,
+        rank,
+      });
+    }
+  }
+  game_engine::shuffle_deck(&mut deck, rng);
+  return deck;
+}
+
+/// The range of a player with no weapon equipped
+const DEFAULT_WEAPON_RANGE: u8 = 1;
+
+/// The number of cards Kit Carlson looks at during the draw phase
+const KIT_CARLSON_REVEAL_COUNT: usize = 3;
+
+/// The number of cards Sid Ketchum discards to regain a life point
+const SID_KETCHUM_DISCARD_COUNT: usize = 2;
+
+/// A dictionary of cards to their Color and Function
+///
+/// Should always remain read-only in concept,
+/// so a lock is not needed.
+pub fn get_card_dictionary() -> game_engine::types::CardDictionary {
+  let mut card_dict = HashMap::new();
+  //===============================
+  // Bang
+  //===============================
+  card_dict.insert(
+    shared_types::CardName::Bang,
+    game_engine::types::CardData {
+      color: game_engine::types::CardColor::Brown,
+      weapon_range: None,
+      distance_modifier: game_engine::types::DistanceModifier::default(),
+      triggers: vec![game_engine::types::EventTrigger::Challenge],
+      preconditions: |user_id, _cards, targets, game_state, game_dict| {
+        if !game_state.player_data.contains_key(user_id) {
+          return Err(game_engine::types::RuleError::CardNotInHand);
+        }
+        check_target_count(targets, 1)?;
+        if game_state.bangs_played >= game_state.get_bang_limit()
+          && !game_state.has_unlimited_bangs(user_id)
+        {
+          return Err(game_engine::types::RuleError::BangLimitReached {
+            limit: game_state.get_bang_limit(),
+          });
+        }
+        let range = game_state.get_player_range(user_id, game_dict);
+        return game_state.check_range(user_id, &targets[0], range, game_dict);
+      },
+      effect: |user_id, cards, targets, game_state, game_dict| {
+        game_state.bangs_played += 1;
+        game_state.discard_cards(user_id, cards);
+        return game_state.shoot_players(
+          user_id,
+          &shared_types::CardName::Bang,
+          targets,
+          game_engine::types::EventTrigger::Damage,
+          game_dict,
+        );
+      },
+      update: resolve_shot,
+    },
+  );
+  //===============================
+  // Hatchet
+  //===============================
+  card_dict.insert(
+    shared_types::CardName::Hatchet,
+    game_engine::types::CardData {
+      color: game_engine::types::CardColor::Brown,
+      weapon_range: None,
+      distance_modifier: game_engine::types::DistanceModifier::default(),
+      triggers: vec![game_engine::types::EventTrigger::Damage],
+      preconditions: |user_id, _cards, targets, game_state, game_dict| {
+        if !game_state.player_data.contains_key(user_id) {
+          return Err(game_engine::types::RuleError::CardNotInHand);
+        }
+        check_target_count(targets, 1)?;
+        return game_state.check_range(user_id, &targets[0], 1, game_dict);
+      },
+      effect: |_user_id, _cards, _targets, _game_state, _game_dict| {
+        return Vec::new();
+      },
+      update: |_user_id, _cards, _targets, _game_state, _game_dict| Vec::new(),
+    },
+  );
+  //===============================
+  // Missed
+  //===============================
+  card_dict.insert(
+    shared_types::CardName::Missed,
+    game_engine::types::CardData {
+      color: game_engine::types::CardColor::Brown,
+      weapon_range: None,
+      distance_modifier: game_engine::types::DistanceModifier::default(),
+      triggers: vec![game_engine::types::EventTrigger::Damage],
+      preconditions: |_user_id, _cards, _targets, _game_state, _game_dict| {
+        return Err(game_engine::types::RuleError::ResponseOnly);
+      },
+      effect: |_user_id, _cards, _targets, _game_state, _game_dict| Vec::new(),
+      update: |_user_id, _cards, _targets, _game_state, _game_dict| Vec::new(),
+    },
+  );
+  //===============================
+  // Beer
+  //===============================
+  card_dict.insert(
+    shared_types::CardName::Beer,
+    game_engine::types::CardData {
+      color: game_engine::types::CardColor::Brown,
+      weapon_range: None,
+      distance_modifier: game_engine::types::DistanceModifier::default(),
+      triggers: vec![],
+      preconditions: |user_id, _cards, targets, game_state, _game_dict| {
+        check_target_count(targets, 0)?;
+        if game_state.player_order.len() <= 2 {
+          return Err(game_engine::types::RuleError::TooFewPlayers);
+        }
+        match game_state.player_data.get(user_id) {
+          Some(player_data) if player_data.health < player_data.max_health => Ok(()),
+          _ => Err(game_engine::types::RuleError::FullHealth),
+        }
+      },
+      effect: |user_id, cards, _targets, game_state, _game_dict| {
+        game_state.discard_cards(user_id, cards);
+        // Tequila Joe regains two life points instead
+        let amount = match game_state.has_character(user_id, shared_types::Character::TequilaJoe) {
+          true => 2,
+          false => 1,
+        };
+        game_state.heal_player(user_id, amount);
+        return game_state.draw_for_beer();
+      },
+      update: |_user_id, _cards, _targets, _game_state, _game_dict| Vec::new(),
+    },
+  );
+  //===============================
+  // Saloon
+  //===============================
+  card_dict.insert(
+    shared_types::CardName::Saloon,
+    game_engine::types::CardData {
+      color: game_engine::types::CardColor::Brown,
+      weapon_range: None,
+      distance_modifier: game_engine::types::DistanceModifier::default(),
+      triggers: vec![],
+      preconditions: |_user_id, _cards, targets, _game_state, _game_dict| {
+        return check_target_count(targets, 0);
+      },
+      effect: |user_id, cards, _targets, game_state, _game_dict| {
+        game_state.discard_cards(user_id, cards);
+        for player in game_state.player_order.clone() {
+          game_state.heal_player(&player, 1);
+        }
+        return Vec::new();
+      },
+      update: |_user_id, _cards, _targets, _game_state, _game_dict| Vec::new(),
+    },
+  );
+  //===============================
+  // Stagecoach & Wells Fargo
+  //===============================
+  card_dict.insert(
+    shared_types::CardName::Stagecoach,
+    game_engine::types::CardData {
+      color: game_engine::types::CardColor::Brown,
+      weapon_range: None,
+      distance_modifier: game_engine::types::DistanceModifier::default(),
+      triggers: vec![],
+      preconditions: no_target_preconditions,
+      effect: |user_id, cards, _targets, game_state, _game_dict| {
+        game_state.discard_cards(user_id, cards);
+        return game_state.draw_cards(user_id, 2);
+      },
+      update: |_user_id, _cards, _targets, _game_state, _game_dict| Vec::new(),
+    },
+  );
+  card_dict.insert(
+    shared_types::CardName::WellsFargo,
+    game_engine::types::CardData {
+      color: game_engine::types::CardColor::Brown,
+      weapon_range: None,
+      distance_modifier: game_engine::types::DistanceModifier::default(),
+      triggers: vec![],
+      preconditions: no_target_preconditions,
+      effect: |user_id, cards, _targets, game_state, _game_dict| {
+        game_state.discard_cards(user_id, cards);
+        return game_state.draw_cards(user_id, 3);
+      },
+      update: |_user_id, _cards, _targets, _game_state, _game_dict| Vec::new(),
+    },
+  );
+  //===============================
+  // Panic
+  //===============================
+  card_dict.insert(
+    shared_types::CardName::Panic,
+    game_engine::types::CardData {
+      color: game_engine::types::CardColor::Brown,
+      weapon_range: None,
+      distance_modifier: game_engine::types::DistanceModifier::default(),
+      triggers: vec![],
+      preconditions: |user_id, _cards, targets, game_state, game_dict| {
+        check_target_count(targets, 1)?;
+        game_state.check_range(user_id, &targets[0], 1, game_dict)?;
+        return game_state.check_field_selection(&targets[0]);
+      },
+      effect: |user_id, cards, targets, game_state, _game_dict| {
+        game_state.discard_cards(user_id, cards);
+        if let Some(card) = game_state.take_selected_card(&targets[0]) {
+          if let Some(player_data) = game_state.player_data.get_mut(user_id) {
+            player_data.hand.push(card);
+          }
+        }
+        return Vec::new();
+      },
+      update: |_user_id, _cards, _targets, _game_state, _game_dict| Vec::new(),
+    },
+  );
+  //===============================
+  // Cat Balou
+  //===============================
+  card_dict.insert(
+    shared_types::CardName::CatBalou,
+    game_engine::types::CardData {
+      color: game_engine::types::CardColor::Brown,
+      weapon_range: None,
+      distance_modifier: game_engine::types::DistanceModifier::default(),
+      triggers: vec![],
+      preconditions: |user_id, _cards, targets, game_state, game_dict| {
+        check_target_count(targets, 1)?;
+        game_state.check_range(user_id, &targets[0], u8::MAX, game_dict)?;
+        return game_state.check_field_selection(&targets[0]);
+      },
+      effect: |user_id, cards, targets, game_state, _game_dict| {
+        game_state.discard_cards(user_id, cards);
+        if let Some(card) = game_state.take_selected_card(&targets[0]) {
+          game_state.discard.push(card);
+        }
+        return Vec::new();
+      },
+      update: |_user_id, _cards, _targets, _game_state, _game_dict| Vec::new(),
+    },
+  );
+  //===============================
+  // Duel
+  //===============================
+  card_dict.insert(
+    shared_types::CardName::Duel,
+    game_engine::types::CardData {
+      color: game_engine::types::CardColor::Brown,
+      weapon_range: None,
+      distance_modifier: game_engine::types::DistanceModifier::default(),
+      triggers: vec![],
+      preconditions: |user_id, _cards, targets, game_state, game_dict| {
+        check_target_count(targets, 1)?;
+        return game_state.check_range(user_id, &targets[0], u8::MAX, game_dict);
+      },
+      effect: |user_id, cards, targets, game_state, game_dict| {
+        game_state.discard_cards(user_id, cards);
+        return game_state.shoot_players(
+          user_id,
+          &cards[0].name,
+          targets,
+          game_engine::types::EventTrigger::Challenge,
+          game_dict,
+        );
+      },
+      update: |user_id, cards, targets, game_state, game_dict| {
+        let responder = &targets[0];
+        if !cards
+          .iter()
+          .any(|card| game_state.counts_as(responder, &card.name, &shared_types::CardName::Bang))
+        {
+          return game_state.damage_player(responder, 1, Some(user_id), game_dict);
+        }
+        game_state.discard_cards(responder, cards);
+        // the duel continues with the roles of the two players swapped
+        if let Some(card_event) = game_state.event_stack.last_mut() {
+          card_event.user_id = responder.clone();
+        }
+        let responses = game_state.trigger_responses(
+          &vec![game_engine::types::EventTrigger::Challenge],
+          &vec![user_id.to_string()],
+          game_dict,
+        );
+        if responses.is_empty() {
+          return game_state.damage_player(user_id, 1, Some(responder), game_dict);
+        }
+        return game_state.request_responses(responses);
+      },
+    },
+  );
+  //===============================
+  // Indians & Gatling
+  //===============================
+  card_dict.insert(
+    shared_types::CardName::Indians,
+    game_engine::types::CardData {
+      color: game_engine::types::CardColor::Brown,
+      weapon_range: None,
+      distance_modifier: game_engine::types::DistanceModifier::default(),
+      triggers: vec![],
+      preconditions: no_target_preconditions,
+      effect: |user_id, cards, _targets, game_state, game_dict| {
+        game_state.discard_cards(user_id, cards);
+        let targets = game_state.get_affected_players(user_id, &cards[0]);
+        return game_state.shoot_players(
+          user_id,
+          &cards[0].name,
+          &targets,
+          game_engine::types::EventTrigger::Challenge,
+          game_dict,
+        );
+      },
+      update: |user_id, cards, targets, game_state, game_dict| {
+        if cards
+          .iter()
+          .any(|card| game_state.counts_as(&targets[0], &card.name, &shared_types::CardName::Bang))
+        {
+          game_state.discard_cards(&targets[0], cards);
+          return Vec::new();
+        }
+        return game_state.damage_player(&targets[0], 1, Some(user_id), game_dict);
+      },
+    },
+  );
+  card_dict.insert(
+    shared_types::CardName::Gatling,
+    game_engine::types::CardData {
+      color: game_engine::types::CardColor::Brown,
+      weapon_range: None,
+      distance_modifier: game_engine::types::DistanceModifier::default(),
+      triggers: vec![],
+      preconditions: no_target_preconditions,
+      effect: |user_id, cards, _targets, game_state, game_dict| {
+        game_state.discard_cards(user_id, cards);
+        let targets = game_state.get_affected_players(user_id, &cards[0]);
+        return game_state.shoot_players(
+          user_id,
+          &cards[0].name,
+          &targets,
+          game_engine::types::EventTrigger::Damage,
+          game_dict,
+        );
+      },
+      update: resolve_shot,
+    },
+  );
+  //===============================
+  // General Store
+  //===============================
+  card_dict.insert(
+    shared_types::CardName::GeneralStore,
+    game_engine::types::CardData {
+      color: game_engine::types::CardColor::Brown,
+      weapon_range: None,
+      distance_modifier: game_engine::types::DistanceModifier::default(),
+      triggers: vec![],
+      preconditions: no_target_preconditions,
+      effect: |user_id, cards, _targets, game_state, game_dict| {
+        game_state.discard_cards(user_id, cards);
+        // a card is revealed for each player, who take turns picking one,
+        // starting with the player of the General Store
+        let mut players = vec![user_id.to_string()];
+        players.extend(game_state.get_other_players(user_id));
+        let mut messages = Vec::new();
+        for _ in 0..players.len() {
+          messages.extend(game_state.refill_deck());
+          if let Some(card) = game_state.deck.pop() {
+            game_state.revealed_cards.push(card);
+          }
+        }
+        messages.extend(game_state.open_response_window(
+          game_engine::types::CardEvent {
+            user_id: user_id.to_string(),
+            card: cards[0].name.clone(),
+            targets: players,
+            trigger: game_engine::types::EventTrigger::Selection,
+          },
+          game_dict,
+        ));
+        return messages;
+      },
+      update: |_user_id, cards, targets, game_state, _game_dict| {
+        // a player who passes takes the first of the revealed cards
+        let index = cards
+          .first()
+          .and_then(|card| {
+            game_state
+              .revealed_cards
+              .iter()
+              .position(|revealed| revealed == card)
+          })
+          .unwrap_or(0);
+        if index < game_state.revealed_cards.len() {
+          let card = game_state.revealed_cards.remove(index);
+          if let Some(player_data) = game_state.player_data.get_mut(&targets[0]) {
+            player_data.hand.push(card);
+          }
+        }
+        return Vec::new();
+      },
+    },
+  );
+  //===============================
+  // Jail
+  //===============================
+  card_dict.insert(
+    shared_types::CardName::Jail,
+    game_engine::types::CardData {
+      color: game_engine::types::CardColor::Blue,
+      weapon_range: None,
+      distance_modifier: game_engine::types::DistanceModifier::default(),
+      triggers: vec![],
+      preconditions: |user_id, cards, targets, game_state, game_dict| {
+        check_target_count(targets, 1)?;
+        game_state.check_range(user_id, &targets[0], u8::MAX, game_dict)?;
+        match game_state.player_data.get(&targets[0]) {
+          Some(player_data) if player_data.role == shared_types::Role::Sheriff => {
+            Err(game_engine::types::RuleError::SheriffTargeted {
+              target: targets[0].clone(),
+            })
+          }
+          _ => game_state.check_equipment(&targets[0], &cards[0]),
+        }
+      },
+      effect: |user_id, cards, targets, game_state, _game_dict| {
+        if let Some(player_data) = game_state.player_data.get_mut(user_id) {
+          player_data.remove_cards(cards);
+        }
+        if let Some(player_data) = game_state.player_data.get_mut(&targets[0]) {
+          player_data.field.extend(cards.iter().cloned());
+        }
+        return Vec::new();
+      },
+      update: |_user_id, _cards, _targets, _game_state, _game_dict| Vec::new(),
+    },
+  );
+  //===============================
+  // Dynamite
+  //===============================
+  // placed in front of the player who plays it,
+  // then resolved at the start of each turn of whoever has it
+  card_dict.insert(
+    shared_types::CardName::Dynamite,
+    equipment_card_data(None, game_engine::types::DistanceModifier::default()),
+  );
+  //===============================
+  // Barrel, Mustang & Scope
+  //===============================
+  card_dict.insert(
+    shared_types::CardName::Barrel,
+    equipment_card_data(None, game_engine::types::DistanceModifier::default()),
+  );
+  card_dict.insert(
+    shared_types::CardName::Mustang,
+    equipment_card_data(
+      None,
+      game_engine::types::DistanceModifier {
+        defense: 1,
+        offense: 0,
+      },
+    ),
+  );
+  card_dict.insert(
+    shared_types::CardName::Scope,
+    equipment_card_data(
+      None,
+      game_engine::types::DistanceModifier {
+        defense: 0,
+        offense: 1,
+      },
+    ),
+  );
+  //===============================
+  // Weapons
+  //===============================
+  for (weapon, range) in [
+    (shared_types::CardName::Volcanic, 1),
+    (shared_types::CardName::Schofield, 2),
+    (shared_types::CardName::Remington, 3),
+    (shared_types::CardName::RevCarabine, 4),
+    (shared_types::CardName::Winchester, 5),
+  ]
+  .iter()
+  {
+    card_dict.insert(
+      weapon.clone(),
+      equipment_card_data(
+        Some(*range),
+        game_engine::types::DistanceModifier::default(),
+      ),
+    );
+  }
+  return card_dict;
+}
+
+/// Preconditions for cards that can only be used in response to another card
+pub fn response_only_preconditions(
+  _user_id: &str,
+  _cards: &Vec<shared_types::Card>,
+  _targets: &Vec<String>,
+  _game_state: &mut game_engine::types::GameState,
+  _game_dict: &game_engine::types::GameDictionary,
+) -> Result<(), game_engine::types::RuleError> {
+  return Err(game_engine::types::RuleError::ResponseOnly);
+}
+
+/// Preconditions for cards that are played without choosing any Targets
+pub fn no_target_preconditions(
+  _user_id: &str,
+  _cards: &Vec<shared_types::Card>,
+  targets: &Vec<String>,
+  _game_state: &mut game_engine::types::GameState,
+  _game_dict: &game_engine::types::GameDictionary,
+) -> Result<(), game_engine::types::RuleError> {
+  return check_target_count(targets, 0);
+}
+
+/// Checks that a card was played at the number of Targets it takes
+pub fn check_target_count(
+  targets: &Vec<String>,
+  expected: usize,
+) -> Result<(), game_engine::types::RuleError> {
+  if targets.len() != expected {
+    return Err(game_engine::types::RuleError::WrongTargetCount {
+      expected,
+      given: targets.len(),
+    });
+  }
+  return Ok(());
+}
+
+/// Resolves the response of a player who was shot at,
+/// who takes the damage unless they responded with a Missed.
+///
+/// A Bang from Slab the Killer takes two Missed to avoid.
+pub fn resolve_shot(
+  user_id: &str,
+  cards: &Vec<shared_types::Card>,
+  targets: &Vec<String>,
+  game_state: &mut game_engine::types::GameState,
+  game_dict: &game_engine::types::GameDictionary,
+) -> game_engine::types::GameMessages {
+  let missed_required = match game_state.event_stack.last() {
+    Some(card_event)
+      if card_event.card == shared_types::CardName::Bang
+        && game_state.has_character(user_id, shared_types::Character::SlabTheKiller) =>
+    {
+      2
+    }
+    _ => 1,
+  };
+  let missed_count = cards
+    .iter()
+    .filter(|card| game_state.counts_as(&targets[0], &card.name, &shared_types::CardName::Missed))
+    .count();
+  if missed_count >= missed_required {
+    game_state.discard_cards(&targets[0], cards);
+    return Vec::new();
+  }
+  return game_state.damage_player(&targets[0], 1, Some(user_id), game_dict);
+}
+
+/// Creates the data for a Blue card whose only effect is being equipped onto the field of the player
+pub fn equipment_card_data(
+  weapon_range: Option<u8>,
+  distance_modifier: game_engine::types::DistanceModifier,
+) -> game_engine::types::CardData {
+  game_engine::types::CardData {
+    color: game_engine::types::CardColor::Blue,
+    weapon_range,
+    distance_modifier,
+    triggers: vec![],
+    preconditions: |user_id, cards, targets, game_state, _game_dict| {
+      check_target_count(targets, 0)?;
+      return game_state.check_equipment(user_id, &cards[0]);
+    },
+    effect: |user_id, cards, _targets, game_state, game_dict| {
+      game_state.equip_cards(user_id, cards, game_dict);
+      return Vec::new();
+    },
+    update: |_user_id, _cards, _targets, _game_state, _game_dict| Vec::new(),
+  }
+}
+
+pub fn get_character_dictionary() -> game_engine::types::CharacterDictionary {
+  let mut character_dict = HashMap::new();
+  //===============================
+  // Bart Cassidy
+  //===============================
+  character_dict.insert(
+    shared_types::Character::BartCassidy,
+    game_engine::types::CharacterData {
+      hp: 4,
+      triggers: vec![game_engine::types::EventTrigger::HealthLost],
+      effect_optional: false,
+      effect: |player, context, game_state, _game_dict| {
+        return Ok(game_state.draw_cards(player, context.amount as usize));
+      },
+      distance_modifier: game_engine::types::DistanceModifier::default(),
+    },
+  );
+  //===============================
+  // Black Jack
+  //===============================
+  character_dict.insert(
+    shared_types::Character::BlackJack,
+    game_engine::types::CharacterData {
+      hp: 4,
+      triggers: vec![game_engine::types::EventTrigger::DrawPhase],
+      effect_optional: false,
+      effect: |player, _context, game_state, _game_dict| {
+        let hand_size = match game_state.player_data.get(player) {
+          Some(player_data) => player_data.hand.len(),
+          None => return Ok(Vec::new()),
+        };
+        let mut messages = game_state.draw_cards(player, game_state.get_draw_count(player));
+        game_state.turn_phase = shared_types::TurnPhase::Play;
+        // the second card drawn is shown to everyone, and is worth another card if it is red,
+        // which can only happen when at least two cards were drawn
+        let second_card = match game_state.player_data[player].hand.get(hand_size + 1) {
+          Some(card) => card.clone(),
+          None => return Ok(messages),
+        };
+        if second_card.suit == shared_types::CardSuit::Hearts
+          || second_card.suit == shared_types::CardSuit::Diamonds
+        {
+          messages.extend(game_state.draw_cards(player, 1));
+        }
+        messages.extend(game_state.broadcast(&shared_types::ServerEvent::from_event(
+          shared_types::ServerEventCode::CardsRevealed,
+          shared_types::ServerEventData {
+            client_id: Some(player.to_string()),
+            cards: Some(vec![second_card]),
+            ..Default::default()
+          },
+        )));
+        return Ok(messages);
+      },
+      distance_modifier: game_engine::types::DistanceModifier::default(),
+    },
+  );
+  //===============================
+  // Calamity Janet
+  //===============================
+  character_dict.insert(
+    shared_types::Character::CalamityJanet,
+    passive_character_data(4, game_engine::types::DistanceModifier::default()),
+  );
+  //===============================
+  // El Gringo
+  //===============================
+  character_dict.insert(
+    shared_types::Character::ElGringo,
+    game_engine::types::CharacterData {
+      hp: 3,
+      triggers: vec![game_engine::types::EventTrigger::HealthLost],
+      effect_optional: false,
+      effect: |player, context, game_state, _game_dict| {
+        // takes a card from the hand of the player who did the damage for each life point lost
+        if let Some(source) = context.source.filter(|source| *source != player) {
+          for _ in 0..context.amount {
+            if let Some(card) = game_state.take_random_hand_card(source) {
+              if let Some(player_data) = game_state.player_data.get_mut(player) {
+                player_data.hand.push(card);
+              }
+            }
+          }
+        }
+        return Ok(Vec::new());
+      },
+      distance_modifier: game_engine::types::DistanceModifier::default(),
+    },
+  );
+  //===============================
+  // Jesse Jones
+  //===============================
+  character_dict.insert(
+    shared_types::Character::JesseJones,
+    game_engine::types::CharacterData {
+      hp: 4,
+      triggers: vec![game_engine::types::EventTrigger::DrawPhase],
+      effect_optional: true,
+      effect: |player, context, game_state, _game_dict| {
+        // the first card may be taken from the hand of the chosen player
+        let deck_card_count = match context.targets.len() {
+          0 => game_state.get_draw_count(player),
+          1 if game_state
+            .get_other_players(player)
+            .contains(&context.targets[0]) =>
+          {
+            let card = game_state
+              .take_random_hand_card(&context.targets[0])
+              .ok_or(game_engine::types::RuleError::InvalidSelection)?;
+            if let Some(player_data) = game_state.player_data.get_mut(player) {
+              player_data.hand.push(card);
+            }
+            game_state.get_draw_count(player).saturating_sub(1)
+          }
+          _ => return Err(game_engine::types::RuleError::InvalidSelection),
+        };
+        let messages = game_state.draw_cards(player, deck_card_count);
+        game_state.turn_phase = shared_types::TurnPhase::Play;
+        return Ok(messages);
+      },
+      distance_modifier: game_engine::types::DistanceModifier::default(),
+    },
+  );
+  //===============================
+  // Jourdonnais
+  //===============================
+  character_dict.insert(
+    shared_types::Character::Jourdonnais,
+    passive_character_data(4, game_engine::types::DistanceModifier::default()),
+  );
+  //===============================
+  // Kit Carlson
+  //===============================
+  character_dict.insert(
+    shared_types::Character::KitCarlson,
+    game_engine::types::CharacterData {
+      hp: 4,
+      triggers: vec![game_engine::types::EventTrigger::DrawPhase],
+      effect_optional: false,
+      effect: |player, context, game_state, _game_dict| {
+        // the top three cards of the deck are shown to the player first,
+        // then the player keeps two of them and the other goes back on the deck
+        if game_state.revealed_cards.is_empty() {
+          let mut messages = Vec::new();
+          for _ in 0..KIT_CARLSON_REVEAL_COUNT {
+            messages.extend(game_state.refill_deck());
+            if let Some(card) = game_state.deck.pop() {
+              game_state.revealed_cards.push(card);
+            }
+          }
+          if game_state.revealed_cards.is_empty() {
+            game_state.turn_phase = shared_types::TurnPhase::Play;
+            return Ok(messages);
+          }
+          messages.push((
+            player.to_string(),
+            shared_types::ServerEvent::from_event(
+              shared_types::ServerEventCode::CardsRevealed,
+              shared_types::ServerEventData {
+                client_id: Some(player.to_string()),
+                cards: Some(game_state.revealed_cards.clone()),
+                ..Default::default()
+              },
+            ),
+          ));
+          return Ok(messages);
+        }
+
+        let keep_count = game_state
+          .get_draw_count(player)
+          .min(game_state.revealed_cards.len());
+        let mut remaining = game_state.revealed_cards.clone();
+        for card in context.cards {
+          match remaining.iter().position(|revealed| revealed == card) {
+            Some(index) => remaining.remove(index),
+            None => return Err(game_engine::types::RuleError::InvalidSelection),
+          };
+        }
+        if context.cards.len() != keep_count {
+          return Err(game_engine::types::RuleError::InvalidSelection);
+        }
+        if let Some(player_data) = game_state.player_data.get_mut(player) {
+          player_data.hand.extend(context.cards.iter().cloned());
+        }
+        game_state.deck.extend(remaining);
+        game_state.revealed_cards.clear();
+        game_state.turn_phase = shared_types::TurnPhase::Play;
+        return Ok(Vec::new());
+      },
+      distance_modifier: game_engine::types::DistanceModifier::default(),
+    },
+  );
+  //===============================
+  // Lucky Duke
+  //===============================
+  character_dict.insert(
+    shared_types::Character::LuckyDuke,
+    passive_character_data(4, game_engine::types::DistanceModifier::default()),
+  );
+  //===============================
+  // Paul Regret
+  //===============================
+  character_dict.insert(
+    shared_types::Character::PaulRegret,
+    passive_character_data(
+      3,
+      game_engine::types::DistanceModifier {
+        defense: 1,
+        offense: 0,
+      },
+    ),
+  );
+  //===============================
+  // Pedro Ramirez
+  //===============================
+  character_dict.insert(
+    shared_types::Character::PedroRamirez,
+    game_engine::types::CharacterData {
+      hp: 4,
+      triggers: vec![game_engine::types::EventTrigger::DrawPhase],
+      effect_optional: true,
+      effect: |player, context, game_state, _game_dict| {
+        // the first card may be taken from the top of the discard pile
+        let deck_card_count = match context.cards.as_slice() {
+          [] => game_state.get_draw_count(player),
+          [card] if game_state.discard.last() == Some(card) => {
+            if let Some(card) = game_state.discard.pop() {
+              if let Some(player_data) = game_state.player_data.get_mut(player) {
+                player_data.hand.push(card);
+              }
+            }
+            game_state.get_draw_count(player).saturating_sub(1)
+          }
+          _ => return Err(game_engine::types::RuleError::InvalidSelection),
+        };
+        let messages = game_state.draw_cards(player, deck_card_count);
+        game_state.turn_phase = shared_types::TurnPhase::Play;
+        return Ok(messages);
+      },
+      distance_modifier: game_engine::types::DistanceModifier::default(),
+    },
+  );
+  //===============================
+  // Rose Doolan
+  //===============================
+  character_dict.insert(
+    shared_types::Character::RoseDoolan,
+    passive_character_data(
+      4,
+      game_engine::types::DistanceModifier {
+        defense: 0,
+        offense: 1,
+      },
+    ),
+  );
+  //===============================
+  // Sid Ketchum
+  //===============================
+  character_dict.insert(
+    shared_types::Character::SidKetchum,
+    game_engine::types::CharacterData {
+      hp: 4,
+      triggers: vec![game_engine::types::EventTrigger::Activated],
+      effect_optional: true,
+      effect: |player, context, game_state, _game_dict| {
+        // discards two cards to regain a life point
+        match game_state.player_data.get(player) {
+          Some(player_data)
+            if context.cards.len() == SID_KETCHUM_DISCARD_COUNT
+              && player_data.has_in_hand(context.cards)
+              && player_data.health < player_data.max_health => {}
+          _ => return Err(game_engine::types::RuleError::InvalidSelection),
+        }
+        game_state.discard_cards(player, context.cards);
+        game_state.heal_player(player, 1);
+        return Ok(Vec::new());
+      },
+      distance_modifier: game_engine::types::DistanceModifier::default(),
+    },
+  );
+  //===============================
+  // Slab the Killer
+  //===============================
+  character_dict.insert(
+    shared_types::Character::SlabTheKiller,
+    passive_character_data(4, game_engine::types::DistanceModifier::default()),
+  );
+  //===============================
+  // Suzy Lafayette
+  //===============================
+  character_dict.insert(
+    shared_types::Character::SuzyLafayette,
+    game_engine::types::CharacterData {
+      hp: 4,
+      triggers: vec![game_engine::types::EventTrigger::HandEmpty],
+      effect_optional: false,
+      effect: |player, _context, game_state, _game_dict| {
+        return Ok(game_state.draw_cards(player, 1));
+      },
+      distance_modifier: game_engine::types::DistanceModifier::default(),
+    },
+  );
+  //===============================
+  // Vulture Sam
+  //===============================
+  character_dict.insert(
+    shared_types::Character::VultureSam,
+    game_engine::types::CharacterData {
+      hp: 4,
+      triggers: vec![game_engine::types::EventTrigger::PlayerEliminated],
+      effect_optional: false,
+      effect: |player, context, game_state, _game_dict| {
+        // takes every card of the eliminated player into their hand
+        let cards: Vec<shared_types::Card> = match context
+          .source
+          .and_then(|source| game_state.player_data.get_mut(source))
+        {
+          Some(eliminated_data) => eliminated_data
+            .hand
+            .drain(..)
+            .chain(eliminated_data.field.drain(..))
+            .collect(),
+          None => return Ok(Vec::new()),
+        };
+        if let Some(player_data) = game_state.player_data.get_mut(player) {
+          player_data.hand.extend(cards);
+        }
+        return Ok(Vec::new());
+      },
+      distance_modifier: game_engine::types::DistanceModifier::default(),
+    },
+  );
+  //===============================
+  // Willy the Kid
+  //===============================
+  character_dict.insert(
+    shared_types::Character::WillyTheKid,
+    passive_character_data(4, game_engine::types::DistanceModifier::default()),
+  );
+  return character_dict;
+}
+
+/// Creates the data for a character whose ability changes the rules of the game
+/// and is checked by the game logic rather than activated by a trigger
+pub fn passive_character_data(
+  hp: u8,
+  distance_modifier: game_engine::types::DistanceModifier,
+) -> game_engine::types::CharacterData {
+  game_engine::types::CharacterData {
+    hp,
+    triggers: vec![],
+    effect_optional: false,
+    effect: |_player, _context, _game_state, _game_dict| Ok(Vec::new()),
+    distance_modifier,
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::game_engine::test_utils::{game_dict, game_state};
+  use crate::game_engine::types::GameState;
+
+  fn equip(game_state: &mut GameState, player: &str, name: shared_types::CardName) {
+    if let Some(player_data) = game_state.player_data.get_mut(player) {
+      player_data.field.push(shared_types::Card {
+        name,
+        suit: shared_types::CardSuit::Spades,
+        rank: shared_types::CardRank::A,
+      });
+    }
+  }
+
+  #[test]
+  fn distance_is_the_shorter_way_around_the_table() {
+    let game_dict = game_dict();
+    for player_count in 4..=7 {
+      let game_state = game_state(player_count);
+      for seat in 1..player_count {
+        let expected = seat.min(player_count - seat) as u8;
+        assert_eq!(
+          game_state.get_player_distance("p0", &format!("p{}", seat), &game_dict),
+          Some(expected),
+          "{} players, seat {}",
+          player_count,
+          seat
+        );
+      }
+    }
+  }
+
+  #[test]
+  fn distance_is_symmetric_without_modifiers() {
+    let game_dict = game_dict();
+    for player_count in 4..=7 {
+      let game_state = game_state(player_count);
+      for a in 0..player_count {
+        for b in 0..player_count {
+          let (a, b) = (format!("p{}", a), format!("p{}", b));
+          assert_eq!(
+            game_state.get_player_distance(&a, &b, &game_dict),
+            game_state.get_player_distance(&b, &a, &game_dict),
+          );
+        }
+      }
+    }
+  }
+
+  #[test]
+  fn distance_skips_eliminated_players() {
+    let game_dict = game_dict();
+    let mut game_state = game_state(6);
+    assert_eq!(game_state.get_player_distance("p0", "p3", &game_dict), Some(3));
+
+    game_state.player_data.get_mut("p1").unwrap().health = 0;
+    assert_eq!(game_state.get_player_distance("p0", "p2", &game_dict), Some(1));
+    assert_eq!(game_state.get_player_distance("p0", "p3", &game_dict), Some(2));
+    assert_eq!(game_state.get_player_distance("p0", "p1", &game_dict), None);
+  }
+
+  #[test]
+  fn mustang_and_scope_modify_distance() {
+    let game_dict = game_dict();
+    let mut game_state = game_state(5);
+
+    equip(&mut game_state, "p1", shared_types::CardName::Mustang);
+    assert_eq!(game_state.get_player_distance("p0", "p1", &game_dict), Some(2));
+    assert_eq!(game_state.get_player_distance("p1", "p0", &game_dict), Some(1));
+
+    equip(&mut game_state, "p0", shared_types::CardName::Scope);
+    assert_eq!(game_state.get_player_distance("p0", "p1", &game_dict), Some(1));
+    assert_eq!(game_state.get_player_distance("p0", "p2", &game_dict), Some(1));
+    assert_eq!(game_state.get_player_distance("p0", "p4", &game_dict), Some(1));
+  }
+
+  #[test]
+  fn range_comes_from_the_equipped_weapon() {
+    let game_dict = game_dict();
+    let mut game_state = game_state(7);
+    assert_eq!(game_state.get_player_range("p0", &game_dict), 1);
+    assert!(!game_state.is_in_range("p0", "p2", 1, &game_dict));
+
+    equip(&mut game_state, "p0", shared_types::CardName::Schofield);
+    assert_eq!(game_state.get_player_range("p0", &game_dict), 2);
+    let range = game_state.get_player_range("p0", &game_dict);
+    assert!(game_state.is_in_range("p0", "p2", range, &game_dict));
+    assert!(!game_state.is_in_range("p0", "p3", range, &game_dict));
+    assert!(!game_state.is_in_range("p0", "p0", range, &game_dict));
+  }
+
+  #[test]
+  fn bang_requires_the_target_in_range() {
+    let game_dict = game_dict();
+    let mut game_state = game_state(4);
+    let bang = &game_dict.card_dict[&shared_types::CardName::Bang];
+    let cards = vec![shared_types::Card {
+      name: shared_types::CardName::Bang,
+      suit: shared_types::CardSuit::Clubs,
+      rank: shared_types::CardRank::N2,
+    }];
+
+    let precheck = bang.preconditions;
+    assert!(precheck("p0", &cards, &vec![String::from("p1")], &mut game_state, &game_dict).is_ok());
+    assert!(precheck("p0", &cards, &vec![String::from("p2")], &mut game_state, &game_dict).is_err());
+    assert!(precheck("p0", &cards, &vec![], &mut game_state, &game_dict).is_err());
+
+    equip(&mut game_state, "p0", shared_types::CardName::Remington);
+    assert!(precheck("p0", &cards, &vec![String::from("p2")], &mut game_state, &game_dict).is_ok());
+  }
+
+  #[test]
+  fn deck_matches_the_base_game_distribution() {
+    let game_dict = game_dict();
+    let deck = generate_deck(&Vec::new(), &game_dict, &mut nanorand::WyRand::new_seed(0));
+    let count = |name: shared_types::CardName| deck.iter().filter(|card| card.name == name).count();
+
+    assert_eq!(deck.len(), 80);
+    assert_eq!(count(shared_types::CardName::Bang), 25);
+    assert_eq!(count(shared_types::CardName::Missed), 12);
+    assert_eq!(count(shared_types::CardName::Beer), 6);
+    assert_eq!(count(shared_types::CardName::Panic), 4);
+    assert_eq!(count(shared_types::CardName::Jail), 3);
+    assert!(deck
+      .iter()
+      .all(|card| game_dict.card_dict.contains_key(&card.name)));
+  }
+
+  #[test]
+  fn only_one_bang_per_turn_without_a_volcanic() {
+    let game_dict = game_dict();
+    let mut game_state = game_state(4);
+    let bang = &game_dict.card_dict[&shared_types::CardName::Bang];
+    let cards = vec![shared_types::Card {
+      name: shared_types::CardName::Bang,
+      suit: shared_types::CardSuit::Clubs,
+      rank: shared_types::CardRank::N2,
+    }];
+    let targets = vec![String::from("p1")];
+
+    (bang.effect)("p0", &cards, &targets, &mut game_state, &game_dict);
+    assert_eq!(game_state.player_data["p1"].health, 3);
+    assert!((bang.preconditions)("p0", &cards, &targets, &mut game_state, &game_dict).is_err());
+
+    equip(&mut game_state, "p0", shared_types::CardName::Volcanic);
+    assert!((bang.preconditions)("p0", &cards, &targets, &mut game_state, &game_dict).is_ok());
+  }
+
+  #[test]
+  fn beer_and_saloon_heal_up_to_max_health() {
+    let game_dict = game_dict();
+    let mut game_state = game_state(4);
+    let beer = &game_dict.card_dict[&shared_types::CardName::Beer];
+    let saloon = &game_dict.card_dict[&shared_types::CardName::Saloon];
+    game_state.player_data.get_mut("p0").unwrap().health = 2;
+    game_state.player_data.get_mut("p1").unwrap().health = 3;
+
+    assert!((beer.preconditions)("p1", &vec![], &vec![], &mut game_state, &game_dict).is_ok());
+    (beer.effect)("p0", &vec![], &vec![], &mut game_state, &game_dict);
+    assert_eq!(game_state.player_data["p0"].health, 3);
+
+    (saloon.effect)("p0", &vec![], &vec![], &mut game_state, &game_dict);
+    assert_eq!(game_state.player_data["p0"].health, 4);
+    assert_eq!(game_state.player_data["p1"].health, 4);
+    assert!((beer.preconditions)("p1", &vec![], &vec![], &mut game_state, &game_dict).is_err());
+  }
+
+  #[test]
+  fn the_discard_pile_is_reshuffled_into_an_empty_deck() {
+    let mut game_state = game_state(4);
+    game_state.discard = generate_deck(&Vec::new(), &game_dict(), &mut game_state.rng)
+      .into_iter()
+      .take(5)
+      .collect();
+
+    let messages = game_state.draw_cards("p0", 3);
+    assert_eq!(game_state.player_data["p0"].hand.len(), 3);
+    assert_eq!(game_state.deck.len(), 2);
+    assert!(game_state.discard.is_empty());
+    assert_eq!(messages.len(), 4);
+    assert!(messages.iter().all(|(_, message)| matches!(
+      message.event_code,
+      shared_types::ServerEventCode::DeckReshuffled
+    )));
+  }
+
+  #[test]
+  fn empty_deck_rule_decides_draws_with_no_cards_left() {
+    let mut game_state = game_state(4);
+    let messages = game_state.draw_cards("p0", 2);
+    assert!(game_state.player_data["p0"].hand.is_empty());
+    assert!(messages
+      .iter()
+      .all(|(_, message)| matches!(message.event_code, shared_types::ServerEventCode::DeckEmpty)));
+
+    game_state.empty_deck_rule = shared_types::EmptyDeckRule::NewDeck;
+    game_state.deck_list = generate_deck(&Vec::new(), &game_dict(), &mut game_state.rng);
+    game_state.draw_cards("p0", 2);
+    assert_eq!(game_state.player_data["p0"].hand.len(), 2);
+    assert_eq!(game_state.deck.len(), 78);
+  }
+
+  #[test]
+  fn a_new_deck_leaves_out_the_cards_in_play() {
+    let mut game_state = game_state(4);
+    game_state.empty_deck_rule = shared_types::EmptyDeckRule::NewDeck;
+    game_state.deck_list = generate_deck(&Vec::new(), &game_dict(), &mut game_state.rng);
+    let in_hand = game_state.deck_list[0].clone();
+    let on_field = game_state.deck_list[1].clone();
+    game_state
+      .player_data
+      .get_mut("p1")
+      .unwrap()
+      .hand
+      .push(in_hand.clone());
+    game_state
+      .player_data
+      .get_mut("p2")
+      .unwrap()
+      .field
+      .push(on_field.clone());
+
+    game_state.refill_deck();
+    assert_eq!(game_state.deck.len(), 78);
+    assert!(!game_state.deck.contains(&in_hand));
+    assert!(!game_state.deck.contains(&on_field));
+  }
+}
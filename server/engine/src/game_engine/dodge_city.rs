@@ -0,0 +1,1137 @@
+use crate::{game_engine, shared_types};
+
+/// The characters that come with Dodge City
+pub const CHARACTERS: [shared_types::Character; 15] = [
+  shared_types::Character::ApacheKid,
+  shared_types::Character::BelleStar,
+  shared_types::Character::BillNoface,
+  shared_types::Character::ChuckWengam,
+  shared_types::Character::DocHolyday,
+  shared_types::Character::ElenaFuente,
+  shared_types::Character::GregDigger,
+  shared_types::Character::HerbHunter,
+  shared_types::Character::JoseDelgado,
+  shared_types::Character::MollyStark,
+  shared_types::Character::PatBrennan,
+  shared_types::Character::PixiePete,
+  shared_types::Character::SeanMallory,
+  shared_types::Character::TequilaJoe,
+  shared_types::Character::VeraCuster,
+];
+
+/// The number of cards Doc Holyday discards to shoot a Bang
+const DOC_HOLYDAY_DISCARD_COUNT: usize = 2;
+
+/// The number of times José Delgado can use his ability each turn
+const JOSE_DELGADO_USES_PER_TURN: u8 = 2;
+
+/// The number of cards Pixie Pete takes during the draw phase
+const PIXIE_PETE_DRAW_COUNT: usize = 3;
+
+/// The most players who can sit at the table with Dodge City
+const MAX_PLAYERS: usize = 8;
+
+/// What Dodge City adds to the base game
+pub fn get_expansion_data() -> game_engine::types::ExpansionData {
+  game_engine::types::ExpansionData {
+    max_players: MAX_PLAYERS,
+    characters: CHARACTERS.to_vec(),
+    card_names: Vec::new(),
+    character_names: Vec::new(),
+    deck_distribution: get_deck_distribution(),
+    events: Vec::new(),
+    final_event: None,
+    insert_cards,
+    insert_characters,
+  }
+}
+
+/// The suit and rank of every card Dodge City adds to the deck
+pub fn get_deck_distribution() -> game_engine::types::DeckDistribution {
+  use shared_types::CardName;
+  use shared_types::CardRank as R;
+  use shared_types::CardSuit as S;
+  vec![
+    // Brown Cards
+    (CardName::Punch, vec![(S::Spades, R::N10)]),
+    (
+      CardName::Dodge,
+      vec![(S::Diamonds, R::N7), (S::Hearts, R::K)],
+    ),
+    (CardName::Springfield, vec![(S::Spades, R::K)]),
+    (CardName::Whisky, vec![(S::Hearts, R::Q)]),
+    (CardName::Tequila, vec![(S::Clubs, R::N9)]),
+    (CardName::Brawl, vec![(S::Spades, R::J)]),
+    (CardName::RagTime, vec![(S::Hearts, R::N9)]),
+    // Blue Cards
+    (CardName::Hideout, vec![(S::Clubs, R::K)]),
+    (CardName::Silver, vec![(S::Hearts, R::N4)]),
+    (CardName::Binocular, vec![(S::Diamonds, R::N10)]),
+    // Green Cards
+    (CardName::PonyExpress, vec![(S::Diamonds, R::Q)]),
+    (CardName::Bible, vec![(S::Hearts, R::N10)]),
+    (CardName::IronPlate, vec![(S::Diamonds, R::A)]),
+    (CardName::Canteen, vec![(S::Hearts, R::N7)]),
+    (CardName::Sombrero, vec![(S::Clubs, R::N7)]),
+    (CardName::TenGallonHat, vec![(S::Clubs, R::J)]),
+    (CardName::BuffaloRifle, vec![(S::Clubs, R::Q)]),
+    (CardName::CanCan, vec![(S::Clubs, R::N8)]),
+    (CardName::Conestoga, vec![(S::Diamonds, R::N9)]),
+    (CardName::Derringer, vec![(S::Spades, R::N7)]),
+    (CardName::Howitzer, vec![(S::Spades, R::N9)]),
+    (CardName::Knife, vec![(S::Hearts, R::N8)]),
+    (CardName::Pepperbox, vec![(S::Hearts, R::A)]),
+  ]
+}
+
+/// Adds the cards of Dodge City to the card dictionary
+pub fn insert_cards(card_dict: &mut game_engine::types::CardDictionary) {
+  //===============================
+  // Punch
+  //===============================
+  card_dict.insert(
+    shared_types::CardName::Punch,
+    game_engine::types::CardData {
+      color: game_engine::types::CardColor::Brown,
+      weapon_range: None,
+      distance_modifier: game_engine::types::DistanceModifier::default(),
+      triggers: vec![],
+      preconditions: |user_id, cards, targets, game_state, game_dict| {
+        // a Punch counts as the Bang of the turn
+        if game_state.bangs_played >= game_state.get_bang_limit()
+          && !game_state.has_unlimited_bangs(user_id)
+        {
+          return Err(game_engine::types::RuleError::BangLimitReached {
+            limit: game_state.get_bang_limit(),
+          });
+        }
+        return shot_within_reach(user_id, cards, targets, game_state, game_dict);
+      },
+      effect: |user_id, cards, targets, game_state, game_dict| {
+        game_state.bangs_played += 1;
+        return shoot(user_id, cards, targets, game_state, game_dict);
+      },
+      update: game_engine::data::resolve_shot,
+    },
+  );
+  //===============================
+  // Dodge
+  //===============================
+  // used as a Missed, drawing a card on top of it
+  card_dict.insert(
+    shared_types::CardName::Dodge,
+    game_engine::types::CardData {
+      color: game_engine::types::CardColor::Brown,
+      weapon_range: None,
+      distance_modifier: game_engine::types::DistanceModifier::default(),
+      triggers: vec![game_engine::types::EventTrigger::Damage],
+      preconditions: game_engine::data::response_only_preconditions,
+      effect: |user_id, _cards, _targets, game_state, _game_dict| {
+        return game_state.draw_cards(user_id, 1);
+      },
+      update: |_user_id, _cards, _targets, _game_state, _game_dict| Vec::new(),
+    },
+  );
+  //===============================
+  // Springfield
+  //===============================
+  card_dict.insert(
+    shared_types::CardName::Springfield,
+    game_engine::types::CardData {
+      color: game_engine::types::CardColor::Brown,
+      weapon_range: None,
+      distance_modifier: game_engine::types::DistanceModifier::default(),
+      triggers: vec![],
+      preconditions: |user_id, cards, targets, game_state, game_dict| {
+        check_extra_discard(cards)?;
+        return shot_at_any_distance(user_id, cards, targets, game_state, game_dict);
+      },
+      effect: shoot,
+      update: game_engine::data::resolve_shot,
+    },
+  );
+  //===============================
+  // Whisky & Tequila
+  //===============================
+  card_dict.insert(
+    shared_types::CardName::Whisky,
+    game_engine::types::CardData {
+      color: game_engine::types::CardColor::Brown,
+      weapon_range: None,
+      distance_modifier: game_engine::types::DistanceModifier::default(),
+      triggers: vec![],
+      preconditions: |user_id, cards, targets, game_state, game_dict| {
+        check_extra_discard(cards)?;
+        game_engine::data::no_target_preconditions(user_id, cards, targets, game_state, game_dict)?;
+        match game_state.player_data.get(user_id) {
+          Some(player_data) if player_data.health < player_data.max_health => Ok(()),
+          _ => Err(game_engine::types::RuleError::FullHealth),
+        }
+      },
+      effect: |user_id, cards, _targets, game_state, _game_dict| {
+        game_state.discard_cards(user_id, cards);
+        game_state.heal_player(user_id, 2);
+        return Vec::new();
+      },
+      update: |_user_id, _cards, _targets, _game_state, _game_dict| Vec::new(),
+    },
+  );
+  card_dict.insert(
+    shared_types::CardName::Tequila,
+    game_engine::types::CardData {
+      color: game_engine::types::CardColor::Brown,
+      weapon_range: None,
+      distance_modifier: game_engine::types::DistanceModifier::default(),
+      triggers: vec![],
+      preconditions: |_user_id, cards, targets, game_state, _game_dict| {
+        check_extra_discard(cards)?;
+        game_engine::data::check_target_count(targets, 1)?;
+        match game_state.player_data.get(&targets[0]) {
+          Some(player_data) if player_data.is_alive() => Ok(()),
+          _ => Err(game_engine::types::RuleError::InvalidTarget {
+            target: targets[0].clone(),
+          }),
+        }
+      },
+      effect: |user_id, cards, targets, game_state, _game_dict| {
+        game_state.discard_cards(user_id, cards);
+        game_state.heal_player(&targets[0], 1);
+        return Vec::new();
+      },
+      update: |_user_id, _cards, _targets, _game_state, _game_dict| Vec::new(),
+    },
+  );
+  //===============================
+  // Brawl
+  //===============================
+  card_dict.insert(
+    shared_types::CardName::Brawl,
+    game_engine::types::CardData {
+      color: game_engine::types::CardColor::Brown,
+      weapon_range: None,
+      distance_modifier: game_engine::types::DistanceModifier::default(),
+      triggers: vec![],
+      preconditions: |user_id, cards, targets, game_state, game_dict| {
+        check_extra_discard(cards)?;
+        return game_engine::data::no_target_preconditions(
+          user_id, cards, targets, game_state, game_dict,
+        );
+      },
+      effect: |user_id, cards, _targets, game_state, _game_dict| {
+        game_state.discard_cards(user_id, cards);
+        // every other player loses a card from their hand, or from their field if their hand is empty
+        for target in game_state.get_affected_players(user_id, &cards[0]) {
+          let card = match game_state.take_random_hand_card(&target) {
+            Some(card) => Some(card),
+            None => game_state
+              .player_data
+              .get_mut(&target)
+              .and_then(|player_data| player_data.field.pop()),
+          };
+          if let Some(card) = card {
+            game_state.discard.push(card);
+          }
+        }
+        return Vec::new();
+      },
+      update: |_user_id, _cards, _targets, _game_state, _game_dict| Vec::new(),
+    },
+  );
+  //===============================
+  // Rag Time
+  //===============================
+  card_dict.insert(
+    shared_types::CardName::RagTime,
+    game_engine::types::CardData {
+      color: game_engine::types::CardColor::Brown,
+      weapon_range: None,
+      distance_modifier: game_engine::types::DistanceModifier::default(),
+      triggers: vec![],
+      preconditions: |user_id, cards, targets, game_state, game_dict| {
+        check_extra_discard(cards)?;
+        return take_at_any_distance(user_id, cards, targets, game_state, game_dict);
+      },
+      effect: steal_card,
+      update: |_user_id, _cards, _targets, _game_state, _game_dict| Vec::new(),
+    },
+  );
+  //===============================
+  // Hideout, Silver & Binocular
+  //===============================
+  for horse in [
+    shared_types::CardName::Hideout,
+    shared_types::CardName::Silver,
+  ]
+  .iter()
+  {
+    card_dict.insert(
+      horse.clone(),
+      game_engine::data::equipment_card_data(
+        None,
+        game_engine::types::DistanceModifier {
+          defense: 1,
+          offense: 0,
+        },
+      ),
+    );
+  }
+  card_dict.insert(
+    shared_types::CardName::Binocular,
+    game_engine::data::equipment_card_data(
+      None,
+      game_engine::types::DistanceModifier {
+        defense: 0,
+        offense: 1,
+      },
+    ),
+  );
+  //===============================
+  // Green Cards
+  //===============================
+  // played onto the field like Blue cards, then activated from there on a later turn
+  card_dict.insert(
+    shared_types::CardName::PonyExpress,
+    game_engine::types::CardData {
+      color: game_engine::types::CardColor::Green,
+      weapon_range: None,
+      distance_modifier: game_engine::types::DistanceModifier::default(),
+      triggers: vec![],
+      preconditions: game_engine::data::no_target_preconditions,
+      effect: |user_id, cards, _targets, game_state, _game_dict| {
+        game_state.discard_cards(user_id, cards);
+        return game_state.draw_cards(user_id, 3);
+      },
+      update: |_user_id, _cards, _targets, _game_state, _game_dict| Vec::new(),
+    },
+  );
+  card_dict.insert(
+    shared_types::CardName::Canteen,
+    game_engine::types::CardData {
+      color: game_engine::types::CardColor::Green,
+      weapon_range: None,
+      distance_modifier: game_engine::types::DistanceModifier::default(),
+      triggers: vec![],
+      preconditions: game_engine::data::no_target_preconditions,
+      effect: |user_id, cards, _targets, game_state, _game_dict| {
+        game_state.discard_cards(user_id, cards);
+        game_state.heal_player(user_id, 1);
+        return Vec::new();
+      },
+      update: |_user_id, _cards, _targets, _game_state, _game_dict| Vec::new(),
+    },
+  );
+  card_dict.insert(
+    shared_types::CardName::Conestoga,
+    game_engine::types::CardData {
+      color: game_engine::types::CardColor::Green,
+      weapon_range: None,
+      distance_modifier: game_engine::types::DistanceModifier::default(),
+      triggers: vec![],
+      preconditions: take_at_any_distance,
+      effect: steal_card,
+      update: |_user_id, _cards, _targets, _game_state, _game_dict| Vec::new(),
+    },
+  );
+  card_dict.insert(
+    shared_types::CardName::CanCan,
+    game_engine::types::CardData {
+      color: game_engine::types::CardColor::Green,
+      weapon_range: None,
+      distance_modifier: game_engine::types::DistanceModifier::default(),
+      triggers: vec![],
+      preconditions: take_at_any_distance,
+      effect: |user_id, cards, targets, game_state, _game_dict| {
+        game_state.discard_cards(user_id, cards);
+        if let Some(card) = game_state.take_selected_card(&targets[0]) {
+          game_state.discard.push(card);
+        }
+        return Vec::new();
+      },
+      update: |_user_id, _cards, _targets, _game_state, _game_dict| Vec::new(),
+    },
+  );
+  card_dict.insert(
+    shared_types::CardName::Howitzer,
+    game_engine::types::CardData {
+      color: game_engine::types::CardColor::Green,
+      weapon_range: None,
+      distance_modifier: game_engine::types::DistanceModifier::default(),
+      triggers: vec![],
+      preconditions: game_engine::data::no_target_preconditions,
+      effect: |user_id, cards, _targets, game_state, game_dict| {
+        game_state.discard_cards(user_id, cards);
+        let targets = game_state.get_affected_players(user_id, &cards[0]);
+        return game_state.shoot_players(
+          user_id,
+          &cards[0].name,
+          &targets,
+          game_engine::types::EventTrigger::Damage,
+          game_dict,
+        );
+      },
+      update: game_engine::data::resolve_shot,
+    },
+  );
+  // green guns shoot without counting as the Bang of the turn
+  card_dict.insert(
+    shared_types::CardName::BuffaloRifle,
+    green_shot_card_data(shot_at_any_distance),
+  );
+  card_dict.insert(
+    shared_types::CardName::Pepperbox,
+    green_shot_card_data(shot_within_weapon_range),
+  );
+  card_dict.insert(
+    shared_types::CardName::Knife,
+    green_shot_card_data(shot_within_reach),
+  );
+  card_dict.insert(
+    shared_types::CardName::Derringer,
+    game_engine::types::CardData {
+      effect: |user_id, cards, targets, game_state, game_dict| {
+        let mut messages = game_state.draw_cards(user_id, 1);
+        messages.extend(shoot(user_id, cards, targets, game_state, game_dict));
+        return messages;
+      },
+      ..green_shot_card_data(shot_within_reach)
+    },
+  );
+  // used as a Missed, which discards them, so their effect is only what they add on top of it
+  card_dict.insert(
+    shared_types::CardName::Bible,
+    game_engine::types::CardData {
+      effect: |user_id, _cards, _targets, game_state, _game_dict| {
+        return game_state.draw_cards(user_id, 1);
+      },
+      ..green_missed_card_data()
+    },
+  );
+  for missed in [
+    shared_types::CardName::IronPlate,
+    shared_types::CardName::Sombrero,
+    shared_types::CardName::TenGallonHat,
+  ]
+  .iter()
+  {
+    card_dict.insert(missed.clone(), green_missed_card_data());
+  }
+}
+
+/// Adds the characters of Dodge City to the character dictionary
+pub fn insert_characters(character_dict: &mut game_engine::types::CharacterDictionary) {
+  //===============================
+  // Apache Kid
+  //===============================
+  // unaffected by Diamonds played by other players
+  character_dict.insert(
+    shared_types::Character::ApacheKid,
+    game_engine::data::passive_character_data(3, game_engine::types::DistanceModifier::default()),
+  );
+  //===============================
+  // Belle Star
+  //===============================
+  // the cards in front of other players have no effect during her turn
+  character_dict.insert(
+    shared_types::Character::BelleStar,
+    game_engine::data::passive_character_data(4, game_engine::types::DistanceModifier::default()),
+  );
+  //===============================
+  // Bill Noface
+  //===============================
+  character_dict.insert(
+    shared_types::Character::BillNoface,
+    game_engine::types::CharacterData {
+      hp: 4,
+      triggers: vec![game_engine::types::EventTrigger::DrawPhase],
+      effect_optional: false,
+      effect: |player, _context, game_state, _game_dict| {
+        // draws a card, plus one for each life point he is missing
+        let wounds = match game_state.player_data.get(player) {
+          Some(player_data) => player_data.max_health - player_data.health,
+          None => 0,
+        };
+        let messages = game_state.draw_cards(player, 1 + wounds as usize);
+        game_state.turn_phase = shared_types::TurnPhase::Play;
+        return Ok(messages);
+      },
+      distance_modifier: game_engine::types::DistanceModifier::default(),
+    },
+  );
+  //===============================
+  // Chuck Wengam
+  //===============================
+  character_dict.insert(
+    shared_types::Character::ChuckWengam,
+    game_engine::types::CharacterData {
+      hp: 4,
+      triggers: vec![game_engine::types::EventTrigger::Activated],
+      effect_optional: true,
+      effect: |player, _context, game_state, _game_dict| {
+        // loses a life point to draw two cards, as long as it is not his last
+        game_state.check_turn_owner(player)?;
+        game_state.check_phase(shared_types::TurnPhase::Play)?;
+        match game_state.player_data.get_mut(player) {
+          Some(player_data) if player_data.health > 1 => player_data.health -= 1,
+          _ => return Err(game_engine::types::RuleError::AbilityUnavailable),
+        }
+        return Ok(game_state.draw_cards(player, 2));
+      },
+      distance_modifier: game_engine::types::DistanceModifier::default(),
+    },
+  );
+  //===============================
+  // Doc Holyday
+  //===============================
+  character_dict.insert(
+    shared_types::Character::DocHolyday,
+    game_engine::types::CharacterData {
+      hp: 4,
+      triggers: vec![game_engine::types::EventTrigger::Activated],
+      effect_optional: true,
+      effect: |player, context, game_state, game_dict| {
+        // once a turn, discards two cards to shoot a Bang that does not count as the Bang of the turn
+        game_state.check_turn_owner(player)?;
+        game_state.check_phase(shared_types::TurnPhase::Play)?;
+        if game_state.ability_uses > 0 {
+          return Err(game_engine::types::RuleError::AbilityUnavailable);
+        }
+        let range = game_state.get_player_range(player, game_dict);
+        match game_state.player_data.get(player) {
+          Some(player_data)
+            if context.cards.len() == DOC_HOLYDAY_DISCARD_COUNT
+              && player_data.has_in_hand(context.cards)
+              && context.targets.len() == 1
+              && game_state.is_in_range(player, &context.targets[0], range, game_dict) => {}
+          _ => return Err(game_engine::types::RuleError::InvalidSelection),
+        }
+        game_state.ability_uses += 1;
+        game_state.discard_cards(player, context.cards);
+        return Ok(game_state.shoot_players(
+          player,
+          &shared_types::CardName::Bang,
+          context.targets,
+          game_engine::types::EventTrigger::Damage,
+          game_dict,
+        ));
+      },
+      distance_modifier: game_engine::types::DistanceModifier::default(),
+    },
+  );
+  //===============================
+  // Elena Fuente
+  //===============================
+  // can use any card in her hand as a Missed
+  character_dict.insert(
+    shared_types::Character::ElenaFuente,
+    game_engine::data::passive_character_data(3, game_engine::types::DistanceModifier::default()),
+  );
+  //===============================
+  // Greg Digger & Herb Hunter
+  //===============================
+  character_dict.insert(
+    shared_types::Character::GregDigger,
+    game_engine::types::CharacterData {
+      hp: 4,
+      triggers: vec![game_engine::types::EventTrigger::PlayerEliminated],
+      effect_optional: false,
+      effect: |player, _context, game_state, _game_dict| {
+        game_state.heal_player(player, 2);
+        return Ok(Vec::new());
+      },
+      distance_modifier: game_engine::types::DistanceModifier::default(),
+    },
+  );
+  character_dict.insert(
+    shared_types::Character::HerbHunter,
+    game_engine::types::CharacterData {
+      hp: 4,
+      triggers: vec![game_engine::types::EventTrigger::PlayerEliminated],
+      effect_optional: false,
+      effect: |player, _context, game_state, _game_dict| {
+        return Ok(game_state.draw_cards(player, 2));
+      },
+      distance_modifier: game_engine::types::DistanceModifier::default(),
+    },
+  );
+  //===============================
+  // José Delgado
+  //===============================
+  character_dict.insert(
+    shared_types::Character::JoseDelgado,
+    game_engine::types::CharacterData {
+      hp: 4,
+      triggers: vec![game_engine::types::EventTrigger::Activated],
+      effect_optional: true,
+      effect: |player, context, game_state, game_dict| {
+        // twice a turn, discards a Blue card from his hand to draw two cards
+        game_state.check_turn_owner(player)?;
+        game_state.check_phase(shared_types::TurnPhase::Play)?;
+        if game_state.ability_uses >= JOSE_DELGADO_USES_PER_TURN {
+          return Err(game_engine::types::RuleError::AbilityUnavailable);
+        }
+        let is_blue = |card: &shared_types::Card| match game_dict.card_dict.get(&card.name) {
+          Some(card_data) => card_data.color == game_engine::types::CardColor::Blue,
+          None => false,
+        };
+        match game_state.player_data.get(player) {
+          Some(player_data)
+            if context.cards.len() == 1
+              && player_data.has_in_hand(context.cards)
+              && is_blue(&context.cards[0]) => {}
+          _ => return Err(game_engine::types::RuleError::InvalidSelection),
+        }
+        game_state.ability_uses += 1;
+        game_state.discard_cards(player, context.cards);
+        return Ok(game_state.draw_cards(player, 2));
+      },
+      distance_modifier: game_engine::types::DistanceModifier::default(),
+    },
+  );
+  //===============================
+  // Molly Stark
+  //===============================
+  character_dict.insert(
+    shared_types::Character::MollyStark,
+    game_engine::types::CharacterData {
+      hp: 4,
+      triggers: vec![game_engine::types::EventTrigger::Responded],
+      effect_optional: false,
+      effect: |player, context, game_state, _game_dict| {
+        // draws a card for each card she uses out of turn
+        return Ok(game_state.draw_cards(player, context.amount as usize));
+      },
+      distance_modifier: game_engine::types::DistanceModifier::default(),
+    },
+  );
+  //===============================
+  // Pat Brennan
+  //===============================
+  character_dict.insert(
+    shared_types::Character::PatBrennan,
+    game_engine::types::CharacterData {
+      hp: 4,
+      triggers: vec![game_engine::types::EventTrigger::DrawPhase],
+      effect_optional: true,
+      effect: |player, context, game_state, _game_dict| {
+        // may take a card in front of another player instead of drawing
+        let messages = match (context.cards.as_slice(), context.targets.as_slice()) {
+          ([], []) => game_state.draw_cards(player, game_state.get_draw_count(player)),
+          ([card], [target]) => {
+            let taken = match game_state.player_data.get_mut(target) {
+              Some(target_data) => match target_data.field.iter().position(|owned| owned == card) {
+                Some(index) => target_data.field.remove(index),
+                None => return Err(game_engine::types::RuleError::InvalidSelection),
+              },
+              None => return Err(game_engine::types::RuleError::InvalidSelection),
+            };
+            game_state.usable_from.remove(&taken);
+            if let Some(player_data) = game_state.player_data.get_mut(player) {
+              player_data.hand.push(taken);
+            }
+            game_state.get_update_messages()
+          }
+          _ => return Err(game_engine::types::RuleError::InvalidSelection),
+        };
+        game_state.turn_phase = shared_types::TurnPhase::Play;
+        return Ok(messages);
+      },
+      distance_modifier: game_engine::types::DistanceModifier::default(),
+    },
+  );
+  //===============================
+  // Pixie Pete
+  //===============================
+  character_dict.insert(
+    shared_types::Character::PixiePete,
+    game_engine::types::CharacterData {
+      hp: 3,
+      triggers: vec![game_engine::types::EventTrigger::DrawPhase],
+      effect_optional: false,
+      effect: |player, _context, game_state, _game_dict| {
+        let messages = game_state.draw_cards(player, PIXIE_PETE_DRAW_COUNT);
+        game_state.turn_phase = shared_types::TurnPhase::Play;
+        return Ok(messages);
+      },
+      distance_modifier: game_engine::types::DistanceModifier::default(),
+    },
+  );
+  //===============================
+  // Sean Mallory
+  //===============================
+  // can hold up to ten cards at the end of his turn
+  character_dict.insert(
+    shared_types::Character::SeanMallory,
+    game_engine::data::passive_character_data(3, game_engine::types::DistanceModifier::default()),
+  );
+  //===============================
+  // Tequila Joe
+  //===============================
+  // regains two life points from a Beer
+  character_dict.insert(
+    shared_types::Character::TequilaJoe,
+    game_engine::data::passive_character_data(4, game_engine::types::DistanceModifier::default()),
+  );
+  //===============================
+  // Vera Custer
+  //===============================
+  character_dict.insert(
+    shared_types::Character::VeraCuster,
+    game_engine::types::CharacterData {
+      hp: 3,
+      triggers: vec![game_engine::types::EventTrigger::Activated],
+      effect_optional: true,
+      effect: |player, context, game_state, _game_dict| {
+        // before drawing, takes on the ability of another living character until her next turn
+        game_state.check_turn_owner(player)?;
+        game_state.check_phase(shared_types::TurnPhase::Draw)?;
+        if game_state.copied_characters.contains_key(player) {
+          return Err(game_engine::types::RuleError::AbilityUnavailable);
+        }
+        let character = match context.targets.as_slice() {
+          [target] if target != player => match game_state.player_data.get(target) {
+            Some(target_data) if target_data.is_alive() => target_data.character.clone(),
+            _ => return Err(game_engine::types::RuleError::InvalidSelection),
+          },
+          _ => return Err(game_engine::types::RuleError::InvalidSelection),
+        };
+        if let Some(player_data) = game_state.player_data.get_mut(player) {
+          game_state
+            .copied_characters
+            .insert(player.to_string(), player_data.character.clone());
+          player_data.character = character;
+        }
+        return Ok(game_state.get_update_messages());
+      },
+      distance_modifier: game_engine::types::DistanceModifier::default(),
+    },
+  );
+}
+
+/// Checks that a card is played along with another card to discard
+fn check_extra_discard(
+  cards: &Vec<shared_types::Card>,
+) -> Result<(), game_engine::types::RuleError> {
+  if cards.len() != 2 {
+    return Err(game_engine::types::RuleError::ExtraDiscardRequired);
+  }
+  return Ok(());
+}
+
+/// Preconditions for shooting a player at a distance of one
+fn shot_within_reach(
+  user_id: &str,
+  _cards: &Vec<shared_types::Card>,
+  targets: &Vec<String>,
+  game_state: &mut game_engine::types::GameState,
+  game_dict: &game_engine::types::GameDictionary,
+) -> Result<(), game_engine::types::RuleError> {
+  game_engine::data::check_target_count(targets, 1)?;
+  return game_state.check_range(user_id, &targets[0], 1, game_dict);
+}
+
+/// Preconditions for shooting a player within the range of the weapon of the user
+fn shot_within_weapon_range(
+  user_id: &str,
+  _cards: &Vec<shared_types::Card>,
+  targets: &Vec<String>,
+  game_state: &mut game_engine::types::GameState,
+  game_dict: &game_engine::types::GameDictionary,
+) -> Result<(), game_engine::types::RuleError> {
+  game_engine::data::check_target_count(targets, 1)?;
+  let range = game_state.get_player_range(user_id, game_dict);
+  return game_state.check_range(user_id, &targets[0], range, game_dict);
+}
+
+/// Preconditions for shooting any other player
+fn shot_at_any_distance(
+  user_id: &str,
+  _cards: &Vec<shared_types::Card>,
+  targets: &Vec<String>,
+  game_state: &mut game_engine::types::GameState,
+  game_dict: &game_engine::types::GameDictionary,
+) -> Result<(), game_engine::types::RuleError> {
+  game_engine::data::check_target_count(targets, 1)?;
+  return game_state.check_range(user_id, &targets[0], u8::MAX, game_dict);
+}
+
+/// Preconditions for taking a card from any other player
+fn take_at_any_distance(
+  user_id: &str,
+  cards: &Vec<shared_types::Card>,
+  targets: &Vec<String>,
+  game_state: &mut game_engine::types::GameState,
+  game_dict: &game_engine::types::GameDictionary,
+) -> Result<(), game_engine::types::RuleError> {
+  shot_at_any_distance(user_id, cards, targets, game_state, game_dict)?;
+  return game_state.check_field_selection(&targets[0]);
+}
+
+/// Discards the played cards and shoots the target
+fn shoot(
+  user_id: &str,
+  cards: &Vec<shared_types::Card>,
+  targets: &Vec<String>,
+  game_state: &mut game_engine::types::GameState,
+  game_dict: &game_engine::types::GameDictionary,
+) -> game_engine::types::GameMessages {
+  game_state.discard_cards(user_id, cards);
+  return game_state.shoot_players(
+    user_id,
+    &cards[0].name,
+    targets,
+    game_engine::types::EventTrigger::Damage,
+    game_dict,
+  );
+}
+
+/// Discards the played cards and takes a card from the target into the hand of the user
+fn steal_card(
+  user_id: &str,
+  cards: &Vec<shared_types::Card>,
+  targets: &Vec<String>,
+  game_state: &mut game_engine::types::GameState,
+  _game_dict: &game_engine::types::GameDictionary,
+) -> game_engine::types::GameMessages {
+  game_state.discard_cards(user_id, cards);
+  if let Some(card) = game_state.take_selected_card(&targets[0]) {
+    game_state.usable_from.remove(&card);
+    if let Some(player_data) = game_state.player_data.get_mut(user_id) {
+      player_data.hand.push(card);
+    }
+  }
+  return Vec::new();
+}
+
+/// Creates the data for a Green card that shoots a single target
+fn green_shot_card_data(
+  preconditions: game_engine::types::CardConditions,
+) -> game_engine::types::CardData {
+  game_engine::types::CardData {
+    color: game_engine::types::CardColor::Green,
+    weapon_range: None,
+    distance_modifier: game_engine::types::DistanceModifier::default(),
+    triggers: vec![],
+    preconditions,
+    effect: shoot,
+    update: game_engine::data::resolve_shot,
+  }
+}
+
+/// Creates the data for a Green card that is used as a Missed
+fn green_missed_card_data() -> game_engine::types::CardData {
+  game_engine::types::CardData {
+    color: game_engine::types::CardColor::Green,
+    weapon_range: None,
+    distance_modifier: game_engine::types::DistanceModifier::default(),
+    triggers: vec![game_engine::types::EventTrigger::Damage],
+    preconditions: game_engine::data::response_only_preconditions,
+    effect: |_user_id, _cards, _targets, _game_state, _game_dict| Vec::new(),
+    update: |_user_id, _cards, _targets, _game_state, _game_dict| Vec::new(),
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::game_engine::test_utils::{card, game_dict, game_state, game_with_sheriff};
+  use crate::game_engine::types::GameState;
+  use crate::shared_types::{Card, CardName, CardSuit, Character, TurnPhase};
+
+  fn set_character(game_state: &mut GameState, player: &str, character: Character) {
+    game_state.player_data.get_mut(player).unwrap().character = character;
+  }
+
+  fn give(game_state: &mut GameState, player: &str, card: Card) {
+    game_state
+      .player_data
+      .get_mut(player)
+      .unwrap()
+      .hand
+      .push(card);
+  }
+
+  fn shoot_p1(game_state: &mut GameState) {
+    let game_dict = game_dict();
+    (game_dict.card_dict[&CardName::Bang].effect)(
+      "p0",
+      &vec![card(CardName::Bang, CardSuit::Clubs)],
+      &vec![String::from("p1")],
+      game_state,
+      &game_dict,
+    );
+  }
+
+  #[test]
+  fn every_card_in_the_deck_has_card_data() {
+    let game_dict = game_dict();
+    for (name, _) in get_deck_distribution() {
+      assert!(game_dict.card_dict.contains_key(&name));
+    }
+    for character in CHARACTERS.iter() {
+      assert!(game_dict.character_dict.contains_key(character));
+    }
+  }
+
+  #[test]
+  fn cards_that_discard_another_card_need_one() {
+    let game_dict = game_dict();
+    let mut game_state = game_state(4);
+    let springfield = &game_dict.card_dict[&CardName::Springfield];
+    let targets = vec![String::from("p2")];
+    let played = vec![card(CardName::Springfield, CardSuit::Spades)];
+    assert!(
+      (springfield.preconditions)("p0", &played, &targets, &mut game_state, &game_dict).is_err()
+    );
+
+    let played = vec![
+      card(CardName::Springfield, CardSuit::Spades),
+      card(CardName::Beer, CardSuit::Hearts),
+    ];
+    assert!(
+      (springfield.preconditions)("p0", &played, &targets, &mut game_state, &game_dict).is_ok()
+    );
+  }
+
+  #[test]
+  fn a_dodge_avoids_the_shot_and_draws_a_card() {
+    let game_dict = game_dict();
+    let mut game_state = game_with_sheriff();
+    game_state.deck = vec![card(CardName::Beer, CardSuit::Hearts)];
+    give(
+      &mut game_state,
+      "p1",
+      card(CardName::Dodge, CardSuit::Diamonds),
+    );
+    shoot_p1(&mut game_state);
+
+    game_state
+      .respond(
+        "p1",
+        &vec![card(CardName::Dodge, CardSuit::Diamonds)],
+        &game_dict,
+      )
+      .unwrap();
+    assert_eq!(game_state.player_data["p1"].health, 4);
+    assert_eq!(
+      game_state.player_data["p1"].hand,
+      vec![card(CardName::Beer, CardSuit::Hearts)]
+    );
+  }
+
+  #[test]
+  fn elena_fuente_uses_any_card_as_a_missed() {
+    let game_dict = game_dict();
+    let mut game_state = game_with_sheriff();
+    set_character(&mut game_state, "p1", Character::ElenaFuente);
+    give(
+      &mut game_state,
+      "p1",
+      card(CardName::Beer, CardSuit::Hearts),
+    );
+    shoot_p1(&mut game_state);
+
+    game_state
+      .respond(
+        "p1",
+        &vec![card(CardName::Beer, CardSuit::Hearts)],
+        &game_dict,
+      )
+      .unwrap();
+    assert_eq!(game_state.player_data["p1"].health, 4);
+    assert!(game_state.player_data["p1"].hand.is_empty());
+  }
+
+  #[test]
+  fn molly_stark_draws_for_cards_used_out_of_turn() {
+    let game_dict = game_dict();
+    let mut game_state = game_with_sheriff();
+    set_character(&mut game_state, "p1", Character::MollyStark);
+    game_state.deck = vec![card(CardName::Beer, CardSuit::Hearts)];
+    give(
+      &mut game_state,
+      "p1",
+      card(CardName::Missed, CardSuit::Clubs),
+    );
+    shoot_p1(&mut game_state);
+
+    game_state
+      .respond(
+        "p1",
+        &vec![card(CardName::Missed, CardSuit::Clubs)],
+        &game_dict,
+      )
+      .unwrap();
+    assert_eq!(
+      game_state.player_data["p1"].hand,
+      vec![card(CardName::Beer, CardSuit::Hearts)]
+    );
+  }
+
+  #[test]
+  fn apache_kid_is_unaffected_by_diamonds_of_others() {
+    let mut game_state = game_with_sheriff();
+    set_character(&mut game_state, "p2", Character::ApacheKid);
+    let diamond = card(CardName::Gatling, CardSuit::Diamonds);
+
+    assert!(game_state.is_unaffected_by("p2", "p0", &diamond));
+    assert!(!game_state.is_unaffected_by("p2", "p2", &diamond));
+    assert_eq!(
+      game_state.get_affected_players("p0", &diamond),
+      vec![String::from("p1"), String::from("p3")]
+    );
+  }
+
+  #[test]
+  fn belle_star_ignores_the_cards_of_others_during_her_turn() {
+    let game_dict = game_dict();
+    let mut game_state = game_state(4);
+    set_character(&mut game_state, "p0", Character::BelleStar);
+    for player in ["p0", "p1"].iter() {
+      game_state
+        .player_data
+        .get_mut(*player)
+        .unwrap()
+        .field
+        .push(card(CardName::Mustang, CardSuit::Hearts));
+    }
+    assert!(game_state.is_field_active("p0"));
+    assert!(!game_state.is_field_active("p1"));
+    assert_eq!(
+      game_state.get_player_distance("p0", "p1", &game_dict),
+      Some(1)
+    );
+    assert_eq!(
+      game_state.get_player_distance("p1", "p0", &game_dict),
+      Some(2)
+    );
+  }
+
+  #[test]
+  fn draw_phase_abilities() {
+    let game_dict = game_dict();
+    let mut game_state = game_state(4);
+    game_state.turn_phase = TurnPhase::Draw;
+    game_state.deck =
+      crate::game_engine::data::generate_deck(&Vec::new(), &game_dict, &mut game_state.rng);
+    set_character(&mut game_state, "p0", Character::BillNoface);
+    game_state.player_data.get_mut("p0").unwrap().health = 2;
+
+    game_state
+      .draw_phase("p0", &Vec::new(), &Vec::new(), &game_dict)
+      .unwrap();
+    assert_eq!(game_state.player_data["p0"].hand.len(), 3);
+
+    let mut game_state = crate::game_engine::test_utils::game_state(4);
+    game_state.turn_phase = TurnPhase::Draw;
+    set_character(&mut game_state, "p0", Character::PatBrennan);
+    let scope = card(CardName::Scope, CardSuit::Spades);
+    game_state
+      .player_data
+      .get_mut("p2")
+      .unwrap()
+      .field
+      .push(scope.clone());
+    game_state
+      .draw_phase(
+        "p0",
+        &vec![scope.clone()],
+        &vec![String::from("p2")],
+        &game_dict,
+      )
+      .unwrap();
+    assert_eq!(game_state.player_data["p0"].hand, vec![scope]);
+    assert!(game_state.player_data["p2"].field.is_empty());
+    assert!(game_state.turn_phase == TurnPhase::Play);
+  }
+
+  #[test]
+  fn tequila_joe_regains_two_from_a_beer() {
+    let game_dict = game_dict();
+    let mut game_state = game_state(4);
+    set_character(&mut game_state, "p0", Character::TequilaJoe);
+    game_state.player_data.get_mut("p0").unwrap().health = 1;
+    (game_dict.card_dict[&CardName::Beer].effect)(
+      "p0",
+      &vec![card(CardName::Beer, CardSuit::Hearts)],
+      &Vec::new(),
+      &mut game_state,
+      &game_dict,
+    );
+    assert_eq!(game_state.player_data["p0"].health, 3);
+  }
+
+  #[test]
+  fn jose_delgado_trades_blue_cards_twice_a_turn() {
+    let game_dict = game_dict();
+    let mut game_state = game_state(4);
+    game_state.deck =
+      crate::game_engine::data::generate_deck(&Vec::new(), &game_dict, &mut game_state.rng);
+    set_character(&mut game_state, "p0", Character::JoseDelgado);
+    for suit in [CardSuit::Clubs, CardSuit::Hearts, CardSuit::Spades].iter() {
+      give(&mut game_state, "p0", card(CardName::Barrel, suit.clone()));
+    }
+    give(&mut game_state, "p0", card(CardName::Bang, CardSuit::Clubs));
+
+    assert!(game_state
+      .use_ability(
+        "p0",
+        &vec![card(CardName::Bang, CardSuit::Clubs)],
+        &Vec::new(),
+        &game_dict
+      )
+      .is_err());
+    for suit in [CardSuit::Clubs, CardSuit::Hearts].iter() {
+      game_state
+        .use_ability(
+          "p0",
+          &vec![card(CardName::Barrel, suit.clone())],
+          &Vec::new(),
+          &game_dict,
+        )
+        .unwrap();
+    }
+    assert!(game_state
+      .use_ability(
+        "p0",
+        &vec![card(CardName::Barrel, CardSuit::Spades)],
+        &Vec::new(),
+        &game_dict
+      )
+      .is_err());
+    assert_eq!(game_state.player_data["p0"].hand.len(), 2 + 4);
+  }
+
+  #[test]
+  fn vera_custer_copies_an_ability_until_her_next_turn() {
+    let game_dict = game_dict();
+    let mut game_state = game_state(4);
+    game_state.turn_phase = TurnPhase::Draw;
+    game_state.deck =
+      crate::game_engine::data::generate_deck(&Vec::new(), &game_dict, &mut game_state.rng);
+    set_character(&mut game_state, "p0", Character::VeraCuster);
+    set_character(&mut game_state, "p1", Character::PixiePete);
+
+    game_state
+      .use_ability("p0", &Vec::new(), &vec![String::from("p1")], &game_dict)
+      .unwrap();
+    game_state
+      .draw_phase("p0", &Vec::new(), &Vec::new(), &game_dict)
+      .unwrap();
+    assert_eq!(game_state.player_data["p0"].hand.len(), 3);
+
+    game_state.start_turn(&game_dict);
+    assert!(game_state.player_data["p0"].character == Character::VeraCuster);
+  }
+
+  #[test]
+  fn sean_mallory_holds_up_to_ten_cards() {
+    let game_dict = game_dict();
+    let mut game_state = game_state(4);
+    set_character(&mut game_state, "p0", Character::SeanMallory);
+    for _ in 0..10 {
+      give(&mut game_state, "p0", card(CardName::Bang, CardSuit::Clubs));
+    }
+    game_state.end_play_phase("p0", &game_dict).unwrap();
+    assert_eq!(game_state.get_active_player(), "p1");
+  }
+}
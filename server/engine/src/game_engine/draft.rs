@@ -0,0 +1,221 @@
+use crate::{game_engine, shared_types};
+
+/// The number of characters each player gets to choose from
+pub const CHARACTER_CHOICE_COUNT: usize = 2;
+
+/// The number of seconds players have to choose a character before one is picked for them
+pub const CHARACTER_DRAFT_TIMEOUT_SECS: u64 = 30;
+
+/// The Character Draft
+///
+/// Before the first turn each player is dealt characters to choose between.
+/// Hands are only dealt once every player has chosen,
+/// since the chosen character decides their health and hand size.
+impl game_engine::types::GameState {
+  /// Whether players are still choosing their characters
+  pub fn is_drafting(&self) -> bool {
+    !self.character_choices.is_empty()
+  }
+
+  /// Sends each player the characters they can choose between
+  pub fn get_character_choice_messages(&self) -> game_engine::types::GameMessages {
+    return self
+      .character_choices
+      .iter()
+      .map(|(player, characters)| {
+        (
+          player.clone(),
+          shared_types::ServerEvent::from_event(
+            shared_types::ServerEventCode::CharacterChoices,
+            shared_types::ServerEventData {
+              client_id: Some(player.clone()),
+              characters: Some(characters.clone()),
+              ..Default::default()
+            },
+          ),
+        )
+      })
+      .collect();
+  }
+
+  /// The player picks one of the characters they were dealt
+  pub fn choose_character(
+    &mut self,
+    player: &str,
+    character: &shared_types::Character,
+    game_dict: &game_engine::types::GameDictionary,
+  ) -> Result<game_engine::types::GameMessages, game_engine::types::RuleError> {
+    if !self.is_drafting() {
+      return Err(game_engine::types::RuleError::InvalidPhase);
+    }
+    match self.character_choices.get(player) {
+      Some(characters) if characters.contains(character) => {}
+      Some(_) => return Err(game_engine::types::RuleError::InvalidSelection),
+      None => return Err(game_engine::types::RuleError::NotYourTurn),
+    }
+
+    self.character_choices.remove(player);
+    self.set_character(player, character, game_dict);
+    if self.is_drafting() {
+      return Ok(Vec::new());
+    }
+    return Ok(self.finish_draft(game_dict));
+  }
+
+  /// Picks the first character dealt to each player who has not chosen yet
+  pub fn auto_choose_characters(
+    &mut self,
+    game_dict: &game_engine::types::GameDictionary,
+  ) -> game_engine::types::GameMessages {
+    if !self.is_drafting() {
+      return Vec::new();
+    }
+    let choices: Vec<(String, Vec<shared_types::Character>)> =
+      self.character_choices.drain().collect();
+    for (player, characters) in choices {
+      if let Some(character) = characters.first() {
+        self.set_character(&player, character, game_dict);
+      }
+    }
+    return self.finish_draft(game_dict);
+  }
+
+  /// Gives the player their character, with health to match.
+  /// The Sheriff gets an extra life point.
+  fn set_character(
+    &mut self,
+    player: &str,
+    character: &shared_types::Character,
+    game_dict: &game_engine::types::GameDictionary,
+  ) {
+    let hp = match game_dict.character_dict.get(character) {
+      Some(character_data) => character_data.hp,
+      None => return,
+    };
+    if let Some(player_data) = self.player_data.get_mut(player) {
+      player_data.character = character.clone();
+      player_data.max_health = hp + (player_data.role == shared_types::Role::Sheriff) as u8;
+      player_data.health = player_data.max_health;
+    }
+  }
+
+  /// Deals each player as many cards as they have health and starts the first turn
+  fn finish_draft(
+    &mut self,
+    game_dict: &game_engine::types::GameDictionary,
+  ) -> game_engine::types::GameMessages {
+    let mut messages = Vec::new();
+    for player in self.player_order.clone() {
+      let health = match self.player_data.get(&player) {
+        Some(player_data) => player_data.health,
+        None => continue,
+      };
+      messages.extend(self.draw_cards(&player, health as usize));
+    }
+
+    messages.extend(self.get_update_messages());
+    messages.extend(self.start_turn(game_dict));
+    return messages;
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::game_engine::test_utils::{game_dict, game_state};
+  use crate::game_engine::types::{GameState, RuleError};
+  use crate::shared_types::{Character, Role, TurnPhase};
+
+  /// A game of four where "p0" is the Sheriff and each player is choosing between two characters
+  fn drafting_game() -> GameState {
+    let mut game_state = game_state(4);
+    game_state.turn_phase = TurnPhase::Start;
+    game_state.deck =
+      crate::game_engine::data::generate_deck(&Vec::new(), &game_dict(), &mut game_state.rng);
+    game_state.player_data.get_mut("p0").unwrap().role = Role::Sheriff;
+    let choices = [
+      [Character::PaulRegret, Character::BartCassidy],
+      [Character::ElGringo, Character::LuckyDuke],
+      [Character::RoseDoolan, Character::WillyTheKid],
+      [Character::SidKetchum, Character::SlabTheKiller],
+    ];
+    for (i, characters) in choices.iter().enumerate() {
+      let player = format!("p{}", i);
+      let player_data = game_state.player_data.get_mut(&player).unwrap();
+      player_data.health = 0;
+      player_data.max_health = 0;
+      game_state
+        .character_choices
+        .insert(player, characters.to_vec());
+    }
+    return game_state;
+  }
+
+  #[test]
+  fn only_dealt_characters_can_be_chosen() {
+    let game_dict = game_dict();
+    let mut game_state = drafting_game();
+    assert_eq!(
+      game_state
+        .choose_character("p0", &Character::ElGringo, &game_dict)
+        .err(),
+      Some(RuleError::InvalidSelection)
+    );
+    assert!(game_state
+      .choose_character("p0", &Character::PaulRegret, &game_dict)
+      .is_ok());
+    assert_eq!(
+      game_state
+        .choose_character("p0", &Character::BartCassidy, &game_dict)
+        .err(),
+      Some(RuleError::NotYourTurn)
+    );
+  }
+
+  #[test]
+  fn hands_are_dealt_once_everyone_has_chosen() {
+    let game_dict = game_dict();
+    let mut game_state = drafting_game();
+    let picks = [
+      ("p0", Character::PaulRegret),
+      ("p1", Character::ElGringo),
+      ("p2", Character::WillyTheKid),
+    ];
+    for (player, character) in picks.iter() {
+      game_state
+        .choose_character(player, character, &game_dict)
+        .unwrap();
+      assert!(game_state.player_data[*player].hand.is_empty());
+    }
+    assert!(game_state.is_drafting());
+
+    game_state
+      .choose_character("p3", &Character::SlabTheKiller, &game_dict)
+      .unwrap();
+    assert!(!game_state.is_drafting());
+    assert!(game_state.turn_phase == TurnPhase::Draw);
+
+    // the Sheriff gets an extra life point on top of the character
+    let expected_health = [("p0", 4), ("p1", 3), ("p2", 4), ("p3", 4)];
+    for (player, health) in expected_health.iter() {
+      let player_data = &game_state.player_data[*player];
+      assert_eq!(player_data.max_health, *health);
+      assert_eq!(player_data.health, *health);
+      assert_eq!(player_data.hand.len(), *health as usize);
+    }
+  }
+
+  #[test]
+  fn players_who_run_out_of_time_get_their_first_character() {
+    let game_dict = game_dict();
+    let mut game_state = drafting_game();
+    game_state
+      .choose_character("p1", &Character::LuckyDuke, &game_dict)
+      .unwrap();
+
+    game_state.auto_choose_characters(&game_dict);
+    assert!(!game_state.is_drafting());
+    assert!(game_state.player_data["p0"].character == Character::PaulRegret);
+    assert!(game_state.player_data["p1"].character == Character::LuckyDuke);
+    assert_eq!(game_state.player_data["p0"].hand.len(), 4);
+  }
+}
@@ -0,0 +1,124 @@
+use crate::{game_engine, shared_types};
+
+/// The number of cards Lucky Duke flips for each "draw!" check
+const LUCKY_DUKE_FLIP_COUNT: usize = 2;
+
+/// "Draw!" checks
+///
+/// Cards such as Barrel, Dynamite and Jail, and some characters, flip the top card of the deck
+/// and test its suit or rank to decide what happens. The flipped cards are shown to everyone
+/// and then discarded.
+impl game_engine::types::GameState {
+  /// Flips cards off the top of the deck for a "draw!" check made by a player.
+  ///
+  /// Lucky Duke flips two cards and uses whichever is better for him,
+  /// and a Horseshoe flips one more card in the same way.
+  /// Returns `None` if there are no cards left in the deck or discard pile to flip.
+  pub fn draw_check(
+    &mut self,
+    player: &str,
+    passes: fn(&shared_types::Card) -> bool,
+  ) -> Option<game_engine::types::DrawCheck> {
+    let mut flip_count = match self.has_character(player, shared_types::Character::LuckyDuke) {
+      true => LUCKY_DUKE_FLIP_COUNT,
+      false => 1,
+    };
+    if self.has_shop_equipment(player, shared_types::ShopCard::Horseshoe) {
+      flip_count += 1;
+    }
+
+    let mut messages = Vec::new();
+    let mut flipped: Vec<shared_types::Card> = Vec::new();
+    for _ in 0..flip_count {
+      messages.extend(self.refill_deck());
+      if let Some(card) = self.deck.pop() {
+        flipped.push(card);
+      }
+    }
+    let card = flipped
+      .iter()
+      .find(|card| passes(card))
+      .or_else(|| flipped.first())?
+      .clone();
+    self.discard.extend(flipped.iter().cloned());
+
+    messages.extend(self.broadcast(&shared_types::ServerEvent::from_event(
+      shared_types::ServerEventCode::CardsRevealed,
+      shared_types::ServerEventData {
+        client_id: Some(player.to_string()),
+        cards: Some(flipped),
+        ..Default::default()
+      },
+    )));
+
+    return Some(game_engine::types::DrawCheck {
+      passed: passes(&card),
+      card,
+      messages,
+    });
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::game_engine::test_utils::{card, game_state};
+  use crate::shared_types::{Card, CardName, CardSuit, Character, ServerEventCode};
+
+  fn is_heart(card: &Card) -> bool {
+    card.suit == CardSuit::Hearts
+  }
+
+  #[test]
+  fn the_flipped_card_is_discarded_and_shown_to_everyone() {
+    let mut game_state = game_state(4);
+    game_state.deck = vec![
+      card(CardName::Bang, CardSuit::Clubs),
+      card(CardName::Bang, CardSuit::Hearts),
+    ];
+
+    let draw_check = game_state.draw_check("p0", is_heart).unwrap();
+    assert!(draw_check.passed);
+    assert_eq!(draw_check.card, card(CardName::Bang, CardSuit::Hearts));
+    assert_eq!(
+      game_state.discard,
+      vec![card(CardName::Bang, CardSuit::Hearts)]
+    );
+    assert_eq!(game_state.deck.len(), 1);
+    assert_eq!(draw_check.messages.len(), 4);
+    assert!(draw_check
+      .messages
+      .iter()
+      .all(|(_, message)| matches!(message.event_code, ServerEventCode::CardsRevealed)));
+  }
+
+  #[test]
+  fn lucky_duke_uses_the_better_of_two_cards() {
+    let mut game_state = game_state(4);
+    game_state.player_data.get_mut("p0").unwrap().character = Character::LuckyDuke;
+    game_state.deck = vec![
+      card(CardName::Bang, CardSuit::Hearts),
+      card(CardName::Bang, CardSuit::Spades),
+    ];
+
+    let draw_check = game_state.draw_check("p0", is_heart).unwrap();
+    assert!(draw_check.passed);
+    assert!(game_state.deck.is_empty());
+    assert_eq!(game_state.discard.len(), 2);
+  }
+
+  #[test]
+  fn the_discard_pile_is_reshuffled_when_the_deck_runs_out() {
+    let mut game_state = game_state(4);
+    game_state.discard = vec![card(CardName::Bang, CardSuit::Spades)];
+
+    let draw_check = game_state.draw_check("p0", is_heart).unwrap();
+    assert!(!draw_check.passed);
+    assert_eq!(
+      game_state.discard,
+      vec![card(CardName::Bang, CardSuit::Spades)]
+    );
+
+    game_state.discard.clear();
+    assert!(game_state.draw_check("p0", is_heart).is_none());
+  }
+}
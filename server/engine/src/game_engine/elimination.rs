@@ -0,0 +1,444 @@
+use crate::{game_engine, shared_types};
+use std::collections::HashMap;
+
+/// The number of cards drawn as a reward for eliminating an Outlaw
+const OUTLAW_BOUNTY_CARD_COUNT: usize = 3;
+
+/// A Beer can not save a player once only this many players are left alive
+const BEER_MIN_LIVING_PLAYERS: usize = 3;
+
+impl game_engine::types::GameState {
+  /// Takes health away from a player, eliminating them if it runs out.
+  ///
+  /// A player who would lose their last life point drinks any Beer left in their hand
+  /// to stay in the game, as long as enough players are still alive for Beer to work
+  /// and Hangover is not in play.
+  pub fn damage_player(
+    &mut self,
+    target: &str,
+    amount: u8,
+    source: Option<&str>,
+    game_dict: &game_engine::types::GameDictionary,
+  ) -> game_engine::types::GameMessages {
+    let living_player_count = self.player_order.len();
+    let beer_allowed = living_player_count >= BEER_MIN_LIVING_PLAYERS
+      && !self.is_event_active(shared_types::EventCard::Hangover);
+    // ghosts are already dead
+    if self.ghosts.iter().any(|ghost| ghost == target) {
+      return Vec::new();
+    }
+    let player_data = match self.player_data.get_mut(target) {
+      Some(player_data) if player_data.is_alive() => player_data,
+      _ => return Vec::new(),
+    };
+
+    let mut health = player_data.health as i16 - amount as i16;
+    while health <= 0 && beer_allowed {
+      match player_data
+        .hand
+        .iter()
+        .position(|card| card.name == shared_types::CardName::Beer)
+      {
+        Some(beer_index) => {
+          self.discard.push(player_data.hand.remove(beer_index));
+          health += 1;
+        }
+        None => break,
+      }
+    }
+    player_data.health = health.max(0) as u8;
+
+    if !player_data.is_alive() {
+      return self.eliminate_player(target, source, game_dict);
+    }
+    // a player with Boots draws a card for each life point lost
+    let mut messages = Vec::new();
+    if self.has_shop_equipment(target, shared_types::ShopCard::Boots) {
+      messages.extend(self.draw_cards(target, amount as usize));
+    }
+    messages.extend(self.trigger_abilities(
+      &vec![target.to_string()],
+      &game_engine::types::TriggerContext {
+        trigger: game_engine::types::EventTrigger::HealthLost,
+        source,
+        amount,
+        cards: &Vec::new(),
+        targets: &Vec::new(),
+      },
+      game_dict,
+    ));
+    return messages;
+  }
+
+  /// Removes a player from the game after their health runs out.
+  ///
+  /// Their role is revealed to everyone, their cards are discarded and they leave the turn order.
+  /// The game ends if a team has met its victory condition, otherwise the killer
+  /// is penalized for eliminating a Deputy as the Sheriff or rewarded for eliminating an Outlaw.
+  pub fn eliminate_player(
+    &mut self,
+    target: &str,
+    killer: Option<&str>,
+    game_dict: &game_engine::types::GameDictionary,
+  ) -> game_engine::types::GameMessages {
+    // other players may take the cards before they are discarded
+    let others = self.get_other_players(target);
+    let mut messages = self.trigger_abilities(
+      &others,
+      &game_engine::types::TriggerContext {
+        trigger: game_engine::types::EventTrigger::PlayerEliminated,
+        source: Some(target),
+        amount: 0,
+        cards: &Vec::new(),
+        targets: &Vec::new(),
+      },
+      game_dict,
+    );
+
+    self.return_shop_equipment(target);
+    let role = match self.player_data.get_mut(target) {
+      Some(player_data) => {
+        let cards: Vec<shared_types::Card> = player_data.card_iter().cloned().collect();
+        player_data.hand.clear();
+        player_data.field.clear();
+        self.discard.extend(cards);
+        player_data.role.clone()
+      }
+      None => return messages,
+    };
+
+    messages.extend(
+      self.broadcast(&shared_types::ServerEvent::from_event(
+        shared_types::ServerEventCode::PlayerEliminated,
+        shared_types::ServerEventData {
+          client_id: Some(target.to_string()),
+          roles: Some(
+            vec![(target.to_string(), role.clone())]
+              .into_iter()
+              .collect(),
+          ),
+          ..Default::default()
+        },
+      )),
+    );
+
+    //======================================================
+    // remove the player from the turn order,
+    // keeping the turn with the player who currently has it
+    //======================================================
+    let was_active_player = self.get_active_player() == target;
+    if let Some(seat) = self.player_order.iter().position(|id| id == target) {
+      self.player_order.remove(seat);
+      if seat < self.turn_index {
+        self.turn_index -= 1;
+      } else if seat == self.turn_index && !self.player_order.is_empty() {
+        self.turn_index %= self.player_order.len();
+      }
+    }
+
+    if let Some(winner_ids) = self.get_winner_ids() {
+      messages.extend(self.end_game(winner_ids));
+      return messages;
+    }
+
+    if let Some(killer) = killer.filter(|killer| *killer != target) {
+      let killer_role = self
+        .player_data
+        .get(killer)
+        .filter(|player_data| player_data.is_alive())
+        .map(|player_data| player_data.role.clone());
+
+      match (killer_role, role) {
+        (Some(shared_types::Role::Sheriff), shared_types::Role::Deputy) => {
+          if let Some(player_data) = self.player_data.get_mut(killer) {
+            let cards: Vec<shared_types::Card> = player_data.card_iter().cloned().collect();
+            player_data.hand.clear();
+            player_data.field.clear();
+            self.discard.extend(cards);
+          }
+        }
+        (Some(_), shared_types::Role::Outlaw) => {
+          messages.extend(self.draw_cards(killer, OUTLAW_BOUNTY_CARD_COUNT));
+        }
+        _ => {}
+      }
+      self.earn_gold(killer, game_engine::gold_rush::GOLD_PER_ELIMINATION);
+    }
+
+    messages.extend(self.remove_responder(target, game_dict));
+    if was_active_player {
+      messages.extend(self.start_turn(game_dict));
+    }
+    return messages;
+  }
+
+  /// Checks the victory conditions of each team, giving the ids of the winning players if the game is over.
+  ///
+  /// - The Sheriff and Deputies win once every Outlaw and Renegade is eliminated
+  /// - The Renegade wins by being the last player standing after the Sheriff is eliminated
+  /// - The Outlaws win if the Sheriff is eliminated in any other way
+  pub fn get_winner_ids(&self) -> Option<Vec<String>> {
+    // ghosts do not count towards the victory of their team
+    let living_ids: Vec<String> = self
+      .player_order
+      .iter()
+      .filter(|id| !self.ghosts.contains(id))
+      .cloned()
+      .collect();
+    let living_roles: Vec<&shared_types::Role> = living_ids
+      .iter()
+      .filter_map(|id| self.player_data.get(id))
+      .map(|player_data| &player_data.role)
+      .collect();
+
+    let players_with_roles = |roles: &[shared_types::Role]| -> Vec<String> {
+      self
+        .player_data
+        .iter()
+        .filter(|(_, player_data)| roles.contains(&player_data.role))
+        .map(|(id, _)| id.clone())
+        .collect()
+    };
+
+    if !living_roles.contains(&&shared_types::Role::Sheriff) {
+      if living_roles == vec![&shared_types::Role::Renegade] {
+        return Some(living_ids.clone());
+      }
+      return Some(players_with_roles(&[shared_types::Role::Outlaw]));
+    }
+
+    if !living_roles
+      .iter()
+      .any(|role| **role == shared_types::Role::Outlaw || **role == shared_types::Role::Renegade)
+    {
+      return Some(players_with_roles(&[
+        shared_types::Role::Sheriff,
+        shared_types::Role::Deputy,
+      ]));
+    }
+
+    return None;
+  }
+
+  /// Stops the game and reveals every role along with the winners
+  fn end_game(&mut self, winner_ids: Vec<String>) -> game_engine::types::GameMessages {
+    self.turn_phase = shared_types::TurnPhase::End;
+    self.event_stack.clear();
+    self.card_events.clear();
+    self.response_queue.clear();
+    self.trigger_queue.clear();
+
+    let roles: HashMap<String, shared_types::Role> = self
+      .player_data
+      .iter()
+      .map(|(id, player_data)| (id.clone(), player_data.role.clone()))
+      .collect();
+
+    return self.broadcast(&shared_types::ServerEvent::from_event(
+      shared_types::ServerEventCode::GameOver,
+      shared_types::ServerEventData {
+        roles: Some(roles),
+        winner_ids: Some(winner_ids),
+        ..Default::default()
+      },
+    ));
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::game_engine::test_utils::{card, game_dict, game_state};
+  use crate::game_engine::types::GameState;
+  use crate::shared_types::{CardName, CardSuit, Role, ServerEventCode};
+
+  /// Seats one player for each role, in order, starting from "p0"
+  fn seat_roles(roles: &[Role]) -> GameState {
+    let mut game_state = game_state(roles.len());
+    for (i, role) in roles.iter().enumerate() {
+      game_state
+        .player_data
+        .get_mut(&format!("p{}", i))
+        .unwrap()
+        .role = role.clone();
+    }
+    for _ in 0..10 {
+      game_state.deck.push(card(CardName::Bang, CardSuit::Hearts));
+    }
+    return game_state;
+  }
+
+  fn game_over_winners(
+    messages: &[(String, crate::shared_types::ServerEvent)],
+  ) -> Option<Vec<String>> {
+    messages
+      .iter()
+      .find(|(_, message)| matches!(message.event_code, ServerEventCode::GameOver))
+      .and_then(|(_, message)| message.data.as_ref())
+      .and_then(|data| data.winner_ids.clone())
+      .map(|mut winner_ids| {
+        winner_ids.sort();
+        winner_ids
+      })
+  }
+
+  const FIVE_PLAYERS: [Role; 5] = [
+    Role::Sheriff,
+    Role::Renegade,
+    Role::Outlaw,
+    Role::Outlaw,
+    Role::Deputy,
+  ];
+
+  #[test]
+  fn damage_past_zero_health_eliminates_without_underflow() {
+    let game_dict = game_dict();
+    let mut game_state = seat_roles(&FIVE_PLAYERS);
+    game_state.player_data.get_mut("p2").unwrap().health = 1;
+    game_state.player_data.get_mut("p2").unwrap().hand =
+      vec![card(CardName::Bang, CardSuit::Hearts)];
+
+    game_state.damage_player("p2", 3, None, &game_dict);
+
+    assert_eq!(game_state.player_data["p2"].health, 0);
+    assert!(game_state.player_data["p2"].hand.is_empty());
+    assert!(!game_state.player_order.contains(&String::from("p2")));
+    assert_eq!(
+      game_state.discard,
+      vec![card(CardName::Bang, CardSuit::Hearts)]
+    );
+  }
+
+  #[test]
+  fn beer_saves_a_player_from_their_last_life_point() {
+    let game_dict = game_dict();
+    let mut game_state = seat_roles(&FIVE_PLAYERS);
+    game_state.player_data.get_mut("p2").unwrap().health = 1;
+    game_state.player_data.get_mut("p2").unwrap().hand =
+      vec![card(CardName::Beer, CardSuit::Hearts)];
+
+    game_state.damage_player("p2", 1, None, &game_dict);
+
+    assert_eq!(game_state.player_data["p2"].health, 1);
+    assert!(game_state.player_data["p2"].hand.is_empty());
+    assert!(game_state.player_order.contains(&String::from("p2")));
+  }
+
+  #[test]
+  fn beer_does_not_work_with_two_players_left() {
+    let game_dict = game_dict();
+    let mut game_state = seat_roles(&[Role::Sheriff, Role::Renegade]);
+    game_state.player_data.get_mut("p1").unwrap().health = 1;
+    game_state.player_data.get_mut("p1").unwrap().hand =
+      vec![card(CardName::Beer, CardSuit::Hearts)];
+
+    let messages = game_state.damage_player("p1", 1, Some("p0"), &game_dict);
+
+    assert_eq!(game_state.player_data["p1"].health, 0);
+    assert_eq!(game_over_winners(&messages), Some(vec![String::from("p0")]));
+  }
+
+  #[test]
+  fn eliminating_a_player_keeps_the_current_turn() {
+    let game_dict = game_dict();
+    let mut game_state = seat_roles(&FIVE_PLAYERS);
+    game_state.turn_index = 3;
+
+    game_state.eliminate_player("p1", Some("p3"), &game_dict);
+
+    assert_eq!(game_state.get_active_player(), "p3");
+    assert_eq!(
+      game_state.get_player_distance("p0", "p2", &game_dict),
+      Some(1)
+    );
+  }
+
+  #[test]
+  fn eliminating_the_active_player_starts_the_next_turn() {
+    let game_dict = game_dict();
+    let mut game_state = seat_roles(&FIVE_PLAYERS);
+    game_state.turn_index = 4;
+
+    game_state.eliminate_player("p4", None, &game_dict);
+
+    assert_eq!(game_state.get_active_player(), "p0");
+    assert_eq!(game_state.turn_phase, crate::shared_types::TurnPhase::Draw);
+  }
+
+  #[test]
+  fn eliminating_an_outlaw_pays_a_bounty() {
+    let game_dict = game_dict();
+    let mut game_state = seat_roles(&FIVE_PLAYERS);
+
+    game_state.eliminate_player("p2", Some("p1"), &game_dict);
+
+    assert_eq!(game_state.player_data["p1"].hand.len(), 3);
+  }
+
+  #[test]
+  fn sheriff_eliminating_a_deputy_discards_everything() {
+    let game_dict = game_dict();
+    let mut game_state = seat_roles(&FIVE_PLAYERS);
+    let sheriff = game_state.player_data.get_mut("p0").unwrap();
+    sheriff.hand = vec![
+      card(CardName::Bang, CardSuit::Hearts),
+      card(CardName::Missed, CardSuit::Hearts),
+    ];
+    sheriff.field = vec![card(CardName::Mustang, CardSuit::Hearts)];
+
+    game_state.eliminate_player("p4", Some("p0"), &game_dict);
+
+    assert_eq!(game_state.player_data["p0"].card_iter().count(), 0);
+    assert_eq!(game_state.discard.len(), 3);
+  }
+
+  #[test]
+  fn sheriff_and_deputies_win_once_outlaws_and_renegade_are_gone() {
+    let game_dict = game_dict();
+    let mut game_state = seat_roles(&FIVE_PLAYERS);
+
+    assert_eq!(
+      game_over_winners(&game_state.eliminate_player("p1", None, &game_dict)),
+      None
+    );
+    assert_eq!(
+      game_over_winners(&game_state.eliminate_player("p2", None, &game_dict)),
+      None
+    );
+    let messages = game_state.eliminate_player("p3", None, &game_dict);
+
+    assert_eq!(
+      game_over_winners(&messages),
+      Some(vec![String::from("p0"), String::from("p4")])
+    );
+    assert!(game_state
+      .check_phase(crate::shared_types::TurnPhase::Play)
+      .is_err());
+  }
+
+  #[test]
+  fn outlaws_win_when_the_sheriff_falls() {
+    let game_dict = game_dict();
+    let mut game_state = seat_roles(&FIVE_PLAYERS);
+    game_state.eliminate_player("p2", None, &game_dict);
+
+    let messages = game_state.eliminate_player("p0", Some("p3"), &game_dict);
+
+    assert_eq!(
+      game_over_winners(&messages),
+      Some(vec![String::from("p2"), String::from("p3")])
+    );
+  }
+
+  #[test]
+  fn renegade_wins_only_when_left_alone() {
+    let game_dict = game_dict();
+    let mut game_state = seat_roles(&FIVE_PLAYERS);
+    for player in ["p2", "p3", "p4"].iter() {
+      game_state.eliminate_player(player, Some("p1"), &game_dict);
+    }
+
+    let messages = game_state.eliminate_player("p0", Some("p1"), &game_dict);
+
+    assert_eq!(game_over_winners(&messages), Some(vec![String::from("p1")]));
+  }
+}
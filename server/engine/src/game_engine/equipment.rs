@@ -0,0 +1,223 @@
+use crate::{game_engine, shared_types};
+
+/// Equipment
+///
+/// Blue cards stay in front of the player who plays them, on their field.
+/// A player carries a single weapon and never two copies of the same card,
+/// and the passive effects of their equipment are checked by the game logic they affect.
+impl game_engine::types::GameState {
+  /// Whether a player has a card with the given name in front of them
+  pub fn has_equipment(&self, player: &str, card: shared_types::CardName) -> bool {
+    return match self.player_data.get(player) {
+      Some(player_data) => player_data
+        .field
+        .iter()
+        .any(|field_card| field_card.name == card),
+      None => false,
+    };
+  }
+
+  /// Whether the cards in front of a player have any effect,
+  /// which they do not during the turn of Belle Star unless they are hers
+  pub fn is_field_active(&self, player: &str) -> bool {
+    if self.is_event_active(shared_types::EventCard::Lasso) {
+      return false;
+    }
+    let active_player = match self.player_order.get(self.turn_index) {
+      Some(active_player) => active_player,
+      None => return true,
+    };
+    return active_player == player
+      || !self.has_character(active_player, shared_types::Character::BelleStar);
+  }
+
+  /// Checks that a card can be put in front of a player who does not have one like it already
+  pub fn check_equipment(
+    &self,
+    player: &str,
+    card: &shared_types::Card,
+  ) -> Result<(), game_engine::types::RuleError> {
+    if self.has_equipment(player, card.name.clone()) {
+      return Err(game_engine::types::RuleError::DuplicateEquipment {
+        target: player.to_string(),
+        card: card.name.clone(),
+      });
+    }
+    return Ok(());
+  }
+
+  /// Moves cards from the hand of a player onto their field.
+  ///
+  /// A new weapon replaces the one the player had, which is discarded.
+  pub fn equip_cards(
+    &mut self,
+    player: &str,
+    cards: &Vec<shared_types::Card>,
+    game_dict: &game_engine::types::GameDictionary,
+  ) {
+    let is_weapon = |card: &shared_types::Card| match game_dict.card_dict.get(&card.name) {
+      Some(card_data) => card_data.weapon_range.is_some(),
+      None => false,
+    };
+    let player_data = match self.player_data.get_mut(player) {
+      Some(player_data) => player_data,
+      None => return,
+    };
+    player_data.remove_cards(cards);
+    if cards.iter().any(is_weapon) {
+      let (weapons, field): (Vec<shared_types::Card>, Vec<shared_types::Card>) = player_data
+        .field
+        .drain(..)
+        .partition(|card| is_weapon(card));
+      player_data.field = field;
+      self.discard.extend(weapons);
+    }
+    player_data.field.extend(cards.iter().cloned());
+  }
+
+  /// Checks that the chosen field card, if any, is in front of the target,
+  /// otherwise that the target has cards in their hand to take from
+  pub fn check_field_selection(&self, target: &str) -> Result<(), game_engine::types::RuleError> {
+    let player_data = match self.player_data.get(target) {
+      Some(player_data) => player_data,
+      None => {
+        return Err(game_engine::types::RuleError::InvalidTarget {
+          target: target.to_string(),
+        })
+      }
+    };
+    return match &self.field_selection {
+      Some(card) if !player_data.field.contains(card) => {
+        Err(game_engine::types::RuleError::FieldCardMissing {
+          target: target.to_string(),
+        })
+      }
+      None if player_data.hand.is_empty() => Err(game_engine::types::RuleError::EmptyHand {
+        target: target.to_string(),
+      }),
+      _ => Ok(()),
+    };
+  }
+
+  /// Removes the chosen field card from a player,
+  /// or a card at random from their hand when no field card was chosen
+  pub fn take_selected_card(&mut self, player: &str) -> Option<shared_types::Card> {
+    return match self.field_selection.take() {
+      Some(card) => {
+        let player_data = self.player_data.get_mut(player)?;
+        let index = player_data
+          .field
+          .iter()
+          .position(|field_card| *field_card == card)?;
+        Some(player_data.field.remove(index))
+      }
+      None => self.take_random_hand_card(player),
+    };
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::game_engine::test_utils::{card, game_dict, game_state};
+  use crate::game_engine::types::{GameState, RuleError};
+  use crate::shared_types::{Card, CardName, CardRank, CardSuit};
+
+  fn play(game_state: &mut GameState, name: CardName, targets: &[&str]) -> Result<(), RuleError> {
+    let game_dict = game_dict();
+    let targets: Vec<String> = targets.iter().map(|target| target.to_string()).collect();
+    let cards = vec![card(name.clone(), CardSuit::Spades)];
+    game_state
+      .player_data
+      .get_mut("p0")
+      .unwrap()
+      .hand
+      .push(cards[0].clone());
+    let card_data = &game_dict.card_dict[&name];
+    (card_data.preconditions)("p0", &cards, &targets, game_state, &game_dict)?;
+    (card_data.effect)("p0", &cards, &targets, game_state, &game_dict);
+    return Ok(());
+  }
+
+  #[test]
+  fn a_new_weapon_replaces_the_old_one() {
+    let mut game_state = game_state(2);
+    play(&mut game_state, CardName::Volcanic, &[]).unwrap();
+    play(&mut game_state, CardName::Mustang, &[]).unwrap();
+    play(&mut game_state, CardName::Winchester, &[]).unwrap();
+
+    assert_eq!(
+      game_state.player_data["p0"].field,
+      vec![
+        card(CardName::Mustang, CardSuit::Spades),
+        card(CardName::Winchester, CardSuit::Spades)
+      ]
+    );
+    assert_eq!(
+      game_state.discard,
+      vec![card(CardName::Volcanic, CardSuit::Spades)]
+    );
+    assert_eq!(game_state.get_player_range("p0", &game_dict()), 5);
+  }
+
+  #[test]
+  fn the_same_card_can_not_be_equipped_twice() {
+    let mut game_state = game_state(2);
+    play(&mut game_state, CardName::Barrel, &[]).unwrap();
+    assert_eq!(
+      play(&mut game_state, CardName::Barrel, &[]),
+      Err(RuleError::DuplicateEquipment {
+        target: String::from("p0"),
+        card: CardName::Barrel,
+      })
+    );
+    assert!(game_state.has_equipment("p0", CardName::Barrel));
+  }
+
+  #[test]
+  fn panic_can_take_a_chosen_field_card() {
+    let mut game_state = game_state(2);
+    game_state
+      .player_data
+      .get_mut("p1")
+      .unwrap()
+      .field
+      .push(card(CardName::Scope, CardSuit::Spades));
+
+    // nothing in hand to take at random
+    assert_eq!(
+      play(&mut game_state, CardName::Panic, &["p1"]),
+      Err(RuleError::EmptyHand {
+        target: String::from("p1"),
+      })
+    );
+
+    game_state.field_selection = Some(card(CardName::Scope, CardSuit::Spades));
+    play(&mut game_state, CardName::Panic, &["p1"]).unwrap();
+    assert!(game_state.player_data["p1"].field.is_empty());
+    assert!(game_state.player_data["p0"]
+      .hand
+      .contains(&card(CardName::Scope, CardSuit::Spades)));
+    assert_eq!(game_state.field_selection, None);
+  }
+
+  #[test]
+  fn a_barrel_can_avoid_a_shot() {
+    let mut game_state = game_state(2);
+    game_state
+      .player_data
+      .get_mut("p1")
+      .unwrap()
+      .field
+      .push(card(CardName::Barrel, CardSuit::Spades));
+    game_state.deck = vec![Card {
+      name: CardName::Beer,
+      suit: CardSuit::Hearts,
+      rank: CardRank::N5,
+    }];
+
+    let (avoided, _) = game_state.avoids_shot("p1");
+    assert!(avoided);
+    let (avoided, _) = game_state.avoids_shot("p0");
+    assert!(!avoided);
+  }
+}
@@ -0,0 +1,463 @@
+use crate::{game_engine, shared_types};
+
+/// The number of cards drawn during the draw phase while Thirst is in play
+const THIRST_DRAW_COUNT: usize = 1;
+
+/// The number of Bangs a player can play during their turn while Shootout is in play
+const SHOOTOUT_BANG_LIMIT: u8 = 2;
+
+/// The damage dealt at the start of every turn once High Noon is in play
+const HIGH_NOON_DAMAGE: u8 = 1;
+
+/// The Event Deck
+///
+/// With High Noon or A Fistful of Cards in play, the Sheriff reveals an event card
+/// at the start of each of their turns. The event changes the rules for every player
+/// until the next one is revealed, and the last event stays in play once the deck runs out.
+///
+/// The game logic asks the hooks below how the current event changes the rules it applies.
+impl game_engine::types::GameState {
+  /// Whether the given event is the one currently in play
+  pub fn is_event_active(&self, event: shared_types::EventCard) -> bool {
+    return self.current_event == Some(event);
+  }
+
+  /// Resolves the event deck at the start of the turn of a player,
+  /// revealing a new event if the player is the Sheriff.
+  pub fn resolve_turn_event(
+    &mut self,
+    player: &str,
+    game_dict: &game_engine::types::GameDictionary,
+  ) -> game_engine::types::GameMessages {
+    let mut messages = Vec::new();
+    let is_sheriff = match self.player_data.get(player) {
+      Some(player_data) => player_data.role == shared_types::Role::Sheriff,
+      None => false,
+    };
+    if is_sheriff {
+      messages.extend(self.reveal_event(player));
+    }
+    if self.is_event_active(shared_types::EventCard::HighNoon) {
+      messages.extend(self.damage_player(player, HIGH_NOON_DAMAGE, None, game_dict));
+    }
+    return messages;
+  }
+
+  /// Puts the next event card into play, resolving events that happen once when they are revealed
+  fn reveal_event(&mut self, player: &str) -> game_engine::types::GameMessages {
+    let event = match self.event_deck.pop() {
+      Some(event) => event,
+      None => return Vec::new(),
+    };
+    self.current_event = Some(event.clone());
+
+    // the players with the least health regain one
+    if event == shared_types::EventCard::TheDoctor {
+      let lowest_health = self
+        .player_order
+        .iter()
+        .filter_map(|id| self.player_data.get(id))
+        .map(|player_data| player_data.health)
+        .min();
+      if let Some(lowest_health) = lowest_health {
+        let patients: Vec<String> = self
+          .player_order
+          .iter()
+          .filter(|id| match self.player_data.get(id.as_str()) {
+            Some(player_data) => player_data.health == lowest_health,
+            None => false,
+          })
+          .cloned()
+          .collect();
+        for patient in patients {
+          self.heal_player(&patient, 1);
+        }
+      }
+    }
+
+    return self.broadcast(&shared_types::ServerEvent::from_event(
+      shared_types::ServerEventCode::EventRevealed,
+      shared_types::ServerEventData {
+        client_id: Some(player.to_string()),
+        game_data: Some(self.to_game_data()),
+        ..Default::default()
+      },
+    ));
+  }
+
+  /// Whether the current event stops a player from playing a card
+  ///
+  /// - Hangover: nobody can drink a Beer
+  /// - Sermon: the active player can not play a Bang!
+  /// - The Judge: no cards can be put in front of a player
+  pub fn is_card_disabled(
+    &self,
+    player: &str,
+    card: &shared_types::CardName,
+    game_dict: &game_engine::types::GameDictionary,
+  ) -> bool {
+    return match self.current_event {
+      Some(shared_types::EventCard::Hangover) => *card == shared_types::CardName::Beer,
+      Some(shared_types::EventCard::Sermon) => {
+        *card == shared_types::CardName::Bang && self.get_active_player() == player
+      }
+      Some(shared_types::EventCard::TheJudge) => match game_dict.card_dict.get(card) {
+        Some(card_data) => card_data.color != game_engine::types::CardColor::Brown,
+        None => false,
+      },
+      _ => false,
+    };
+  }
+
+  /// The number of Bangs a player without a Volcanic can play during their turn
+  pub fn get_bang_limit(&self) -> u8 {
+    if self.is_event_active(shared_types::EventCard::Shootout) {
+      return SHOOTOUT_BANG_LIMIT;
+    }
+    return 1;
+  }
+
+  /// The number of cards a player takes from the deck during their draw phase,
+  /// which is one more for a player with a Pickaxe
+  pub fn get_draw_count(&self, player: &str) -> usize {
+    let pickaxe = self.has_shop_equipment(player, shared_types::ShopCard::Pickaxe) as usize;
+    if self.is_event_active(shared_types::EventCard::Thirst) {
+      return THIRST_DRAW_COUNT + pickaxe;
+    }
+    return game_engine::turn::DRAW_PHASE_CARD_COUNT + pickaxe;
+  }
+
+  /// Whether every player is one seat away from the others (Ambush)
+  pub fn is_distance_fixed(&self) -> bool {
+    return self.is_event_active(shared_types::EventCard::Ambush);
+  }
+
+  //======================================================
+  // Ghost Town
+  //======================================================
+
+  /// The player whose turn comes after the given player.
+  ///
+  /// During Ghost Town the eliminated players take their turn as well.
+  pub fn get_next_turn_player(&self, player: &str) -> Option<String> {
+    let seat = self.seating.iter().position(|id| id == player)?;
+    let ghost_town = self.is_event_active(shared_types::EventCard::GhostTown);
+    return (1..=self.seating.len())
+      .map(|offset| &self.seating[(seat + offset) % self.seating.len()])
+      .find(|id| match self.player_data.get(id.as_str()) {
+        Some(player_data) => player_data.is_alive() || ghost_town,
+        None => false,
+      })
+      .cloned();
+  }
+
+  /// Brings an eliminated player back to their seat as a ghost for one turn.
+  /// Ghosts can not lose health.
+  pub fn raise_ghost(&mut self, player: &str) {
+    let seat = match self.seating.iter().position(|id| id == player) {
+      Some(seat) => seat,
+      None => return,
+    };
+    let index = self.seating[..seat]
+      .iter()
+      .filter(|id| self.player_order.contains(id))
+      .count();
+    self.player_order.insert(index, player.to_string());
+    if index <= self.turn_index && self.player_order.len() > 1 {
+      self.turn_index += 1;
+    }
+    if let Some(player_data) = self.player_data.get_mut(player) {
+      player_data.health = 1;
+    }
+    self.ghosts.push(player.to_string());
+  }
+
+  /// Returns a ghost to the grave at the end of their turn, discarding their cards
+  pub fn lay_ghost_to_rest(&mut self, player: &str) {
+    self.ghosts.retain(|id| id != player);
+    self.return_shop_equipment(player);
+    if let Some(player_data) = self.player_data.get_mut(player) {
+      let cards: Vec<shared_types::Card> = player_data.card_iter().cloned().collect();
+      player_data.hand.clear();
+      player_data.field.clear();
+      player_data.health = 0;
+      for card in cards.iter() {
+        self.usable_from.remove(card);
+      }
+      self.discard.extend(cards);
+    }
+    if let Some(seat) = self.player_order.iter().position(|id| id == player) {
+      self.player_order.remove(seat);
+      if seat < self.turn_index {
+        self.turn_index -= 1;
+      } else if seat == self.turn_index && !self.player_order.is_empty() {
+        self.turn_index %= self.player_order.len();
+      }
+    }
+  }
+}
+
+/// The events of High Noon, which always ends with High Noon itself
+pub fn get_high_noon_data() -> game_engine::types::ExpansionData {
+  return game_engine::expansions::event_expansion_data(
+    vec![
+      shared_types::EventCard::Thirst,
+      shared_types::EventCard::Hangover,
+      shared_types::EventCard::Sermon,
+      shared_types::EventCard::Shootout,
+      shared_types::EventCard::TheDoctor,
+      shared_types::EventCard::GhostTown,
+    ],
+    Some(shared_types::EventCard::HighNoon),
+  );
+}
+
+/// The events of A Fistful of Cards
+pub fn get_fistful_of_cards_data() -> game_engine::types::ExpansionData {
+  return game_engine::expansions::event_expansion_data(
+    vec![
+      shared_types::EventCard::Ambush,
+      shared_types::EventCard::Lasso,
+      shared_types::EventCard::TheJudge,
+    ],
+    None,
+  );
+}
+
+/// Creates a shuffled event deck from the events of the expansions in play,
+/// with their final events (ex. High Noon) revealed last.
+pub fn generate_event_deck(
+  expansions: &Vec<shared_types::Expansion>,
+  expansion_dict: &game_engine::types::ExpansionDictionary,
+  rng: &mut nanorand::WyRand,
+) -> Vec<shared_types::EventCard> {
+  let enabled: Vec<&game_engine::types::ExpansionData> = expansions
+    .iter()
+    .filter_map(|expansion| expansion_dict.get(expansion))
+    .collect();
+  let mut deck: Vec<shared_types::EventCard> = enabled
+    .iter()
+    .flat_map(|expansion_data| expansion_data.events.iter().cloned())
+    .collect();
+  game_engine::shuffle_deck(&mut deck, rng);
+  for expansion_data in enabled {
+    if let Some(final_event) = &expansion_data.final_event {
+      deck.insert(0, final_event.clone());
+    }
+  }
+  return deck;
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::game_engine::test_utils::{card, game_dict, game_state, game_with_sheriff};
+  use crate::game_engine::types::GameState;
+  use crate::shared_types::{
+    CardName, CardSuit, EventCard, Expansion, Role, ServerEventCode, TurnPhase,
+  };
+
+  /// A game where the Sheriff "p3" is about to start their turn with the given event on top of the deck
+  fn game_with_event(event: EventCard) -> GameState {
+    let mut game_state = game_with_sheriff();
+    game_state.turn_index = 3;
+    game_state.event_deck = vec![event];
+    game_state.deck = (0..10)
+      .map(|_| card(CardName::Bang, CardSuit::Clubs))
+      .collect();
+    return game_state;
+  }
+
+  #[test]
+  fn high_noon_is_the_last_event() {
+    let deck = generate_event_deck(
+      &vec![Expansion::HighNoon],
+      &game_dict().expansion_dict,
+      &mut nanorand::WyRand::new_seed(0),
+    );
+    assert_eq!(deck.len(), 7);
+    assert_eq!(deck[0], EventCard::HighNoon);
+
+    let deck = generate_event_deck(
+      &vec![Expansion::FistfulOfCards],
+      &game_dict().expansion_dict,
+      &mut nanorand::WyRand::new_seed(0),
+    );
+    assert_eq!(deck.len(), 3);
+    assert!(!deck.contains(&EventCard::HighNoon));
+    assert!(generate_event_deck(
+      &Vec::new(),
+      &game_dict().expansion_dict,
+      &mut nanorand::WyRand::new_seed(0)
+    )
+    .is_empty());
+  }
+
+  #[test]
+  fn the_sheriff_reveals_an_event_at_the_start_of_their_turn() {
+    let game_dict = game_dict();
+    let mut game_state = game_with_event(EventCard::Thirst);
+
+    game_state.turn_index = 2;
+    game_state.start_turn(&game_dict);
+    assert_eq!(game_state.current_event, None);
+
+    game_state.turn_index = 3;
+    let messages = game_state.start_turn(&game_dict);
+    assert!(messages
+      .iter()
+      .any(|(_, message)| matches!(message.event_code, ServerEventCode::EventRevealed)));
+    assert_eq!(game_state.current_event, Some(EventCard::Thirst));
+    assert_eq!(
+      game_state.to_game_data().current_event,
+      Some(EventCard::Thirst)
+    );
+
+    // the last event stays in play
+    game_state.start_turn(&game_dict);
+    assert_eq!(game_state.current_event, Some(EventCard::Thirst));
+  }
+
+  #[test]
+  fn thirst_draws_one_card() {
+    let game_dict = game_dict();
+    let mut game_state = game_with_event(EventCard::Thirst);
+    game_state.start_turn(&game_dict);
+    game_state
+      .draw_phase("p3", &Vec::new(), &Vec::new(), &game_dict)
+      .unwrap();
+    assert_eq!(game_state.player_data["p3"].hand.len(), 1);
+  }
+
+  #[test]
+  fn the_doctor_heals_the_weakest_players() {
+    let game_dict = game_dict();
+    let mut game_state = game_with_event(EventCard::TheDoctor);
+    game_state.player_data.get_mut("p0").unwrap().health = 2;
+    game_state.player_data.get_mut("p1").unwrap().health = 2;
+    game_state.player_data.get_mut("p2").unwrap().health = 3;
+    game_state.start_turn(&game_dict);
+    assert_eq!(game_state.player_data["p0"].health, 3);
+    assert_eq!(game_state.player_data["p1"].health, 3);
+    assert_eq!(game_state.player_data["p2"].health, 3);
+  }
+
+  #[test]
+  fn high_noon_hurts_every_player_at_the_start_of_their_turn() {
+    let game_dict = game_dict();
+    let mut game_state = game_with_event(EventCard::HighNoon);
+    game_state.start_turn(&game_dict);
+    assert_eq!(game_state.player_data["p3"].health, 3);
+  }
+
+  #[test]
+  fn hangover_stops_beer_from_saving_a_player() {
+    let game_dict = game_dict();
+    let mut game_state = game_with_sheriff();
+    game_state.current_event = Some(EventCard::Hangover);
+    let player_data = game_state.player_data.get_mut("p1").unwrap();
+    player_data.health = 1;
+    player_data.hand.push(card(CardName::Beer, CardSuit::Clubs));
+
+    assert!(game_state.is_card_disabled("p1", &CardName::Beer, &game_dict));
+    game_state.damage_player("p1", 1, None, &game_dict);
+    assert!(!game_state.player_data["p1"].is_alive());
+  }
+
+  #[test]
+  fn events_change_what_can_be_played() {
+    let game_dict = game_dict();
+    let mut game_state = game_state(4);
+    game_state.current_event = Some(EventCard::Sermon);
+    assert!(game_state.is_card_disabled("p0", &CardName::Bang, &game_dict));
+    assert!(!game_state.is_card_disabled("p1", &CardName::Bang, &game_dict));
+
+    game_state.current_event = Some(EventCard::TheJudge);
+    assert!(game_state.is_card_disabled("p0", &CardName::Barrel, &game_dict));
+    assert!(!game_state.is_card_disabled("p0", &CardName::Bang, &game_dict));
+
+    game_state.current_event = Some(EventCard::Shootout);
+    game_state.bangs_played = 1;
+    let bang = &game_dict.card_dict[&CardName::Bang];
+    let targets = vec![String::from("p1")];
+    assert!((bang.preconditions)(
+      "p0",
+      &vec![card(CardName::Bang, CardSuit::Clubs)],
+      &targets,
+      &mut game_state,
+      &game_dict
+    )
+    .is_ok());
+    game_state.bangs_played = 2;
+    assert!((bang.preconditions)(
+      "p0",
+      &vec![card(CardName::Bang, CardSuit::Clubs)],
+      &targets,
+      &mut game_state,
+      &game_dict
+    )
+    .is_err());
+  }
+
+  #[test]
+  fn ambush_and_lasso_ignore_distance_and_field_cards() {
+    let game_dict = game_dict();
+    let mut game_state = game_state(6);
+    game_state
+      .player_data
+      .get_mut("p3")
+      .unwrap()
+      .field
+      .push(card(CardName::Mustang, CardSuit::Clubs));
+    assert_eq!(
+      game_state.get_player_distance("p0", "p3", &game_dict),
+      Some(4)
+    );
+
+    game_state.current_event = Some(EventCard::Lasso);
+    assert!(!game_state.is_field_active("p3"));
+    assert_eq!(
+      game_state.get_player_distance("p0", "p3", &game_dict),
+      Some(3)
+    );
+
+    game_state.current_event = Some(EventCard::Ambush);
+    assert_eq!(
+      game_state.get_player_distance("p0", "p3", &game_dict),
+      Some(1)
+    );
+  }
+
+  #[test]
+  fn ghosts_take_their_turn_during_ghost_town() {
+    let game_dict = game_dict();
+    let mut game_state = game_with_sheriff();
+    game_state.deck = (0..10)
+      .map(|_| card(CardName::Bang, CardSuit::Clubs))
+      .collect();
+    game_state.player_data.get_mut("p0").unwrap().role = Role::Renegade;
+    game_state.current_event = Some(EventCard::GhostTown);
+    game_state.damage_player("p1", 4, None, &game_dict);
+    assert_eq!(game_state.player_order.len(), 3);
+
+    game_state.turn_phase = TurnPhase::Play;
+    game_state.end_play_phase("p0", &game_dict).unwrap();
+    assert_eq!(game_state.get_active_player(), "p1");
+    assert_eq!(game_state.player_order.len(), 4);
+    assert!(game_state.ghosts.contains(&String::from("p1")));
+
+    // ghosts can not lose health
+    game_state.damage_player("p1", 4, None, &game_dict);
+    assert_eq!(game_state.player_data["p1"].health, 1);
+
+    game_state
+      .draw_phase("p1", &Vec::new(), &Vec::new(), &game_dict)
+      .unwrap();
+    game_state.end_play_phase("p1", &game_dict).unwrap();
+    assert_eq!(game_state.get_active_player(), "p2");
+    assert_eq!(game_state.player_order.len(), 3);
+    assert!(game_state.ghosts.is_empty());
+    assert!(!game_state.player_data["p1"].is_alive());
+    assert!(game_state.player_data["p1"].hand.is_empty());
+  }
+}
@@ -0,0 +1,326 @@
+use crate::{game_engine, shared_types};
+use std::collections::HashMap;
+use std::sync::{Arc, RwLock};
+
+/// The number of players who can sit at the table without an expansion that makes room for more
+pub const BASE_MAX_PLAYERS: usize = 7;
+
+/// The fewest players a game can start with
+pub const MIN_PLAYERS: usize = 4;
+
+/// The Expansion Registry
+///
+/// Every expansion registers what it adds to the base game under its id,
+/// and a game is played with a dictionary composed from the base game
+/// and only the expansions its session has enabled.
+///
+/// The cards and characters of a registered expansion are not built into clients.
+/// They are `CardName::Custom` and `Character::Custom` codes that the expansion registers
+/// along with their names, which are sent to clients with every game they are dealt in.
+/// The expansions that predate the registry keep their codes in `CardName` and `Character`,
+/// which is where clients already know them from.
+///
+/// Wild West Show is deferred: it is not part of the first expansions built on the registry,
+/// so it has no id and can not be enabled.
+pub fn get_expansion_dictionary() -> game_engine::types::ExpansionDictionary {
+  let mut expansion_dict = HashMap::new();
+  expansion_dict.insert(
+    shared_types::Expansion::DodgeCity,
+    game_engine::dodge_city::get_expansion_data(),
+  );
+  expansion_dict.insert(
+    shared_types::Expansion::HighNoon,
+    game_engine::events::get_high_noon_data(),
+  );
+  expansion_dict.insert(
+    shared_types::Expansion::FistfulOfCards,
+    game_engine::events::get_fistful_of_cards_data(),
+  );
+  expansion_dict.insert(
+    shared_types::Expansion::GoldRush,
+    game_engine::gold_rush::get_expansion_data(),
+  );
+  expansion_dict.insert(
+    shared_types::Expansion::ValleyOfShadows,
+    game_engine::valley_of_shadows::get_expansion_data(),
+  );
+  return expansion_dict;
+}
+
+/// Composes the dictionary of a game played with the given expansions,
+/// with the rules of the defined cards of the game in place of their built in ones
+pub fn get_game_dictionary(
+  expansions: &Vec<shared_types::Expansion>,
+  card_definitions: &game_engine::card_definitions::CardDefinitions,
+) -> game_engine::types::GameDictionary {
+  let expansion_dict = get_expansion_dictionary();
+  let mut card_dict = game_engine::data::get_card_dictionary();
+  let mut character_dict = game_engine::data::get_character_dictionary();
+  let mut card_names = HashMap::new();
+  let mut character_names = HashMap::new();
+  for expansion in expansions {
+    if let Some(expansion_data) = expansion_dict.get(expansion) {
+      (expansion_data.insert_cards)(&mut card_dict);
+      (expansion_data.insert_characters)(&mut character_dict);
+      for (name, title) in expansion_data.card_names.iter() {
+        card_names.insert(name.clone(), title.to_string());
+      }
+      for (character, title) in expansion_data.character_names.iter() {
+        character_names.insert(character.clone(), title.to_string());
+      }
+    }
+  }
+  // cards of the expansions that are not enabled are not defined in the game
+  let card_definitions: game_engine::card_definitions::CardDefinitions = card_definitions
+    .iter()
+    .filter(|(name, _)| {
+      card_dict.contains_key(name) || game_engine::card_definitions::is_new_card(name)
+    })
+    .map(|(name, definition)| (name.clone(), definition.clone()))
+    .collect();
+  game_engine::card_definitions::insert_definitions(&mut card_dict, &card_definitions);
+  for definition in card_definitions.values() {
+    if game_engine::card_definitions::is_new_card(&definition.name) {
+      card_names.insert(definition.name.clone(), definition.title.clone());
+    }
+  }
+  return game_engine::types::GameDictionary {
+    card_dict,
+    character_dict,
+    expansion_dict,
+    card_definitions,
+    card_names,
+    character_names,
+  };
+}
+
+/// Every expansion that can be enabled
+pub fn get_registered_expansions() -> Vec<shared_types::Expansion> {
+  let mut expansions: Vec<shared_types::Expansion> =
+    get_expansion_dictionary().into_keys().collect();
+  expansions.sort_by_key(|expansion| expansion.clone() as u8);
+  return expansions;
+}
+
+/// The game dictionaries of each combination of expansions, composed the first time it is played
+pub struct GameDictionaries {
+  card_definitions: game_engine::card_definitions::CardDefinitions,
+  dictionaries:
+    RwLock<HashMap<Vec<shared_types::Expansion>, Arc<game_engine::types::GameDictionary>>>,
+}
+
+impl GameDictionaries {
+  pub fn new(card_definitions: game_engine::card_definitions::CardDefinitions) -> GameDictionaries {
+    GameDictionaries {
+      card_definitions,
+      dictionaries: RwLock::new(HashMap::new()),
+    }
+  }
+
+  /// The dictionary of a game played with the given expansions, in whatever order they were enabled
+  pub fn get(
+    &self,
+    expansions: &Vec<shared_types::Expansion>,
+  ) -> Arc<game_engine::types::GameDictionary> {
+    let mut key = expansions.clone();
+    key.sort_by_key(|expansion| expansion.clone() as u8);
+    key.dedup();
+    if let Some(game_dict) = self.dictionaries.read().unwrap().get(&key) {
+      return game_dict.clone();
+    }
+    return self
+      .dictionaries
+      .write()
+      .unwrap()
+      .entry(key.clone())
+      .or_insert_with(|| Arc::new(get_game_dictionary(&key, &self.card_definitions)))
+      .clone();
+  }
+}
+
+/// The expansion a character comes from, `None` for characters of the base game
+pub fn get_character_expansion(
+  character: &shared_types::Character,
+  expansion_dict: &game_engine::types::ExpansionDictionary,
+) -> Option<shared_types::Expansion> {
+  return expansion_dict
+    .iter()
+    .find(|(_, expansion_data)| expansion_data.characters.contains(character))
+    .map(|(expansion, _)| expansion.clone());
+}
+
+/// The most players who can sit at the table with the given expansions enabled
+pub fn get_max_players(
+  expansions: &Vec<shared_types::Expansion>,
+  expansion_dict: &game_engine::types::ExpansionDictionary,
+) -> usize {
+  return expansions
+    .iter()
+    .filter_map(|expansion| expansion_dict.get(expansion))
+    .map(|expansion_data| expansion_data.max_players)
+    .fold(BASE_MAX_PLAYERS, usize::max);
+}
+
+/// Expansion data for an expansion that only adds characters
+pub fn character_expansion_data(
+  characters: Vec<shared_types::Character>,
+  insert_characters: fn(&mut game_engine::types::CharacterDictionary),
+) -> game_engine::types::ExpansionData {
+  game_engine::types::ExpansionData {
+    max_players: BASE_MAX_PLAYERS,
+    characters,
+    card_names: Vec::new(),
+    character_names: Vec::new(),
+    deck_distribution: Vec::new(),
+    events: Vec::new(),
+    final_event: None,
+    insert_cards: |_card_dict| {},
+    insert_characters,
+  }
+}
+
+/// Expansion data for an expansion that only adds events
+pub fn event_expansion_data(
+  events: Vec<shared_types::EventCard>,
+  final_event: Option<shared_types::EventCard>,
+) -> game_engine::types::ExpansionData {
+  game_engine::types::ExpansionData {
+    max_players: BASE_MAX_PLAYERS,
+    characters: Vec::new(),
+    card_names: Vec::new(),
+    character_names: Vec::new(),
+    deck_distribution: Vec::new(),
+    events,
+    final_event,
+    insert_cards: |_card_dict| {},
+    insert_characters: |_character_dict| {},
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::game_engine::test_utils::game_dict;
+  use crate::shared_types::{CardName, Character, Expansion};
+
+  #[test]
+  fn every_registered_expansion_is_in_the_dictionaries() {
+    let game_dict = game_dict();
+    for expansion_data in game_dict.expansion_dict.values() {
+      for (name, _) in expansion_data.deck_distribution.iter() {
+        assert!(game_dict.card_dict.contains_key(name));
+        // clients can only show the cards they do not know with the names registered for them
+        if let CardName::Custom(_) = name {
+          assert!(game_dict.card_names.contains_key(name));
+        }
+      }
+      for character in expansion_data.characters.iter() {
+        assert!(game_dict.character_dict.contains_key(character));
+        if let Character::Custom(_) = character {
+          assert!(game_dict.character_names.contains_key(character));
+        }
+      }
+    }
+  }
+
+  #[test]
+  fn a_game_only_holds_the_expansions_it_is_played_with() {
+    let game_dict = get_game_dictionary(&vec![Expansion::DodgeCity], &HashMap::new());
+    assert!(game_dict.card_dict.contains_key(&CardName::Whisky));
+    assert!(game_dict.character_dict.contains_key(&Character::ApacheKid));
+    assert!(!game_dict
+      .card_dict
+      .contains_key(&game_engine::valley_of_shadows::TOMAHAWK));
+    assert!(game_dict.card_names.is_empty());
+    assert!(!game_dict
+      .character_dict
+      .contains_key(&Character::SimeonPicos));
+
+    let base_dict = get_game_dictionary(&Vec::new(), &HashMap::new());
+    assert!(base_dict.card_dict.contains_key(&CardName::Bang));
+    assert!(!base_dict.card_dict.contains_key(&CardName::Whisky));
+  }
+
+  #[test]
+  fn registered_names_are_sent_with_the_game() {
+    let expansions = vec![Expansion::ValleyOfShadows];
+    let game_dict = get_game_dictionary(&expansions, &HashMap::new());
+    let client_ids: Vec<String> = (0..4).map(|i| format!("p{}", i)).collect();
+    let game_state = game_engine::types::GameState::new(
+      &client_ids,
+      &expansions,
+      Some(0),
+      shared_types::EmptyDeckRule::default(),
+      &game_dict,
+    )
+    .unwrap();
+    let game_data = game_state.to_game_data();
+    assert_eq!(game_data.custom_cards.len(), 6);
+    assert_eq!(
+      game_data.custom_cards[0],
+      shared_types::CustomName {
+        code: 47,
+        name: String::from("Tomahawk"),
+      }
+    );
+    // only the names of the characters that were dealt are sent
+    let dealt: Vec<u8> = game_state
+      .character_choices
+      .values()
+      .flatten()
+      .filter(|character| matches!(character, Character::Custom(_)))
+      .map(|character| u8::from(character.clone()))
+      .collect();
+    assert!(!dealt.is_empty());
+    assert_eq!(game_data.custom_characters.len(), dealt.len());
+    assert!(game_data
+      .custom_characters
+      .iter()
+      .all(|custom_character| dealt.contains(&custom_character.code)));
+  }
+
+  #[test]
+  fn games_with_the_same_expansions_share_a_dictionary() {
+    let game_dicts = GameDictionaries::new(HashMap::new());
+    let game_dict = game_dicts.get(&vec![Expansion::DodgeCity, Expansion::GoldRush]);
+    assert!(Arc::ptr_eq(
+      &game_dict,
+      &game_dicts.get(&vec![Expansion::GoldRush, Expansion::DodgeCity])
+    ));
+    assert!(!Arc::ptr_eq(&game_dict, &game_dicts.get(&Vec::new())));
+  }
+
+  #[test]
+  fn characters_belong_to_their_expansion() {
+    let game_dict = game_dict();
+    assert_eq!(
+      get_character_expansion(&Character::ApacheKid, &game_dict.expansion_dict),
+      Some(Expansion::DodgeCity)
+    );
+    assert_eq!(
+      get_character_expansion(&Character::SimeonPicos, &game_dict.expansion_dict),
+      Some(Expansion::GoldRush)
+    );
+    assert_eq!(
+      get_character_expansion(&Character::BartCassidy, &game_dict.expansion_dict),
+      None
+    );
+  }
+
+  #[test]
+  fn the_table_grows_with_the_expansions_that_allow_it() {
+    let game_dict = game_dict();
+    assert_eq!(get_max_players(&Vec::new(), &game_dict.expansion_dict), 7);
+    assert_eq!(
+      get_max_players(&vec![Expansion::GoldRush], &game_dict.expansion_dict),
+      7
+    );
+    assert_eq!(
+      get_max_players(
+        &vec![Expansion::GoldRush, Expansion::DodgeCity],
+        &game_dict.expansion_dict
+      ),
+      8
+    );
+  }
+}
@@ -0,0 +1,737 @@
+use crate::{game_engine, shared_types};
+
+/// The characters that come with Gold Rush
+pub const CHARACTERS: [shared_types::Character; 8] = [
+  shared_types::Character::DonBell,
+  shared_types::Character::DutchWill,
+  shared_types::Character::JackyMurieta,
+  shared_types::Character::JoshMcCloud,
+  shared_types::Character::MadamYto,
+  shared_types::Character::PrettyLuzena,
+  shared_types::Character::RaddieSnake,
+  shared_types::Character::SimeonPicos,
+];
+
+/// The number of shop cards up for sale at once
+const SHOP_SIZE: usize = 3;
+
+/// The gold nuggets a player earns for each card they discard at the end of their turn
+pub const GOLD_PER_DISCARD: u8 = 1;
+
+/// The gold nuggets a player earns for eliminating another player
+pub const GOLD_PER_ELIMINATION: u8 = 1;
+
+/// The number of cards a player with a Belt can hold at the end of their turn
+pub const BELT_HAND_LIMIT: usize = 8;
+
+/// The number of cards drawn with a Union Pacific
+const UNION_PACIFIC_DRAW_COUNT: usize = 4;
+
+/// The number of cards flipped with a Rum
+const RUM_FLIP_COUNT: usize = 4;
+
+/// The gold nuggets Jacky Murieta and Josh McCloud pay to use their abilities
+const ABILITY_GOLD_COST: u8 = 2;
+
+/// The number of times Raddie Snake can use his ability each turn
+const RADDIE_SNAKE_USES_PER_TURN: u8 = 2;
+
+/// The number of cards Dutch Will looks at during the draw phase
+const DUTCH_WILL_REVEAL_COUNT: usize = 2;
+
+/// Gold Rush
+///
+/// Players earn gold nuggets by discarding at the end of their turn and by eliminating other players.
+/// During the play phase they can spend their gold on the shop cards up for sale.
+/// Brown shop cards take effect right away, while black shop cards stay in front of the player as equipment.
+/// Used shop cards go to the bottom of the shop deck.
+impl game_engine::types::GameState {
+  /// Gives gold nuggets to a player, if Gold Rush is being played
+  pub fn earn_gold(&mut self, player: &str, amount: u8) {
+    if !self.expansions.contains(&shared_types::Expansion::GoldRush) {
+      return;
+    }
+    if let Some(player_data) = self.player_data.get_mut(player) {
+      player_data.gold = player_data.gold.saturating_add(amount);
+    }
+  }
+
+  /// Whether a player has a black shop card in front of them
+  pub fn has_shop_equipment(&self, player: &str, card: shared_types::ShopCard) -> bool {
+    return match self.player_data.get(player) {
+      Some(player_data) => player_data.equipment.contains(&card),
+      None => false,
+    };
+  }
+
+  /// The gold nuggets a player pays for a shop card.
+  /// Pretty Luzena pays one less for the first card she buys each turn.
+  pub fn get_shop_price(&self, player: &str, card: &shared_types::ShopCard) -> u8 {
+    let cost = get_shop_cost(card);
+    if self.has_character(player, shared_types::Character::PrettyLuzena) && self.ability_uses == 0 {
+      return cost.saturating_sub(1);
+    }
+    return cost;
+  }
+
+  /// Puts shop cards up for sale until the shop is full or the shop deck runs out
+  pub fn refill_shop(&mut self) {
+    while self.shop.len() < SHOP_SIZE {
+      match self.shop_deck.pop() {
+        Some(card) => self.shop.push(card),
+        None => break,
+      }
+    }
+  }
+
+  /// The player spends their gold on one of the shop cards up for sale
+  pub fn buy_card(
+    &mut self,
+    player: &str,
+    card: &shared_types::ShopCard,
+    game_dict: &game_engine::types::GameDictionary,
+  ) -> Result<game_engine::types::GameMessages, game_engine::types::RuleError> {
+    self.check_turn_owner(player)?;
+    self.check_phase(shared_types::TurnPhase::Play)?;
+
+    let index = match self.shop.iter().position(|shop_card| shop_card == card) {
+      Some(index) => index,
+      None => return Err(game_engine::types::RuleError::InvalidSelection),
+    };
+    if is_shop_equipment(card) && self.has_shop_equipment(player, card.clone()) {
+      return Err(game_engine::types::RuleError::InvalidSelection);
+    }
+    let price = self.get_shop_price(player, card);
+    match self.player_data.get_mut(player) {
+      Some(player_data) if player_data.gold >= price => player_data.gold -= price,
+      _ => return Err(game_engine::types::RuleError::NotEnoughGold),
+    }
+    if price < get_shop_cost(card) {
+      self.ability_uses += 1;
+    }
+
+    let card = self.shop.remove(index);
+    self.refill_shop();
+    let mut messages = self.use_shop_card(player, card, game_dict);
+    messages.extend(self.broadcast(&shared_types::ServerEvent::from_event(
+      shared_types::ServerEventCode::CardBought,
+      shared_types::ServerEventData {
+        client_id: Some(player.to_string()),
+        game_data: Some(self.to_game_data()),
+        ..Default::default()
+      },
+    )));
+    messages.extend(self.resolve_empty_hands(game_dict));
+    messages.extend(self.get_update_messages());
+    return Ok(messages);
+  }
+
+  /// Applies the effect of a brown shop card, or puts a black shop card in front of the player.
+  /// A black shop card the player already has goes straight to the bottom of the shop deck.
+  fn use_shop_card(
+    &mut self,
+    player: &str,
+    card: shared_types::ShopCard,
+    _game_dict: &game_engine::types::GameDictionary,
+  ) -> game_engine::types::GameMessages {
+    if is_shop_equipment(&card) {
+      if !self.has_shop_equipment(player, card.clone()) {
+        if let Some(player_data) = self.player_data.get_mut(player) {
+          player_data.equipment.push(card);
+          return Vec::new();
+        }
+      }
+      self.shop_deck.insert(0, card);
+      return Vec::new();
+    }
+
+    let messages = match card {
+      shared_types::ShopCard::UnionPacific => self.draw_cards(player, UNION_PACIFIC_DRAW_COUNT),
+      // regains a life point for each suit among the flipped cards
+      shared_types::ShopCard::Rum => {
+        let mut messages = Vec::new();
+        let mut flipped: Vec<shared_types::Card> = Vec::new();
+        for _ in 0..RUM_FLIP_COUNT {
+          messages.extend(self.refill_deck());
+          if let Some(card) = self.deck.pop() {
+            flipped.push(card);
+          }
+        }
+        let mut suits: Vec<&shared_types::CardSuit> =
+          flipped.iter().map(|card| &card.suit).collect();
+        suits.sort_by_key(|suit| (*suit).clone() as u8);
+        suits.dedup();
+        let amount = suits.len() as u8;
+        self.heal_player(player, amount);
+
+        messages.extend(self.broadcast(&shared_types::ServerEvent::from_event(
+          shared_types::ServerEventCode::CardsRevealed,
+          shared_types::ServerEventData {
+            client_id: Some(player.to_string()),
+            cards: Some(flipped.clone()),
+            ..Default::default()
+          },
+        )));
+        self.discard.extend(flipped);
+        messages
+      }
+      _ => Vec::new(),
+    };
+    self.shop_deck.insert(0, card);
+    return messages;
+  }
+
+  /// Returns the equipment of a player who left the table to the bottom of the shop deck
+  pub fn return_shop_equipment(&mut self, player: &str) {
+    if let Some(player_data) = self.player_data.get_mut(player) {
+      for card in player_data.equipment.drain(..) {
+        self.shop_deck.insert(0, card);
+      }
+    }
+  }
+
+  /// Every Madam Yto at the table draws a card when a Beer is played
+  pub fn draw_for_beer(&mut self) -> game_engine::types::GameMessages {
+    let drinkers: Vec<String> = self
+      .player_order
+      .iter()
+      .filter(|id| self.has_character(id, shared_types::Character::MadamYto))
+      .cloned()
+      .collect();
+    let mut messages = Vec::new();
+    for drinker in drinkers {
+      messages.extend(self.draw_cards(&drinker, 1));
+    }
+    return messages;
+  }
+
+  /// Don Bell takes another turn at the end of his turn on a draw check of a Heart or Diamond,
+  /// but never twice in a row
+  pub fn takes_extra_turn(&mut self, player: &str) -> (bool, game_engine::types::GameMessages) {
+    if self.extra_turn || !self.has_character(player, shared_types::Character::DonBell) {
+      self.extra_turn = false;
+      return (false, Vec::new());
+    }
+    let (passed, messages) = match self.draw_check(player, |card| {
+      card.suit == shared_types::CardSuit::Hearts || card.suit == shared_types::CardSuit::Diamonds
+    }) {
+      Some(draw_check) => (draw_check.passed, draw_check.messages),
+      None => (false, Vec::new()),
+    };
+    self.extra_turn = passed;
+    return (passed, messages);
+  }
+}
+
+/// What Gold Rush adds to the base game, apart from the shop
+pub fn get_expansion_data() -> game_engine::types::ExpansionData {
+  return game_engine::expansions::character_expansion_data(CHARACTERS.to_vec(), insert_characters);
+}
+
+/// The gold nuggets a shop card costs
+pub fn get_shop_cost(card: &shared_types::ShopCard) -> u8 {
+  return match card {
+    shared_types::ShopCard::UnionPacific => 4,
+    shared_types::ShopCard::Rum => 3,
+    shared_types::ShopCard::Belt => 2,
+    shared_types::ShopCard::Boots => 3,
+    shared_types::ShopCard::Calumet => 3,
+    shared_types::ShopCard::Horseshoe => 2,
+    shared_types::ShopCard::Pickaxe => 4,
+  };
+}
+
+/// Whether a shop card is black equipment that stays in front of the player who bought it
+pub fn is_shop_equipment(card: &shared_types::ShopCard) -> bool {
+  return !matches!(
+    card,
+    shared_types::ShopCard::UnionPacific | shared_types::ShopCard::Rum
+  );
+}
+
+/// Creates a shuffled shop deck, which is empty unless Gold Rush is being played
+pub fn generate_shop_deck(
+  expansions: &Vec<shared_types::Expansion>,
+  rng: &mut nanorand::WyRand,
+) -> Vec<shared_types::ShopCard> {
+  if !expansions.contains(&shared_types::Expansion::GoldRush) {
+    return Vec::new();
+  }
+  let mut deck = Vec::new();
+  for card in [
+    shared_types::ShopCard::UnionPacific,
+    shared_types::ShopCard::Rum,
+    shared_types::ShopCard::Belt,
+    shared_types::ShopCard::Boots,
+    shared_types::ShopCard::Calumet,
+    shared_types::ShopCard::Horseshoe,
+    shared_types::ShopCard::Pickaxe,
+  ]
+  .iter()
+  {
+    deck.push(card.clone());
+    deck.push(card.clone());
+  }
+  game_engine::shuffle_deck(&mut deck, rng);
+  return deck;
+}
+
+/// Adds the characters of Gold Rush to the character dictionary
+pub fn insert_characters(character_dict: &mut game_engine::types::CharacterDictionary) {
+  //===============================
+  // Don Bell
+  //===============================
+  // takes another turn on a red draw check at the end of his turn
+  character_dict.insert(
+    shared_types::Character::DonBell,
+    game_engine::data::passive_character_data(4, game_engine::types::DistanceModifier::default()),
+  );
+  //===============================
+  // Dutch Will
+  //===============================
+  character_dict.insert(
+    shared_types::Character::DutchWill,
+    game_engine::types::CharacterData {
+      hp: 4,
+      triggers: vec![game_engine::types::EventTrigger::DrawPhase],
+      effect_optional: false,
+      effect: |player, context, game_state, _game_dict| {
+        // looks at the top two cards of the deck and keeps one,
+        // discarding the other to earn a gold nugget
+        if game_state.revealed_cards.is_empty() {
+          let mut messages = Vec::new();
+          for _ in 0..DUTCH_WILL_REVEAL_COUNT {
+            messages.extend(game_state.refill_deck());
+            if let Some(card) = game_state.deck.pop() {
+              game_state.revealed_cards.push(card);
+            }
+          }
+          if game_state.revealed_cards.is_empty() {
+            game_state.turn_phase = shared_types::TurnPhase::Play;
+            return Ok(messages);
+          }
+          messages.push((
+            player.to_string(),
+            shared_types::ServerEvent::from_event(
+              shared_types::ServerEventCode::CardsRevealed,
+              shared_types::ServerEventData {
+                client_id: Some(player.to_string()),
+                cards: Some(game_state.revealed_cards.clone()),
+                ..Default::default()
+              },
+            ),
+          ));
+          return Ok(messages);
+        }
+
+        let kept = match context.cards.as_slice() {
+          [card] => card,
+          _ => return Err(game_engine::types::RuleError::InvalidSelection),
+        };
+        let index = match game_state
+          .revealed_cards
+          .iter()
+          .position(|revealed| revealed == kept)
+        {
+          Some(index) => index,
+          None => return Err(game_engine::types::RuleError::InvalidSelection),
+        };
+        let card = game_state.revealed_cards.remove(index);
+        if let Some(player_data) = game_state.player_data.get_mut(player) {
+          player_data.hand.push(card);
+        }
+        let rest: Vec<shared_types::Card> = game_state.revealed_cards.drain(..).collect();
+        if !rest.is_empty() {
+          game_state.discard.extend(rest);
+          game_state.earn_gold(player, 1);
+        }
+        game_state.turn_phase = shared_types::TurnPhase::Play;
+        return Ok(Vec::new());
+      },
+      distance_modifier: game_engine::types::DistanceModifier::default(),
+    },
+  );
+  //===============================
+  // Jacky Murieta
+  //===============================
+  character_dict.insert(
+    shared_types::Character::JackyMurieta,
+    game_engine::types::CharacterData {
+      hp: 4,
+      triggers: vec![game_engine::types::EventTrigger::Activated],
+      effect_optional: true,
+      effect: |player, context, game_state, game_dict| {
+        // pays two gold nuggets to shoot a Bang that does not count as the Bang of the turn
+        game_state.check_turn_owner(player)?;
+        game_state.check_phase(shared_types::TurnPhase::Play)?;
+        let range = game_state.get_player_range(player, game_dict);
+        if context.targets.len() != 1
+          || !game_state.is_in_range(player, &context.targets[0], range, game_dict)
+        {
+          return Err(game_engine::types::RuleError::InvalidSelection);
+        }
+        match game_state.player_data.get_mut(player) {
+          Some(player_data) if player_data.gold >= ABILITY_GOLD_COST => {
+            player_data.gold -= ABILITY_GOLD_COST
+          }
+          _ => return Err(game_engine::types::RuleError::NotEnoughGold),
+        }
+        return Ok(game_state.shoot_players(
+          player,
+          &shared_types::CardName::Bang,
+          context.targets,
+          game_engine::types::EventTrigger::Damage,
+          game_dict,
+        ));
+      },
+      distance_modifier: game_engine::types::DistanceModifier::default(),
+    },
+  );
+  //===============================
+  // Josh McCloud
+  //===============================
+  character_dict.insert(
+    shared_types::Character::JoshMcCloud,
+    game_engine::types::CharacterData {
+      hp: 4,
+      triggers: vec![game_engine::types::EventTrigger::Activated],
+      effect_optional: true,
+      effect: |player, _context, game_state, game_dict| {
+        // pays two gold nuggets for the top card of the shop deck, whatever it is
+        game_state.check_turn_owner(player)?;
+        game_state.check_phase(shared_types::TurnPhase::Play)?;
+        if game_state.shop_deck.is_empty() {
+          return Err(game_engine::types::RuleError::AbilityUnavailable);
+        }
+        match game_state.player_data.get_mut(player) {
+          Some(player_data) if player_data.gold >= ABILITY_GOLD_COST => {
+            player_data.gold -= ABILITY_GOLD_COST
+          }
+          _ => return Err(game_engine::types::RuleError::NotEnoughGold),
+        }
+        return match game_state.shop_deck.pop() {
+          Some(card) => Ok(game_state.use_shop_card(player, card, game_dict)),
+          None => Ok(Vec::new()),
+        };
+      },
+      distance_modifier: game_engine::types::DistanceModifier::default(),
+    },
+  );
+  //===============================
+  // Madam Yto
+  //===============================
+  // draws a card whenever a Beer is played
+  character_dict.insert(
+    shared_types::Character::MadamYto,
+    game_engine::data::passive_character_data(4, game_engine::types::DistanceModifier::default()),
+  );
+  //===============================
+  // Pretty Luzena
+  //===============================
+  // pays one less gold nugget for the first shop card she buys each turn
+  character_dict.insert(
+    shared_types::Character::PrettyLuzena,
+    game_engine::data::passive_character_data(4, game_engine::types::DistanceModifier::default()),
+  );
+  //===============================
+  // Raddie Snake
+  //===============================
+  character_dict.insert(
+    shared_types::Character::RaddieSnake,
+    game_engine::types::CharacterData {
+      hp: 4,
+      triggers: vec![game_engine::types::EventTrigger::Activated],
+      effect_optional: true,
+      effect: |player, _context, game_state, _game_dict| {
+        // twice a turn, pays a gold nugget to draw a card
+        game_state.check_turn_owner(player)?;
+        game_state.check_phase(shared_types::TurnPhase::Play)?;
+        if game_state.ability_uses >= RADDIE_SNAKE_USES_PER_TURN {
+          return Err(game_engine::types::RuleError::AbilityUnavailable);
+        }
+        match game_state.player_data.get_mut(player) {
+          Some(player_data) if player_data.gold > 0 => player_data.gold -= 1,
+          _ => return Err(game_engine::types::RuleError::NotEnoughGold),
+        }
+        game_state.ability_uses += 1;
+        return Ok(game_state.draw_cards(player, 1));
+      },
+      distance_modifier: game_engine::types::DistanceModifier::default(),
+    },
+  );
+  //===============================
+  // Simeon Picos
+  //===============================
+  character_dict.insert(
+    shared_types::Character::SimeonPicos,
+    game_engine::types::CharacterData {
+      hp: 3,
+      triggers: vec![game_engine::types::EventTrigger::HealthLost],
+      effect_optional: false,
+      effect: |player, context, game_state, _game_dict| {
+        // earns a gold nugget for each life point he loses
+        game_state.earn_gold(player, context.amount);
+        return Ok(Vec::new());
+      },
+      distance_modifier: game_engine::types::DistanceModifier::default(),
+    },
+  );
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::game_engine::test_utils::{card, game_dict, game_with_sheriff};
+  use crate::game_engine::types::{GameState, RuleError};
+  use crate::shared_types::{Card, CardName, CardSuit, Character, Expansion, ShopCard, TurnPhase};
+
+  /// A Gold Rush game where "p0" is in the play phase with the given gold
+  fn gold_rush_game(gold: u8) -> GameState {
+    let mut game_state = game_with_sheriff();
+    game_state.expansions = vec![Expansion::GoldRush];
+    game_state.shop = vec![ShopCard::Belt, ShopCard::UnionPacific, ShopCard::Pickaxe];
+    game_state.shop_deck = vec![ShopCard::Rum];
+    game_state.deck = (0..10)
+      .map(|_| card(CardName::Bang, CardSuit::Clubs))
+      .collect();
+    game_state.player_data.get_mut("p0").unwrap().gold = gold;
+    return game_state;
+  }
+
+  fn set_character(game_state: &mut GameState, player: &str, character: Character) {
+    game_state.player_data.get_mut(player).unwrap().character = character;
+  }
+
+  #[test]
+  fn the_shop_deck_is_only_used_with_gold_rush() {
+    assert!(generate_shop_deck(&Vec::new(), &mut nanorand::WyRand::new_seed(0)).is_empty());
+    assert_eq!(
+      generate_shop_deck(
+        &vec![Expansion::GoldRush],
+        &mut nanorand::WyRand::new_seed(0)
+      )
+      .len(),
+      14
+    );
+    for character in CHARACTERS.iter() {
+      assert!(game_dict().character_dict.contains_key(character));
+    }
+  }
+
+  #[test]
+  fn equipment_is_bought_with_gold() {
+    let game_dict = game_dict();
+    let mut game_state = gold_rush_game(1);
+    assert!(matches!(
+      game_state.buy_card("p0", &ShopCard::Belt, &game_dict),
+      Err(RuleError::NotEnoughGold)
+    ));
+
+    game_state.player_data.get_mut("p0").unwrap().gold = 4;
+    game_state
+      .buy_card("p0", &ShopCard::Belt, &game_dict)
+      .unwrap();
+    assert_eq!(game_state.player_data["p0"].gold, 2);
+    assert_eq!(game_state.player_data["p0"].equipment, vec![ShopCard::Belt]);
+    assert_eq!(
+      game_state.to_game_data().shop,
+      vec![ShopCard::UnionPacific, ShopCard::Pickaxe, ShopCard::Rum]
+    );
+    assert!(matches!(
+      game_state.buy_card("p0", &ShopCard::Belt, &game_dict),
+      Err(RuleError::InvalidSelection)
+    ));
+  }
+
+  #[test]
+  fn brown_shop_cards_take_effect_right_away() {
+    let game_dict = game_dict();
+    let mut game_state = gold_rush_game(4);
+    game_state
+      .buy_card("p0", &ShopCard::UnionPacific, &game_dict)
+      .unwrap();
+    assert_eq!(game_state.player_data["p0"].hand.len(), 4);
+    assert!(game_state.player_data["p0"].equipment.is_empty());
+    assert_eq!(game_state.shop_deck, vec![ShopCard::UnionPacific]);
+  }
+
+  #[test]
+  fn pretty_luzena_gets_a_discount_once_a_turn() {
+    let game_dict = game_dict();
+    let mut game_state = gold_rush_game(5);
+    set_character(&mut game_state, "p0", Character::PrettyLuzena);
+    assert_eq!(game_state.get_shop_price("p0", &ShopCard::Pickaxe), 3);
+    game_state
+      .buy_card("p0", &ShopCard::Pickaxe, &game_dict)
+      .unwrap();
+    assert_eq!(game_state.player_data["p0"].gold, 2);
+    assert_eq!(game_state.get_shop_price("p0", &ShopCard::Belt), 2);
+  }
+
+  #[test]
+  fn gold_is_earned_by_discarding_and_eliminating() {
+    let game_dict = game_dict();
+    let mut game_state = gold_rush_game(0);
+    game_state.turn_phase = TurnPhase::Discard;
+    let player_data = game_state.player_data.get_mut("p0").unwrap();
+    player_data.health = 1;
+    player_data.hand = vec![
+      card(CardName::Bang, CardSuit::Clubs),
+      card(CardName::Missed, CardSuit::Clubs),
+      card(CardName::Beer, CardSuit::Hearts),
+    ];
+    game_state
+      .discard_phase(
+        "p0",
+        &vec![
+          card(CardName::Bang, CardSuit::Clubs),
+          card(CardName::Missed, CardSuit::Clubs),
+        ],
+        &game_dict,
+      )
+      .unwrap();
+    assert_eq!(game_state.player_data["p0"].gold, 2);
+
+    game_state.player_data.get_mut("p2").unwrap().health = 1;
+    game_state.damage_player("p2", 1, Some("p1"), &game_dict);
+    assert_eq!(game_state.player_data["p1"].gold, 1);
+  }
+
+  #[test]
+  fn gold_can_not_be_farmed_by_discarding_below_the_hand_limit() {
+    let game_dict = game_dict();
+    let mut game_state = gold_rush_game(0);
+    game_state.turn_phase = TurnPhase::Discard;
+    let hand: Vec<Card> = (0..5)
+      .map(|_| card(CardName::Bang, CardSuit::Clubs))
+      .collect();
+    game_state.player_data.get_mut("p0").unwrap().hand = hand.clone();
+
+    // one card over the limit of four
+    assert!(matches!(
+      game_state.discard_phase("p0", &hand, &game_dict),
+      Err(RuleError::InvalidSelection)
+    ));
+    assert_eq!(game_state.player_data["p0"].gold, 0);
+
+    game_state
+      .discard_phase("p0", &hand[..1].to_vec(), &game_dict)
+      .unwrap();
+    assert_eq!(game_state.player_data["p0"].gold, 1);
+    assert_eq!(game_state.player_data["p0"].hand.len(), 4);
+  }
+
+  #[test]
+  fn no_gold_is_earned_without_gold_rush() {
+    let mut game_state = gold_rush_game(0);
+    game_state.expansions.clear();
+    game_state.earn_gold("p0", 3);
+    assert_eq!(game_state.player_data["p0"].gold, 0);
+  }
+
+  #[test]
+  fn black_shop_cards_change_the_rules_for_their_owner() {
+    let game_dict = game_dict();
+    let mut game_state = gold_rush_game(0);
+    game_state.player_data.get_mut("p1").unwrap().equipment =
+      vec![ShopCard::Pickaxe, ShopCard::Calumet, ShopCard::Boots];
+    assert_eq!(game_state.get_draw_count("p1"), 3);
+    assert!(game_state.is_unaffected_by("p1", "p0", &card(CardName::Panic, CardSuit::Diamonds)));
+
+    game_state.damage_player("p1", 2, None, &game_dict);
+    assert_eq!(game_state.player_data["p1"].hand.len(), 2);
+  }
+
+  #[test]
+  fn simeon_picos_earns_gold_when_hurt() {
+    let game_dict = game_dict();
+    let mut game_state = gold_rush_game(0);
+    set_character(&mut game_state, "p1", Character::SimeonPicos);
+    game_state.damage_player("p1", 2, None, &game_dict);
+    assert_eq!(game_state.player_data["p1"].gold, 2);
+  }
+
+  #[test]
+  fn raddie_snake_buys_cards_twice_a_turn() {
+    let game_dict = game_dict();
+    let mut game_state = gold_rush_game(3);
+    set_character(&mut game_state, "p0", Character::RaddieSnake);
+    for _ in 0..2 {
+      game_state
+        .use_ability("p0", &Vec::new(), &Vec::new(), &game_dict)
+        .unwrap();
+    }
+    assert!(game_state
+      .use_ability("p0", &Vec::new(), &Vec::new(), &game_dict)
+      .is_err());
+    assert_eq!(game_state.player_data["p0"].hand.len(), 2);
+    assert_eq!(game_state.player_data["p0"].gold, 1);
+  }
+
+  #[test]
+  fn dutch_will_keeps_one_card_and_earns_gold() {
+    let game_dict = game_dict();
+    let mut game_state = gold_rush_game(0);
+    game_state.turn_phase = TurnPhase::Draw;
+    set_character(&mut game_state, "p0", Character::DutchWill);
+    game_state.deck = vec![
+      card(CardName::Beer, CardSuit::Hearts),
+      card(CardName::Bang, CardSuit::Clubs),
+    ];
+
+    game_state
+      .draw_phase("p0", &Vec::new(), &Vec::new(), &game_dict)
+      .unwrap();
+    assert_eq!(game_state.revealed_cards.len(), 2);
+    game_state
+      .draw_phase(
+        "p0",
+        &vec![card(CardName::Beer, CardSuit::Hearts)],
+        &Vec::new(),
+        &game_dict,
+      )
+      .unwrap();
+    assert_eq!(
+      game_state.player_data["p0"].hand,
+      vec![card(CardName::Beer, CardSuit::Hearts)]
+    );
+    assert_eq!(game_state.player_data["p0"].gold, 1);
+    assert!(game_state.turn_phase == TurnPhase::Play);
+  }
+
+  #[test]
+  fn don_bell_takes_another_turn_on_a_red_card() {
+    let game_dict = game_dict();
+    let mut game_state = gold_rush_game(0);
+    set_character(&mut game_state, "p0", Character::DonBell);
+    game_state.deck = (0..10)
+      .map(|_| card(CardName::Bang, CardSuit::Hearts))
+      .collect();
+
+    game_state.end_play_phase("p0", &game_dict).unwrap();
+    assert_eq!(game_state.get_active_player(), "p0");
+    game_state
+      .draw_phase("p0", &Vec::new(), &Vec::new(), &game_dict)
+      .unwrap();
+    game_state.end_play_phase("p0", &game_dict).unwrap();
+    assert_eq!(game_state.get_active_player(), "p1");
+  }
+
+  #[test]
+  fn madam_yto_draws_when_a_beer_is_played() {
+    let game_dict = game_dict();
+    let mut game_state = gold_rush_game(0);
+    set_character(&mut game_state, "p2", Character::MadamYto);
+    game_state.player_data.get_mut("p0").unwrap().health = 3;
+    let beer = vec![card(CardName::Beer, CardSuit::Hearts)];
+    game_state.player_data.get_mut("p0").unwrap().hand = beer.clone();
+    (game_dict.card_dict[&CardName::Beer].effect)(
+      "p0",
+      &beer,
+      &Vec::new(),
+      &mut game_state,
+      &game_dict,
+    );
+    assert_eq!(game_state.player_data["p2"].hand.len(), 1);
+  }
+}
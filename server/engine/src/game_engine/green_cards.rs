@@ -0,0 +1,181 @@
+use crate::{game_engine, shared_types};
+
+/// Green Cards
+///
+/// Green cards from Dodge City are put in front of the player who plays them like blue cards,
+/// but they cannot be used on the turn they were played.
+/// From the next turn on they can be activated from the field for their effect,
+/// and some of them can be used out of turn as a response (ex. Bible, Iron Plate).
+impl game_engine::types::GameState {
+  /// Whether a card is a green card
+  pub fn is_green_card(
+    &self,
+    card: &shared_types::CardName,
+    game_dict: &game_engine::types::GameDictionary,
+  ) -> bool {
+    return match game_dict.card_dict.get(card) {
+      Some(card_data) => card_data.color == game_engine::types::CardColor::Green,
+      None => false,
+    };
+  }
+
+  /// Whether a green card is in front of a player and is ready to be used
+  pub fn is_card_usable(&self, player: &str, card: &shared_types::Card) -> bool {
+    let on_field = match self.player_data.get(player) {
+      Some(player_data) => player_data.field.contains(card),
+      None => false,
+    };
+    return match self.usable_from.get(card) {
+      Some(turn) => on_field && *turn <= self.turn_count,
+      None => false,
+    };
+  }
+
+  /// Puts a green card from the hand of a player onto their field,
+  /// where it becomes usable from the next turn
+  pub fn place_green_card(
+    &mut self,
+    player: &str,
+    card: &shared_types::Card,
+  ) -> Result<game_engine::types::GameMessages, game_engine::types::RuleError> {
+    if self.check_equipment(player, card).is_err() {
+      return Err(game_engine::types::RuleError::InvalidSelection);
+    }
+    if let Some(player_data) = self.player_data.get_mut(player) {
+      player_data.remove_cards(&vec![card.clone()]);
+      player_data.field.push(card.clone());
+    }
+    self.usable_from.insert(card.clone(), self.turn_count + 1);
+    return Ok(self.get_update_messages());
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::game_engine::test_utils::{card, game_dict, game_with_sheriff};
+  use crate::game_engine::types::{GameState, RuleError};
+  use crate::shared_types::{CardName, CardSuit};
+
+  /// Places a green card in front of "p1" and starts the next turn
+  fn ready_green_card(game_state: &mut GameState, name: CardName) {
+    let game_dict = game_dict();
+    game_state
+      .player_data
+      .get_mut("p1")
+      .unwrap()
+      .hand
+      .push(card(name.clone(), CardSuit::Diamonds));
+    game_state
+      .place_green_card("p1", &card(name.clone(), CardSuit::Diamonds))
+      .unwrap();
+    assert!(!game_state.is_card_usable("p1", &card(name, CardSuit::Diamonds)));
+    game_state.start_turn(&game_dict);
+  }
+
+  #[test]
+  fn green_cards_are_usable_from_the_next_turn() {
+    let mut game_state = game_with_sheriff();
+    ready_green_card(&mut game_state, CardName::PonyExpress);
+    assert!(game_state.is_card_usable("p1", &card(CardName::PonyExpress, CardSuit::Diamonds)));
+    assert!(game_state.player_data["p1"].hand.is_empty());
+
+    game_state
+      .player_data
+      .get_mut("p1")
+      .unwrap()
+      .hand
+      .push(card(CardName::PonyExpress, CardSuit::Diamonds));
+    assert_eq!(
+      game_state
+        .place_green_card("p1", &card(CardName::PonyExpress, CardSuit::Diamonds))
+        .err(),
+      Some(RuleError::InvalidSelection)
+    );
+  }
+
+  #[test]
+  fn an_iron_plate_responds_to_a_bang_out_of_turn() {
+    let game_dict = game_dict();
+    let mut game_state = game_with_sheriff();
+    ready_green_card(&mut game_state, CardName::IronPlate);
+
+    let bang = &game_dict.card_dict[&CardName::Bang];
+    (bang.effect)(
+      "p0",
+      &vec![card(CardName::Bang, CardSuit::Diamonds)],
+      &vec![String::from("p1")],
+      &mut game_state,
+      &game_dict,
+    );
+    assert_eq!(
+      game_state.response_queue["p1"].cards,
+      vec![CardName::IronPlate]
+    );
+
+    game_state
+      .respond(
+        "p1",
+        &vec![card(CardName::IronPlate, CardSuit::Diamonds)],
+        &game_dict,
+      )
+      .unwrap();
+    assert_eq!(game_state.player_data["p1"].health, 4);
+    assert!(game_state.player_data["p1"].field.is_empty());
+    assert!(game_state.usable_from.is_empty());
+  }
+
+  #[test]
+  fn a_bible_draws_a_card_on_top_of_the_missed() {
+    let game_dict = game_dict();
+    let mut game_state = game_with_sheriff();
+    ready_green_card(&mut game_state, CardName::Bible);
+    game_state.deck = vec![card(CardName::Beer, CardSuit::Diamonds)];
+
+    let bang = &game_dict.card_dict[&CardName::Bang];
+    (bang.effect)(
+      "p0",
+      &vec![card(CardName::Bang, CardSuit::Diamonds)],
+      &vec![String::from("p1")],
+      &mut game_state,
+      &game_dict,
+    );
+    game_state
+      .respond(
+        "p1",
+        &vec![card(CardName::Bible, CardSuit::Diamonds)],
+        &game_dict,
+      )
+      .unwrap();
+    assert_eq!(game_state.player_data["p1"].health, 4);
+    assert_eq!(
+      game_state.player_data["p1"].hand,
+      vec![card(CardName::Beer, CardSuit::Diamonds)]
+    );
+  }
+
+  #[test]
+  fn green_cards_that_are_not_ready_can_not_respond() {
+    let game_dict = game_dict();
+    let mut game_state = game_with_sheriff();
+    game_state
+      .player_data
+      .get_mut("p1")
+      .unwrap()
+      .hand
+      .push(card(CardName::IronPlate, CardSuit::Diamonds));
+    game_state
+      .place_green_card("p1", &card(CardName::IronPlate, CardSuit::Diamonds))
+      .unwrap();
+
+    let bang = &game_dict.card_dict[&CardName::Bang];
+    (bang.effect)(
+      "p0",
+      &vec![card(CardName::Bang, CardSuit::Diamonds)],
+      &vec![String::from("p1")],
+      &mut game_state,
+      &game_dict,
+    );
+    assert!(!game_state.is_awaiting_responses());
+    assert_eq!(game_state.player_data["p1"].health, 3);
+  }
+}
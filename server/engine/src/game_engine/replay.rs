@@ -0,0 +1,260 @@
+use crate::{game_engine, shared_types};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::sync::Arc;
+
+/// An action accepted by the game and the messages it produced
+#[derive(Serialize, Deserialize, Clone)]
+pub struct LogEntry {
+  pub player: String,
+  pub action: game_engine::actions::Action,
+  pub events: Vec<game_engine::actions::Event>,
+}
+
+/// Everything needed to deal a game again and play it out the same way
+#[derive(Serialize, Deserialize, Clone)]
+pub struct GameLog {
+  pub seed: u32,
+  pub client_ids: Vec<String>,
+  pub expansions: Vec<shared_types::Expansion>,
+  /// older logs were all played with the default rule
+  #[serde(default)]
+  pub empty_deck_rule: shared_types::EmptyDeckRule,
+  /// the card definitions the game was played with, since the definition file may have changed since
+  #[serde(default)]
+  pub card_definitions: Vec<game_engine::card_definitions::CardDefinition>,
+  /// the accepted actions in the order they were taken
+  pub entries: Vec<LogEntry>,
+}
+
+impl GameLog {
+  /// Deals the game of the log again and applies every action in it
+  pub fn replay(&self) -> Result<game_engine::types::GameState, String> {
+    let mut replay = Replay::new(self.clone())?;
+    while !replay.is_finished() {
+      replay.step()?;
+    }
+    return Ok(replay.game_state);
+  }
+}
+
+/// A game being played back from its log, one action at a time
+#[derive(Clone)]
+pub struct Replay {
+  pub game_state: game_engine::types::GameState,
+  log: GameLog,
+  /// the index of the next entry of the log to apply
+  position: usize,
+  /// the cards and characters of the game, with the card definitions it was played with
+  game_dict: Arc<game_engine::types::GameDictionary>,
+}
+
+// the game state can not be printed, so a replay is shown by how far along the log it is
+impl fmt::Debug for Replay {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    f.debug_struct("Replay")
+      .field("position", &self.position)
+      .field("entries", &self.log.entries.len())
+      .finish_non_exhaustive()
+  }
+}
+
+impl Replay {
+  pub fn new(log: GameLog) -> Result<Replay, String> {
+    let card_definitions = log
+      .card_definitions
+      .iter()
+      .map(|definition| (definition.name.clone(), definition.clone()))
+      .collect();
+    let game_dict = Arc::new(game_engine::expansions::get_game_dictionary(
+      &log.expansions,
+      &card_definitions,
+    ));
+    let game_state = game_engine::types::GameState::new(
+      &log.client_ids,
+      &log.expansions,
+      Some(log.seed),
+      log.empty_deck_rule.clone(),
+      &game_dict,
+    )?;
+    return Ok(Replay {
+      game_state,
+      log,
+      position: 0,
+      game_dict,
+    });
+  }
+
+  pub fn is_finished(&self) -> bool {
+    self.position >= self.log.entries.len()
+  }
+
+  /// Applies the next action of the log, returning the messages it produced.
+  ///
+  /// The game no longer matches the log if the action is refused,
+  /// which happens when it is replayed with different cards or rules.
+  pub fn step(&mut self) -> Result<Vec<game_engine::actions::Event>, String> {
+    let entry = match self.log.entries.get(self.position) {
+      Some(entry) => entry.clone(),
+      None => return Ok(Vec::new()),
+    };
+    self.position += 1;
+    return self
+      .game_state
+      .apply(&entry.player, entry.action, &self.game_dict)
+      .map_err(|error| {
+        format!(
+          "The replay no longer matches the game at action {}: {}",
+          self.position,
+          error.get_message()
+        )
+      });
+  }
+
+  /// Applies the actions of the log until the next turn starts or the log runs out
+  pub fn step_turn(&mut self) -> Result<Vec<game_engine::actions::Event>, String> {
+    let turn_count = self.game_state.turn_count;
+    let mut events = Vec::new();
+    while !self.is_finished() && self.game_state.turn_count == turn_count {
+      events.extend(self.step()?);
+    }
+    return Ok(events);
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::game_engine::actions::{Action, SERVER_PLAYER};
+  use crate::game_engine::test_utils::game_dict;
+  use crate::game_engine::types::GameState;
+
+  /// Plays out a few turns of a new game, with each player drawing and discarding down to their health
+  fn play_turns(game_state: &mut GameState, turns: usize) {
+    let game_dict = game_dict();
+    game_state
+      .apply(SERVER_PLAYER, Action::EndDraft, &game_dict)
+      .unwrap();
+    for _ in 0..turns {
+      let player = game_state.get_active_player().to_string();
+      game_state
+        .apply(
+          &player,
+          Action::DrawCards {
+            cards: Vec::new(),
+            targets: Vec::new(),
+          },
+          &game_dict,
+        )
+        .unwrap();
+      game_state
+        .apply(&player, Action::EndTurn, &game_dict)
+        .unwrap();
+      // the turn only ends once the hand is within the limit
+      let overflow = game_state.get_hand_overflow(&player);
+      if overflow > 0 {
+        let cards = game_state.player_data[&player].hand[..overflow].to_vec();
+        game_state
+          .apply(&player, Action::DiscardCards { cards }, &game_dict)
+          .unwrap();
+      }
+    }
+  }
+
+  fn new_game() -> GameState {
+    let client_ids: Vec<String> = (0..4).map(|i| format!("p{}", i)).collect();
+    return GameState::new(
+      &client_ids,
+      &Vec::new(),
+      Some(7),
+      shared_types::EmptyDeckRule::NewDeck,
+      &game_dict(),
+    )
+    .unwrap();
+  }
+
+  #[test]
+  fn refused_actions_are_not_logged() {
+    let mut game_state = new_game();
+    play_turns(&mut game_state, 0);
+    assert_eq!(game_state.log.entries.len(), 1);
+
+    let player = game_state.get_active_player().to_string();
+    assert!(game_state
+      .apply(&player, Action::EndDraft, &game_dict())
+      .is_err());
+    assert_eq!(game_state.log.entries.len(), 1);
+  }
+
+  #[test]
+  fn the_log_replays_to_the_same_game() {
+    let mut game_state = new_game();
+    play_turns(&mut game_state, 6);
+
+    let log: GameLog =
+      serde_json::from_str(&serde_json::to_string(&game_state.log).unwrap()).unwrap();
+    let replayed = log.replay().unwrap();
+    assert_eq!(replayed.turn_count, game_state.turn_count);
+    assert_eq!(
+      replayed.empty_deck_rule,
+      shared_types::EmptyDeckRule::NewDeck
+    );
+    assert_eq!(replayed.deck, game_state.deck);
+    assert_eq!(replayed.discard, game_state.discard);
+    assert_eq!(
+      serde_json::to_value(&replayed.player_data).unwrap(),
+      serde_json::to_value(&game_state.player_data).unwrap()
+    );
+    assert_eq!(
+      serde_json::to_value(&replayed.log).unwrap(),
+      serde_json::to_value(&game_state.log).unwrap()
+    );
+  }
+
+  #[test]
+  fn the_log_is_replayed_with_the_card_definitions_of_the_game() {
+    let client_ids: Vec<String> = (0..4).map(|i| format!("p{}", i)).collect();
+    let expansions = vec![shared_types::Expansion::DodgeCity];
+    let game_dict = game_engine::expansions::get_game_dictionary(
+      &expansions,
+      &game_engine::test_utils::example_definitions(),
+    );
+    let game_state = GameState::new(
+      &client_ids,
+      &expansions,
+      Some(7),
+      shared_types::EmptyDeckRule::default(),
+      &game_dict,
+    )
+    .unwrap();
+    // the Tomahawk is not defined without Valley of Shadows
+    assert_eq!(game_state.log.card_definitions.len(), 1);
+
+    let log: GameLog =
+      serde_json::from_str(&serde_json::to_string(&game_state.log).unwrap()).unwrap();
+    let replay = Replay::new(log).unwrap();
+    assert!(replay
+      .game_dict
+      .card_definitions
+      .contains_key(&shared_types::CardName::Whisky));
+  }
+
+  #[test]
+  fn a_replay_steps_through_one_turn_at_a_time() {
+    let mut game_state = new_game();
+    play_turns(&mut game_state, 3);
+
+    let mut replay = Replay::new(game_state.log.clone()).unwrap();
+    // the draft ends with the first turn starting
+    replay.step().unwrap();
+    let first_turn = replay.game_state.turn_count;
+    let events = replay.step_turn().unwrap();
+    assert!(!events.is_empty());
+    assert_eq!(replay.game_state.turn_count, first_turn + 1);
+
+    while !replay.is_finished() {
+      replay.step_turn().unwrap();
+    }
+    assert_eq!(replay.game_state.turn_count, game_state.turn_count);
+  }
+}
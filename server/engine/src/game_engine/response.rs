@@ -0,0 +1,562 @@
+use crate::{game_engine, shared_types};
+use std::collections::HashMap;
+
+/// The Response Window
+///
+/// A card that targets other players (ex. Bang!) is put onto the event stack
+/// and its targets are prompted one at a time, in seat order.
+/// Each response, or pass, is resolved by the `update` of the card, and once
+/// every target has been resolved the card is taken off the stack and the turn continues.
+impl game_engine::types::GameState {
+  /// Puts a card onto the event stack and prompts the first of its targets
+  pub fn open_response_window(
+    &mut self,
+    card_event: game_engine::types::CardEvent,
+    game_dict: &game_engine::types::GameDictionary,
+  ) -> game_engine::types::GameMessages {
+    let mut messages = Vec::new();
+    let effect_code = get_effect_code(&card_event.card);
+    if effect_code != shared_types::EffectCode::None {
+      let cards = match card_event.trigger {
+        game_engine::types::EventTrigger::Selection => Some(self.revealed_cards.clone()),
+        _ => None,
+      };
+      for player in self.player_order.iter() {
+        messages.push((
+          player.clone(),
+          shared_types::ServerEvent::from_event(
+            shared_types::ServerEventCode::EffectStarted,
+            shared_types::ServerEventData {
+              client_id: Some(card_event.user_id.clone()),
+              effect_code: Some(effect_code.clone()),
+              cards: cards.clone(),
+              ..Default::default()
+            },
+          ),
+        ));
+      }
+    }
+
+    self.card_events.push(card_event.card.clone());
+    self.event_stack.push(card_event);
+    messages.extend(self.prompt_next_target(game_dict));
+    return messages;
+  }
+
+  /// Prompts the next target of the card on top of the event stack.
+  ///
+  /// Targets who avoid the card or have no way of responding to it are resolved right away,
+  /// and the card is taken off the stack once there is nobody left to prompt.
+  fn prompt_next_target(
+    &mut self,
+    game_dict: &game_engine::types::GameDictionary,
+  ) -> game_engine::types::GameMessages {
+    let mut messages = Vec::new();
+    loop {
+      let (user_id, target, trigger) = match self.event_stack.last_mut() {
+        Some(card_event) if !card_event.targets.is_empty() => (
+          card_event.user_id.clone(),
+          card_event.targets.remove(0),
+          card_event.trigger.clone(),
+        ),
+        _ => break,
+      };
+      if self.turn_phase == shared_types::TurnPhase::End {
+        break;
+      }
+      match self.player_data.get(&target) {
+        Some(player_data) if player_data.is_alive() => {}
+        _ => continue,
+      }
+
+      let responses = match trigger {
+        game_engine::types::EventTrigger::Selection => {
+          if self.revealed_cards.is_empty() {
+            continue;
+          }
+          let mut responses = HashMap::new();
+          responses.insert(
+            target.clone(),
+            shared_types::ResponseData {
+              cards: self
+                .revealed_cards
+                .iter()
+                .map(|card| card.name.clone())
+                .collect(),
+              characters: Vec::new(),
+            },
+          );
+          responses
+        }
+        _ => {
+          if trigger == game_engine::types::EventTrigger::Damage {
+            let (avoided, draw_check_messages) = self.avoids_shot(&target);
+            messages.extend(draw_check_messages);
+            if avoided {
+              continue;
+            }
+          }
+          self.trigger_responses(&vec![trigger], &vec![target.clone()], game_dict)
+        }
+      };
+
+      if responses.is_empty() {
+        messages.extend(self.damage_player(&target, 1, Some(&user_id), game_dict));
+        continue;
+      }
+      messages.extend(self.request_responses(responses));
+      return messages;
+    }
+    self.close_response_window();
+    return messages;
+  }
+
+  /// Asks more players to respond to the card on top of the event stack
+  pub fn request_responses(
+    &mut self,
+    responses: HashMap<String, shared_types::ResponseData>,
+  ) -> game_engine::types::GameMessages {
+    let (user_id, effect_code, cards) = match self.event_stack.last() {
+      Some(card_event) => (
+        Some(card_event.user_id.clone()),
+        Some(get_effect_code(&card_event.card)),
+        match card_event.trigger {
+          game_engine::types::EventTrigger::Selection => Some(self.revealed_cards.clone()),
+          _ => None,
+        },
+      ),
+      None => (None, None, None),
+    };
+
+    let messages = responses
+      .iter()
+      .map(|(player, response_data)| {
+        (
+          player.clone(),
+          shared_types::ServerEvent::from_event(
+            shared_types::ServerEventCode::ResponseRequested,
+            shared_types::ServerEventData {
+              client_id: user_id.clone(),
+              game_data: Some(self.to_game_data()),
+              response_data: Some(response_data.clone()),
+              effect_code: effect_code.clone(),
+              cards: cards.clone(),
+              ..Default::default()
+            },
+          ),
+        )
+      })
+      .collect();
+
+    self.trigger_queue.extend(responses.clone());
+    self.response_queue.extend(responses);
+    return messages;
+  }
+
+  /// The player responds to the card on top of the event stack with some of the cards they were asked for,
+  /// or passes by responding with no cards.
+  ///
+  /// When picking from the revealed cards, the player responds with the one card they take.
+  pub fn respond(
+    &mut self,
+    player: &str,
+    cards: &Vec<shared_types::Card>,
+    game_dict: &game_engine::types::GameDictionary,
+  ) -> Result<game_engine::types::GameMessages, game_engine::types::RuleError> {
+    self.check_responder(player)?;
+
+    let card_event = match self.event_stack.last() {
+      Some(card_event) => card_event.clone(),
+      None => return Err(game_engine::types::RuleError::InvalidPhase),
+    };
+    if card_event.trigger == game_engine::types::EventTrigger::Selection {
+      if cards.len() > 1 || !cards.iter().all(|card| self.revealed_cards.contains(card)) {
+        return Err(game_engine::types::RuleError::InvalidSelection);
+      }
+    } else {
+      match self.player_data.get(player) {
+        Some(player_data) if player_data.has_cards(cards) => {}
+        _ => return Err(game_engine::types::RuleError::CardNotInHand),
+      }
+      if cards
+        .iter()
+        .any(|card| self.is_green_card(&card.name, game_dict) && !self.is_card_usable(player, card))
+      {
+        return Err(game_engine::types::RuleError::CardNotReady);
+      }
+    }
+    let response_data = &self.response_queue[player];
+    if !cards
+      .iter()
+      .all(|card| response_data.cards.contains(&card.name))
+    {
+      return Err(game_engine::types::RuleError::InvalidSelection);
+    }
+
+    let update = match game_dict.card_dict.get(&card_event.card) {
+      Some(card_data) => card_data.update,
+      None => return Err(game_engine::types::RuleError::InvalidPhase),
+    };
+
+    self.response_queue.remove(player);
+    let mut messages = update(
+      &card_event.user_id,
+      cards,
+      &vec![player.to_string()],
+      self,
+      game_dict,
+    );
+    if card_event.trigger != game_engine::types::EventTrigger::Selection {
+      messages.extend(self.resolve_stand_in_responses(player, cards, game_dict));
+    }
+
+    if !self.is_awaiting_responses() {
+      messages.extend(self.prompt_next_target(game_dict));
+    }
+    messages.extend(self.resolve_empty_hands(game_dict));
+    messages.extend(self.get_update_messages());
+    return Ok(messages);
+  }
+
+  /// Cards used as a response that stand in for the card asked for add their own effect on top of it
+  /// (ex. a Dodge draws a card), and using cards out of turn may trigger abilities (ex. Molly Stark)
+  fn resolve_stand_in_responses(
+    &mut self,
+    player: &str,
+    cards: &Vec<shared_types::Card>,
+    game_dict: &game_engine::types::GameDictionary,
+  ) -> game_engine::types::GameMessages {
+    let mut messages = Vec::new();
+    for card in cards {
+      // only cards that were used up by the response count
+      let still_held = match self.player_data.get(player) {
+        Some(player_data) => player_data.has_cards(&vec![card.clone()]),
+        None => false,
+      };
+      if still_held || game_engine::data::get_stand_in(&card.name).is_none() {
+        continue;
+      }
+      if let Some(card_data) = game_dict.card_dict.get(&card.name) {
+        messages.extend((card_data.effect)(
+          player,
+          &vec![card.clone()],
+          &Vec::new(),
+          self,
+          game_dict,
+        ));
+      }
+    }
+
+    let used_count = match self.player_data.get(player) {
+      Some(player_data) => cards
+        .iter()
+        .filter(|card| !player_data.has_cards(&vec![(*card).clone()]))
+        .count(),
+      None => 0,
+    };
+    if used_count > 0 && self.get_active_player() != player {
+      messages.extend(self.trigger_abilities(
+        &vec![player.to_string()],
+        &game_engine::types::TriggerContext {
+          trigger: game_engine::types::EventTrigger::Responded,
+          source: None,
+          amount: used_count as u8,
+          cards,
+          targets: &Vec::new(),
+        },
+        game_dict,
+      ));
+    }
+    return messages;
+  }
+
+  /// Takes the resolved card off of the event stack so that the turn can continue.
+  /// Any revealed cards that were not picked are discarded.
+  pub fn close_response_window(&mut self) {
+    self.event_stack.pop();
+    self.card_events.pop();
+    self.trigger_queue.clear();
+    self.response_queue.clear();
+    self.discard.append(&mut self.revealed_cards);
+  }
+
+  /// Stops waiting on a player who can no longer respond
+  pub fn remove_responder(
+    &mut self,
+    player: &str,
+    game_dict: &game_engine::types::GameDictionary,
+  ) -> game_engine::types::GameMessages {
+    self.trigger_queue.remove(player);
+    if self.response_queue.remove(player).is_some() && !self.is_awaiting_responses() {
+      return self.prompt_next_target(game_dict);
+    }
+    return Vec::new();
+  }
+}
+
+/// The effect that clients are shown while a card is resolved against several players
+pub fn get_effect_code(card: &shared_types::CardName) -> shared_types::EffectCode {
+  return match card {
+    shared_types::CardName::GeneralStore => shared_types::EffectCode::GeneralStore,
+    shared_types::CardName::Gatling => shared_types::EffectCode::Gatling,
+    shared_types::CardName::Indians => shared_types::EffectCode::Indians,
+    _ => shared_types::EffectCode::None,
+  };
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::game_engine::test_utils::{card, game_dict, game_with_sheriff};
+  use crate::game_engine::types::{GameDictionary, GameState, RuleError};
+  use crate::shared_types::{CardName, CardSuit, ServerEventCode};
+
+  fn give(game_state: &mut GameState, player: &str, name: CardName) {
+    game_state
+      .player_data
+      .get_mut(player)
+      .unwrap()
+      .hand
+      .push(card(name, CardSuit::Clubs));
+  }
+
+  fn play(
+    game_state: &mut GameState,
+    game_dict: &GameDictionary,
+    name: CardName,
+    targets: &[&str],
+  ) -> Vec<String> {
+    let targets: Vec<String> = targets.iter().map(|target| target.to_string()).collect();
+    let messages = (game_dict.card_dict[&name].effect)(
+      "p0",
+      &vec![card(name, CardSuit::Clubs)],
+      &targets,
+      game_state,
+      game_dict,
+    );
+    return messages
+      .iter()
+      .filter(|(_, message)| matches!(message.event_code, ServerEventCode::ResponseRequested))
+      .map(|(player, _)| player.clone())
+      .collect();
+  }
+
+  #[test]
+  fn responders_are_asked_and_a_pass_takes_the_damage() {
+    let game_dict = game_dict();
+    let mut game_state = game_with_sheriff();
+    give(&mut game_state, "p1", CardName::Missed);
+
+    let asked = play(&mut game_state, &game_dict, CardName::Bang, &["p1"]);
+    assert_eq!(asked, vec![String::from("p1")]);
+    assert!(game_state.is_awaiting_responses());
+
+    game_state.respond("p1", &Vec::new(), &game_dict).unwrap();
+    assert_eq!(game_state.player_data["p1"].health, 3);
+    assert!(!game_state.is_awaiting_responses());
+    assert!(game_state.event_stack.is_empty());
+    assert!(game_state.card_events.is_empty());
+  }
+
+  #[test]
+  fn responding_with_a_missed_avoids_the_damage() {
+    let game_dict = game_dict();
+    let mut game_state = game_with_sheriff();
+    give(&mut game_state, "p1", CardName::Missed);
+    give(&mut game_state, "p1", CardName::Beer);
+    play(&mut game_state, &game_dict, CardName::Bang, &["p1"]);
+
+    assert_eq!(
+      game_state
+        .respond(
+          "p1",
+          &vec![card(CardName::Beer, CardSuit::Clubs)],
+          &game_dict
+        )
+        .err(),
+      Some(RuleError::InvalidSelection)
+    );
+    assert_eq!(
+      game_state
+        .respond(
+          "p1",
+          &vec![card(CardName::Bang, CardSuit::Clubs)],
+          &game_dict
+        )
+        .err(),
+      Some(RuleError::CardNotInHand)
+    );
+
+    game_state
+      .respond(
+        "p1",
+        &vec![card(CardName::Missed, CardSuit::Clubs)],
+        &game_dict,
+      )
+      .unwrap();
+    assert_eq!(game_state.player_data["p1"].health, 4);
+    assert_eq!(
+      game_state.discard.last(),
+      Some(&card(CardName::Missed, CardSuit::Clubs))
+    );
+    assert!(!game_state.is_awaiting_responses());
+  }
+
+  #[test]
+  fn targets_are_prompted_one_at_a_time_in_seat_order() {
+    let game_dict = game_dict();
+    let mut game_state = game_with_sheriff();
+    give(&mut game_state, "p1", CardName::Missed);
+    give(&mut game_state, "p3", CardName::Missed);
+
+    let asked = play(&mut game_state, &game_dict, CardName::Gatling, &[]);
+    assert_eq!(asked, vec![String::from("p1")]);
+    assert_eq!(game_state.player_data["p2"].health, 4);
+    assert_eq!(
+      game_state.respond("p3", &Vec::new(), &game_dict).err(),
+      Some(RuleError::NotYourTurn)
+    );
+
+    // without a Missed, the next player is hit before the one after them is prompted
+    let messages = game_state
+      .respond(
+        "p1",
+        &vec![card(CardName::Missed, CardSuit::Clubs)],
+        &game_dict,
+      )
+      .unwrap();
+    assert_eq!(game_state.player_data["p1"].health, 4);
+    assert_eq!(game_state.player_data["p2"].health, 3);
+    assert!(messages.iter().any(|(player, message)| {
+      player == "p3" && matches!(message.event_code, ServerEventCode::ResponseRequested)
+    }));
+
+    game_state.respond("p3", &Vec::new(), &game_dict).unwrap();
+    assert!(!game_state.is_awaiting_responses());
+    assert!(game_state.event_stack.is_empty());
+    assert_eq!(game_state.player_data["p3"].health, 3);
+  }
+
+  #[test]
+  fn indians_asks_each_player_for_a_bang() {
+    let game_dict = game_dict();
+    let mut game_state = game_with_sheriff();
+    give(&mut game_state, "p2", CardName::Bang);
+    give(&mut game_state, "p2", CardName::Missed);
+
+    let asked = play(&mut game_state, &game_dict, CardName::Indians, &[]);
+    assert_eq!(asked, vec![String::from("p2")]);
+    assert_eq!(game_state.player_data["p1"].health, 3);
+
+    game_state
+      .respond(
+        "p2",
+        &vec![card(CardName::Bang, CardSuit::Clubs)],
+        &game_dict,
+      )
+      .unwrap();
+    assert_eq!(game_state.player_data["p2"].health, 4);
+    assert_eq!(game_state.player_data["p3"].health, 3);
+    assert!(!game_state.is_awaiting_responses());
+  }
+
+  #[test]
+  fn general_store_players_pick_from_the_revealed_cards_in_turn() {
+    let game_dict = game_dict();
+    let mut game_state = game_with_sheriff();
+    game_state.deck = vec![
+      card(CardName::Beer, CardSuit::Clubs),
+      card(CardName::Missed, CardSuit::Clubs),
+      card(CardName::Bang, CardSuit::Clubs),
+      card(CardName::Panic, CardSuit::Clubs),
+    ];
+
+    let messages = (game_dict.card_dict[&CardName::GeneralStore].effect)(
+      "p0",
+      &vec![card(CardName::GeneralStore, CardSuit::Clubs)],
+      &Vec::new(),
+      &mut game_state,
+      &game_dict,
+    );
+    assert_eq!(
+      messages
+        .iter()
+        .filter(|(_, message)| matches!(message.event_code, ServerEventCode::EffectStarted))
+        .count(),
+      4
+    );
+    assert_eq!(game_state.revealed_cards.len(), 4);
+    assert!(game_state.response_queue.contains_key("p0"));
+
+    assert_eq!(
+      game_state
+        .respond(
+          "p0",
+          &vec![card(CardName::Gatling, CardSuit::Clubs)],
+          &game_dict
+        )
+        .err(),
+      Some(RuleError::InvalidSelection)
+    );
+    game_state
+      .respond(
+        "p0",
+        &vec![card(CardName::Missed, CardSuit::Clubs)],
+        &game_dict,
+      )
+      .unwrap();
+    assert!(game_state.response_queue.contains_key("p1"));
+
+    // a player who passes takes the first card left
+    game_state.respond("p1", &Vec::new(), &game_dict).unwrap();
+    game_state
+      .respond(
+        "p2",
+        &vec![card(CardName::Beer, CardSuit::Clubs)],
+        &game_dict,
+      )
+      .unwrap();
+    game_state.respond("p3", &Vec::new(), &game_dict).unwrap();
+
+    assert!(!game_state.is_awaiting_responses());
+    assert!(game_state.revealed_cards.is_empty());
+    let picks = [
+      ("p0", CardName::Missed),
+      ("p1", CardName::Panic),
+      ("p2", CardName::Beer),
+      ("p3", CardName::Bang),
+    ];
+    for (player, name) in picks.iter() {
+      assert_eq!(
+        game_state.player_data[*player].hand,
+        vec![card(name.clone(), CardSuit::Clubs)]
+      );
+    }
+  }
+
+  #[test]
+  fn a_duel_goes_back_and_forth_until_someone_passes() {
+    let game_dict = game_dict();
+    let mut game_state = game_with_sheriff();
+    give(&mut game_state, "p0", CardName::Bang);
+    give(&mut game_state, "p2", CardName::Bang);
+
+    let asked = play(&mut game_state, &game_dict, CardName::Duel, &["p2"]);
+    assert_eq!(asked, vec![String::from("p2")]);
+
+    let messages = game_state
+      .respond(
+        "p2",
+        &vec![card(CardName::Bang, CardSuit::Clubs)],
+        &game_dict,
+      )
+      .unwrap();
+    assert!(messages.iter().any(|(player, message)| {
+      player == "p0" && matches!(message.event_code, ServerEventCode::ResponseRequested)
+    }));
+
+    game_state.respond("p0", &Vec::new(), &game_dict).unwrap();
+    assert_eq!(game_state.player_data["p0"].health, 3);
+    assert_eq!(game_state.player_data["p2"].health, 4);
+    assert!(!game_state.is_awaiting_responses());
+  }
+}
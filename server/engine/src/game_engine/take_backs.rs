@@ -0,0 +1,152 @@
+use crate::{game_engine, shared_types};
+
+impl game_engine::types::GameState {
+  /// Copies the game as it is now, for taking back the action about to be applied
+  pub fn take_snapshot(&mut self) -> game_engine::types::GameState {
+    // only the last action can be taken back, so the snapshot does not keep its own
+    let previous = self.snapshot.take();
+    let snapshot = self.clone();
+    self.snapshot = previous;
+    return snapshot;
+  }
+
+  /// The player votes to take back the last action,
+  /// which puts the game back the way it was before it once every living player agrees
+  pub fn vote_take_back(
+    &mut self,
+    player: &str,
+  ) -> Result<game_engine::types::GameMessages, game_engine::types::RuleError> {
+    if !self.take_backs
+      || self.snapshot.is_none()
+      || !self.player_order.iter().any(|id| id == player)
+    {
+      return Err(game_engine::types::RuleError::TakeBackUnavailable);
+    }
+    if !self.take_back_votes.iter().any(|id| id == player) {
+      self.take_back_votes.push(player.to_string());
+    }
+
+    if !self
+      .player_order
+      .iter()
+      .all(|id| self.take_back_votes.contains(id))
+    {
+      return Ok(self.broadcast(&shared_types::ServerEvent::from_event(
+        shared_types::ServerEventCode::TakeBackVoted,
+        shared_types::ServerEventData {
+          client_id: Some(player.to_string()),
+          ..Default::default()
+        },
+      )));
+    }
+
+    if let Some(snapshot) = self.snapshot.take() {
+      *self = *snapshot;
+      self.take_back_votes.clear();
+    }
+    return Ok(self.get_restored_messages());
+  }
+
+  /// Gives each player their own view of the game after it was put back to an earlier state
+  fn get_restored_messages(&self) -> game_engine::types::GameMessages {
+    return self
+      .get_update_messages()
+      .into_iter()
+      .map(|(player, mut message)| {
+        message.event_code = shared_types::ServerEventCode::DataResponse;
+        (player, message)
+      })
+      .collect();
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::game_engine::actions::Action;
+  use crate::game_engine::test_utils::{game_dict, game_state};
+  use crate::game_engine::types::{GameState, RuleError};
+  use crate::shared_types::{Card, CardName, CardRank, CardSuit, ServerEventCode};
+
+  fn mustang() -> Card {
+    Card {
+      name: CardName::Mustang,
+      suit: CardSuit::Hearts,
+      rank: CardRank::N8,
+    }
+  }
+
+  /// A game with take backs where "p0" has just put a Mustang in front of them
+  fn game_after_mustang() -> GameState {
+    let mut game_state = game_state(3);
+    game_state.take_backs = true;
+    game_state
+      .player_data
+      .get_mut("p0")
+      .unwrap()
+      .hand
+      .push(mustang());
+    game_state
+      .apply(
+        "p0",
+        Action::PlayCard {
+          cards: vec![mustang()],
+          targets: Vec::new(),
+          field_card: None,
+        },
+        &game_dict(),
+      )
+      .unwrap();
+    return game_state;
+  }
+
+  #[test]
+  fn the_last_action_is_taken_back_once_everyone_agrees() {
+    let mut game_state = game_after_mustang();
+    assert_eq!(game_state.player_data["p0"].field, vec![mustang()]);
+
+    for player in ["p0", "p1"].iter() {
+      let messages = game_state.vote_take_back(player).unwrap();
+      assert!(messages
+        .iter()
+        .all(|(_, message)| matches!(message.event_code, ServerEventCode::TakeBackVoted)));
+    }
+    assert_eq!(game_state.player_data["p0"].field, vec![mustang()]);
+
+    let messages = game_state.vote_take_back("p2").unwrap();
+    assert_eq!(messages.len(), 3);
+    assert!(messages
+      .iter()
+      .all(|(_, message)| matches!(message.event_code, ServerEventCode::DataResponse)));
+    assert!(game_state.player_data["p0"].field.is_empty());
+    assert_eq!(game_state.player_data["p0"].hand, vec![mustang()]);
+    assert!(game_state.log.entries.is_empty());
+    // only the last action can be taken back
+    assert!(matches!(
+      game_state.vote_take_back("p0"),
+      Err(RuleError::TakeBackUnavailable)
+    ));
+  }
+
+  #[test]
+  fn votes_do_not_carry_over_to_the_next_action() {
+    let mut game_state = game_after_mustang();
+    game_state.vote_take_back("p1").unwrap();
+    game_state.vote_take_back("p2").unwrap();
+
+    game_state
+      .apply("p0", Action::EndTurn, &game_dict())
+      .unwrap();
+    game_state.vote_take_back("p0").unwrap();
+    assert_eq!(game_state.get_active_player(), "p1");
+  }
+
+  #[test]
+  fn take_backs_are_only_allowed_when_the_game_opted_in() {
+    let mut game_state = game_after_mustang();
+    game_state.take_backs = false;
+    assert!(matches!(
+      game_state.vote_take_back("p0"),
+      Err(RuleError::TakeBackUnavailable)
+    ));
+  }
+}
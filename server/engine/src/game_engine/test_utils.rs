@@ -0,0 +1,109 @@
+use crate::game_engine::{card_definitions, expansions, replay, types};
+use crate::shared_types;
+use nanorand::WyRand;
+use std::collections::HashMap;
+
+/// The dictionary of a game with every expansion enabled
+pub fn game_dict() -> types::GameDictionary {
+  return expansions::get_game_dictionary(
+    &expansions::get_registered_expansions(),
+    &HashMap::new(),
+  );
+}
+
+/// The card definitions of the example definition file
+pub fn example_definitions() -> card_definitions::CardDefinitions {
+  return card_definitions::parse_card_definitions(
+    include_str!("../../cards.example.json"),
+    &game_dict(),
+  )
+  .unwrap_or_else(|errors| panic!("{:?}", errors));
+}
+
+/// A card of the given name and suit, for tests where its rank does not matter
+pub fn card(name: shared_types::CardName, suit: shared_types::CardSuit) -> shared_types::Card {
+  return shared_types::Card {
+    name,
+    suit,
+    rank: shared_types::CardRank::N5,
+  };
+}
+
+/// Seats players "p0" through "p{n-1}" around the table in order,
+/// with "p0" in the play phase of their turn
+pub fn game_state(player_count: usize) -> types::GameState {
+  let player_order: Vec<String> = (0..player_count).map(|i| format!("p{}", i)).collect();
+  let player_data = player_order
+    .iter()
+    .map(|id| {
+      (
+        id.clone(),
+        shared_types::PlayerData {
+          health: 4,
+          max_health: 4,
+          hand: Vec::new(),
+          field: Vec::new(),
+          character: shared_types::Character::SidKetchum,
+          role: shared_types::Role::Outlaw,
+          gold: 0,
+          equipment: Vec::new(),
+        },
+      )
+    })
+    .collect();
+
+  types::GameState {
+    turn_index: 0,
+    turn_count: 0,
+    ability_uses: 0,
+    expansions: Vec::new(),
+    copied_characters: HashMap::new(),
+    turn_phase: shared_types::TurnPhase::Play,
+    bangs_played: 0,
+    empty_deck_rule: shared_types::EmptyDeckRule::default(),
+    character_choices: HashMap::new(),
+    revealed_cards: Vec::new(),
+    field_selection: None,
+    usable_from: HashMap::new(),
+    event_deck: Vec::new(),
+    current_event: None,
+    shop_deck: Vec::new(),
+    shop: Vec::new(),
+    extra_turn: false,
+    seed: 0,
+    rng: WyRand::new_seed(0),
+    log: replay::GameLog {
+      seed: 0,
+      client_ids: player_order.clone(),
+      expansions: Vec::new(),
+      empty_deck_rule: shared_types::EmptyDeckRule::default(),
+      card_definitions: Vec::new(),
+      entries: Vec::new(),
+    },
+    take_backs: false,
+    custom_cards: Vec::new(),
+    custom_characters: Vec::new(),
+    snapshot: None,
+    take_back_votes: Vec::new(),
+    seating: player_order.clone(),
+    ghosts: Vec::new(),
+    player_order,
+    player_data,
+    deck: Vec::new(),
+    deck_list: Vec::new(),
+    discard: Vec::new(),
+    event_stack: Vec::new(),
+    card_events: Vec::new(),
+    trigger_queue: HashMap::new(),
+    response_queue: HashMap::new(),
+  }
+}
+
+/// Seats players like `game_state`, with "p3" as the Sheriff so that the game is not already over
+pub fn game_with_sheriff() -> types::GameState {
+  let mut game_state = game_state(4);
+  if let Some(player_data) = game_state.player_data.get_mut("p3") {
+    player_data.role = shared_types::Role::Sheriff;
+  }
+  return game_state;
+}
@@ -0,0 +1,620 @@
+use crate::{game_engine, shared_types};
+
+/// The number of cards a player takes from the deck during their draw phase
+pub const DRAW_PHASE_CARD_COUNT: usize = 2;
+
+/// The damage dealt to the player holding the Dynamite when it explodes
+pub const DYNAMITE_DAMAGE: u8 = 3;
+
+/// The number of cards Sean Mallory can hold at the end of his turn
+const SEAN_MALLORY_HAND_LIMIT: usize = 10;
+
+/// The Turn State Machine
+///
+/// Every turn moves through the phases in order:
+/// Start -> Draw -> Play -> Discard -> End
+///
+/// Start and End are resolved by the server without waiting on the player,
+/// while Draw, Play and Discard each wait for the player to act.
+/// A player who fails to break out of Jail skips from Start straight to End.
+impl game_engine::types::GameState {
+  /// The id of the player whose turn it currently is
+  pub fn get_active_player(&self) -> &str {
+    &self.player_order[self.turn_index]
+  }
+
+  /// Whether the game is waiting on players to respond to a card before the turn can continue
+  pub fn is_awaiting_responses(&self) -> bool {
+    !self.response_queue.is_empty()
+  }
+
+  /// Checks that it is the turn of the given player
+  pub fn check_turn_owner(&self, player: &str) -> Result<(), game_engine::types::RuleError> {
+    if self.get_active_player() != player {
+      return Err(game_engine::types::RuleError::NotYourTurn);
+    }
+    return Ok(());
+  }
+
+  /// Checks that the game is waiting on a response from the given player
+  pub fn check_responder(&self, player: &str) -> Result<(), game_engine::types::RuleError> {
+    if !self.is_awaiting_responses() {
+      return Err(game_engine::types::RuleError::InvalidPhase);
+    }
+    if !self.response_queue.contains_key(player) {
+      return Err(game_engine::types::RuleError::NotYourTurn);
+    }
+    return Ok(());
+  }
+
+  /// Checks that the game is in the given phase and is not waiting on any responses
+  pub fn check_phase(
+    &self,
+    phase: shared_types::TurnPhase,
+  ) -> Result<(), game_engine::types::RuleError> {
+    if self.turn_phase != phase || self.is_awaiting_responses() {
+      return Err(game_engine::types::RuleError::InvalidPhase);
+    }
+    return Ok(());
+  }
+
+  /// Begins the turn of the active player.
+  ///
+  /// Start of turn checks are resolved before the game waits on the player to draw.
+  pub fn start_turn(
+    &mut self,
+    game_dict: &game_engine::types::GameDictionary,
+  ) -> game_engine::types::GameMessages {
+    self.turn_phase = shared_types::TurnPhase::Start;
+    self.bangs_played = 0;
+    self.turn_count += 1;
+    self.ability_uses = 0;
+    let player = self.get_active_player().to_string();
+    // a copied ability lasts until the start of the next turn of the player who copied it
+    if let Some(character) = self.copied_characters.remove(&player) {
+      if let Some(player_data) = self.player_data.get_mut(&player) {
+        player_data.character = character;
+      }
+    }
+
+    let mut messages = self.resolve_turn_event(&player, game_dict);
+    // the turn was passed on or the game ended if the event eliminated the player
+    if self.turn_phase != shared_types::TurnPhase::Start {
+      return messages;
+    }
+    if self.has_equipment(&player, shared_types::CardName::Dynamite) {
+      messages.extend(self.resolve_dynamite(&player, game_dict));
+      // the turn was passed on or the game ended if the player did not survive the explosion
+      if self.turn_phase != shared_types::TurnPhase::Start {
+        return messages;
+      }
+    }
+    if self.has_equipment(&player, game_engine::valley_of_shadows::RATTLESNAKE) {
+      messages.extend(self.resolve_rattlesnake(&player, game_dict));
+      if self.turn_phase != shared_types::TurnPhase::Start {
+        return messages;
+      }
+    }
+    if self.has_equipment(&player, shared_types::CardName::Jail) {
+      let (skipped, jail_messages) = self.resolve_jail(&player);
+      messages.extend(jail_messages);
+      if skipped {
+        messages.extend(self.broadcast(&shared_types::ServerEvent::from_event(
+          shared_types::ServerEventCode::TurnSkipped,
+          shared_types::ServerEventData {
+            client_id: Some(player),
+            game_data: Some(self.to_game_data()),
+            ..Default::default()
+          },
+        )));
+        messages.extend(self.end_turn(game_dict));
+        return messages;
+      }
+    }
+    self.turn_phase = shared_types::TurnPhase::Draw;
+
+    let turn_start = shared_types::ServerEvent::from_event(
+      shared_types::ServerEventCode::TurnStart,
+      shared_types::ServerEventData {
+        client_id: Some(player),
+        game_data: Some(self.to_game_data()),
+        ..Default::default()
+      },
+    );
+    messages.extend(self.broadcast(&turn_start));
+    return messages;
+  }
+
+  /// The player breaks out of Jail on a draw check of a Heart, and skips their turn otherwise.
+  /// The Jail is discarded either way.
+  fn resolve_jail(&mut self, player: &str) -> (bool, game_engine::types::GameMessages) {
+    let (skipped, messages) =
+      match self.draw_check(player, |card| card.suit == shared_types::CardSuit::Hearts) {
+        Some(draw_check) => (!draw_check.passed, draw_check.messages),
+        None => (false, Vec::new()),
+      };
+    if let Some(player_data) = self.player_data.get_mut(player) {
+      if let Some(index) = player_data
+        .field
+        .iter()
+        .position(|card| card.name == shared_types::CardName::Jail)
+      {
+        self.discard.push(player_data.field.remove(index));
+      }
+    }
+    return (skipped, messages);
+  }
+
+  /// The Dynamite explodes on a draw check of a Spade from 2 to 9, dealing damage to the player holding it.
+  /// Otherwise it is passed on to the next living player.
+  fn resolve_dynamite(
+    &mut self,
+    player: &str,
+    game_dict: &game_engine::types::GameDictionary,
+  ) -> game_engine::types::GameMessages {
+    let (exploded, mut messages) = match self.draw_check(player, |card| {
+      card.suit != shared_types::CardSuit::Spades
+        || !(shared_types::CardRank::N2 as u8..=shared_types::CardRank::N9 as u8)
+          .contains(&(card.rank.clone() as u8))
+    }) {
+      Some(draw_check) => (!draw_check.passed, draw_check.messages),
+      None => (false, Vec::new()),
+    };
+
+    let dynamite = match self.player_data.get_mut(player) {
+      Some(player_data) => {
+        match player_data
+          .field
+          .iter()
+          .position(|card| card.name == shared_types::CardName::Dynamite)
+        {
+          Some(index) => player_data.field.remove(index),
+          None => return messages,
+        }
+      }
+      None => return messages,
+    };
+
+    if exploded {
+      self.discard.push(dynamite);
+      messages.extend(self.broadcast(&shared_types::ServerEvent::from_event(
+        shared_types::ServerEventCode::DynamiteExploded,
+        shared_types::ServerEventData {
+          client_id: Some(player.to_string()),
+          ..Default::default()
+        },
+      )));
+      messages.extend(self.damage_player(player, DYNAMITE_DAMAGE, None, game_dict));
+      return messages;
+    }
+
+    let next_player = self.get_other_players(player).into_iter().next();
+    let holder = next_player.unwrap_or_else(|| player.to_string());
+    if let Some(player_data) = self.player_data.get_mut(&holder) {
+      player_data.field.push(dynamite);
+    }
+    messages.extend(self.broadcast(&shared_types::ServerEvent::from_event(
+      shared_types::ServerEventCode::DynamitePassed,
+      shared_types::ServerEventData {
+        client_id: Some(holder),
+        game_data: Some(self.to_game_data()),
+        ..Default::default()
+      },
+    )));
+    messages.extend(self.get_update_messages());
+    return messages;
+  }
+
+  /// The player takes their cards for the turn and moves on to playing cards.
+  ///
+  /// Characters with an ability that changes how they draw use the cards and targets
+  /// they chose, and move on to playing cards once they are finished drawing.
+  pub fn draw_phase(
+    &mut self,
+    player: &str,
+    cards: &Vec<shared_types::Card>,
+    targets: &Vec<String>,
+    game_dict: &game_engine::types::GameDictionary,
+  ) -> Result<game_engine::types::GameMessages, game_engine::types::RuleError> {
+    self.check_turn_owner(player)?;
+    self.check_phase(shared_types::TurnPhase::Draw)?;
+
+    let character_data = self
+      .player_data
+      .get(player)
+      .and_then(|player_data| game_dict.character_dict.get(&player_data.character));
+
+    let mut messages = match character_data {
+      Some(character_data)
+        if character_data
+          .triggers
+          .contains(&game_engine::types::EventTrigger::DrawPhase) =>
+      {
+        let context = game_engine::types::TriggerContext {
+          trigger: game_engine::types::EventTrigger::DrawPhase,
+          source: None,
+          amount: 0,
+          cards,
+          targets,
+        };
+        (character_data.effect)(player, &context, self, game_dict)?
+      }
+      _ => {
+        let messages = self.draw_cards(player, self.get_draw_count(player));
+        self.turn_phase = shared_types::TurnPhase::Play;
+        messages
+      }
+    };
+
+    messages.extend(self.get_update_messages());
+    return Ok(messages);
+  }
+
+  /// The player is finished playing cards.
+  ///
+  /// The turn ends right away if their hand is within the limit,
+  /// otherwise they must discard down to their current health first.
+  pub fn end_play_phase(
+    &mut self,
+    player: &str,
+    game_dict: &game_engine::types::GameDictionary,
+  ) -> Result<game_engine::types::GameMessages, game_engine::types::RuleError> {
+    self.check_turn_owner(player)?;
+    if self.turn_phase == shared_types::TurnPhase::Discard && !self.is_awaiting_responses() {
+      return Err(game_engine::types::RuleError::HandLimitExceeded);
+    }
+    self.check_phase(shared_types::TurnPhase::Play)?;
+
+    self.turn_phase = shared_types::TurnPhase::Discard;
+    if self.get_hand_overflow(player) > 0 {
+      return Ok(self.get_update_messages());
+    }
+    return Ok(self.end_turn(game_dict));
+  }
+
+  /// The player discards cards from their hand down to the hand limit,
+  /// which ends the turn.
+  pub fn discard_phase(
+    &mut self,
+    player: &str,
+    cards: &Vec<shared_types::Card>,
+    game_dict: &game_engine::types::GameDictionary,
+  ) -> Result<game_engine::types::GameMessages, game_engine::types::RuleError> {
+    self.check_turn_owner(player)?;
+    self.check_phase(shared_types::TurnPhase::Discard)?;
+
+    match self.player_data.get(player) {
+      Some(player_data) if player_data.has_in_hand(cards) => {}
+      _ => return Err(game_engine::types::RuleError::CardNotInHand),
+    }
+    // exactly the cards over the limit are discarded, never fewer or more
+    if cards.len() != self.get_hand_overflow(player) {
+      return Err(game_engine::types::RuleError::InvalidSelection);
+    }
+    self.discard_cards(player, cards);
+    self.earn_gold(
+      player,
+      (cards.len() as u8).saturating_mul(game_engine::gold_rush::GOLD_PER_DISCARD),
+    );
+
+    return Ok(self.end_turn(game_dict));
+  }
+
+  /// Passes the turn on to the next living player in the turn order,
+  /// or to the next ghost during Ghost Town.
+  /// Don Bell may keep the turn for himself instead.
+  fn end_turn(
+    &mut self,
+    game_dict: &game_engine::types::GameDictionary,
+  ) -> game_engine::types::GameMessages {
+    self.turn_phase = shared_types::TurnPhase::End;
+
+    let player = self.get_active_player().to_string();
+    let (extra_turn, mut messages) = self.takes_extra_turn(&player);
+    if extra_turn {
+      messages.extend(self.start_turn(game_dict));
+      return messages;
+    }
+    let next_player = self.get_next_turn_player(&player);
+    // a ghost with a Ghost card in front of them stays in play
+    if self.ghosts.contains(&player)
+      && !self.has_equipment(&player, game_engine::valley_of_shadows::GHOST)
+    {
+      self.lay_ghost_to_rest(&player);
+    }
+    if let Some(next_player) = next_player {
+      if !self.player_order.contains(&next_player) {
+        self.raise_ghost(&next_player);
+      }
+      if let Some(index) = self.player_order.iter().position(|id| *id == next_player) {
+        self.turn_index = index;
+      }
+    }
+
+    messages.extend(self.start_turn(game_dict));
+    return messages;
+  }
+
+  /// The number of cards a player holds over their hand limit.
+  ///
+  /// The limit is their current health, except for Sean Mallory who can always hold ten cards
+  /// and players with a Belt who can always hold eight.
+  /// Ghosts lose their cards at the end of their turn anyway.
+  pub fn get_hand_overflow(&self, player: &str) -> usize {
+    if self.ghosts.iter().any(|ghost| ghost == player) {
+      return 0;
+    }
+    match self.player_data.get(player) {
+      Some(player_data) => {
+        let mut hand_limit = match player_data.character {
+          shared_types::Character::SeanMallory => SEAN_MALLORY_HAND_LIMIT,
+          _ => player_data.health as usize,
+        };
+        if player_data
+          .equipment
+          .contains(&shared_types::ShopCard::Belt)
+        {
+          hand_limit = hand_limit.max(game_engine::gold_rush::BELT_HAND_LIMIT);
+        }
+        player_data.hand.len().saturating_sub(hand_limit)
+      }
+      None => 0,
+    }
+  }
+
+  /// Addresses the same message to every player in the game
+  pub fn broadcast(&self, message: &shared_types::ServerEvent) -> game_engine::types::GameMessages {
+    // in seat order, so that replaying a game sends the messages in the same order
+    return self
+      .seating
+      .iter()
+      .filter(|player| self.player_data.contains_key(*player))
+      .map(|player| (player.clone(), message.clone()))
+      .collect();
+  }
+
+  /// Gives each player their own view of the current state of the game
+  pub fn get_update_messages(&self) -> game_engine::types::GameMessages {
+    return self
+      .seating
+      .iter()
+      .filter_map(|player| Some((player, self.player_data.get(player)?)))
+      .map(|(player, player_data)| {
+        (
+          player.clone(),
+          shared_types::ServerEvent::from_event(
+            shared_types::ServerEventCode::GameUpdate,
+            shared_types::ServerEventData {
+              game_data: Some(self.to_game_data()),
+              player_data: Some(player_data.clone()),
+              ..Default::default()
+            },
+          ),
+        )
+      })
+      .collect();
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::game_engine::test_utils::{card, game_dict, game_with_sheriff};
+  use crate::game_engine::types::{GameState, RuleError};
+  use crate::shared_types::{Card, CardName, CardRank, CardSuit, ServerEventCode, TurnPhase};
+
+  /// A game where "p0" is about to start their turn with the Dynamite in front of them
+  fn game_with_dynamite(flip: Card) -> GameState {
+    let mut game_state = game_with_sheriff();
+    game_state
+      .player_data
+      .get_mut("p0")
+      .unwrap()
+      .field
+      .push(Card {
+        rank: CardRank::N2,
+        ..card(CardName::Dynamite, CardSuit::Hearts)
+      });
+    game_state.deck = vec![flip];
+    return game_state;
+  }
+
+  #[test]
+  fn dynamite_explodes_on_a_low_spade() {
+    let game_dict = game_dict();
+    let mut game_state = game_with_dynamite(Card {
+      rank: CardRank::N9,
+      ..card(CardName::Bang, CardSuit::Spades)
+    });
+
+    let messages = game_state.start_turn(&game_dict);
+    assert!(messages
+      .iter()
+      .any(|(_, message)| matches!(message.event_code, ServerEventCode::DynamiteExploded)));
+    assert_eq!(game_state.player_data["p0"].health, 1);
+    assert!(game_state.player_data["p0"].field.is_empty());
+    assert!(game_state
+      .discard
+      .iter()
+      .any(|card| card.name == CardName::Dynamite));
+    assert!(game_state.turn_phase == TurnPhase::Draw);
+    assert_eq!(game_state.get_active_player(), "p0");
+  }
+
+  #[test]
+  fn dynamite_passes_to_the_next_player_otherwise() {
+    let game_dict = game_dict();
+    let mut game_state = game_with_dynamite(Card {
+      rank: CardRank::N10,
+      ..card(CardName::Bang, CardSuit::Spades)
+    });
+
+    let messages = game_state.start_turn(&game_dict);
+    assert!(messages.iter().any(|(player, message)| {
+      player == "p2" && matches!(message.event_code, ServerEventCode::DynamitePassed)
+    }));
+    assert_eq!(game_state.player_data["p0"].health, 4);
+    assert!(game_state.player_data["p0"].field.is_empty());
+    assert_eq!(
+      game_state.player_data["p1"].field[0].name,
+      CardName::Dynamite
+    );
+  }
+
+  #[test]
+  fn an_explosion_can_end_the_turn_of_its_holder() {
+    let game_dict = game_dict();
+    let mut game_state = game_with_dynamite(Card {
+      rank: CardRank::N2,
+      ..card(CardName::Bang, CardSuit::Spades)
+    });
+    game_state.player_data.get_mut("p0").unwrap().health = 3;
+
+    game_state.start_turn(&game_dict);
+    assert!(!game_state.player_data["p0"].is_alive());
+    assert_eq!(game_state.get_active_player(), "p1");
+    assert!(game_state.turn_phase == TurnPhase::Draw);
+  }
+
+  /// A game where "p0" is about to start their turn in Jail
+  fn game_with_jail(flip: Card) -> GameState {
+    let mut game_state = game_with_sheriff();
+    game_state
+      .player_data
+      .get_mut("p0")
+      .unwrap()
+      .field
+      .push(Card {
+        rank: CardRank::J,
+        ..card(CardName::Jail, CardSuit::Spades)
+      });
+    game_state.deck = vec![flip];
+    return game_state;
+  }
+
+  #[test]
+  fn a_heart_breaks_out_of_jail() {
+    let game_dict = game_dict();
+    let mut game_state = game_with_jail(Card {
+      rank: CardRank::N6,
+      ..card(CardName::Beer, CardSuit::Hearts)
+    });
+
+    game_state.start_turn(&game_dict);
+    assert_eq!(game_state.get_active_player(), "p0");
+    assert!(game_state.turn_phase == TurnPhase::Draw);
+    assert!(game_state.player_data["p0"].field.is_empty());
+  }
+
+  #[test]
+  fn otherwise_the_turn_is_skipped() {
+    let game_dict = game_dict();
+    let mut game_state = game_with_jail(Card {
+      rank: CardRank::N6,
+      ..card(CardName::Beer, CardSuit::Clubs)
+    });
+
+    let messages = game_state.start_turn(&game_dict);
+    assert!(messages
+      .iter()
+      .any(|(_, message)| { matches!(message.event_code, ServerEventCode::TurnSkipped) }));
+    assert_eq!(game_state.get_active_player(), "p1");
+    assert!(game_state.turn_phase == TurnPhase::Draw);
+    assert!(game_state.player_data["p0"].field.is_empty());
+    assert_eq!(game_state.discard.last().unwrap().name, CardName::Jail);
+  }
+
+  #[test]
+  fn neither_the_sheriff_nor_a_jailed_player_can_be_jailed() {
+    let game_dict = game_dict();
+    let mut game_state = game_with_jail(Card {
+      rank: CardRank::N6,
+      ..card(CardName::Beer, CardSuit::Clubs)
+    });
+    let jail = &game_dict.card_dict[&CardName::Jail];
+    let cards = vec![Card {
+      rank: CardRank::N4,
+      ..card(CardName::Jail, CardSuit::Hearts)
+    }];
+    let targets = |target: &str| vec![target.to_string()];
+
+    assert!(
+      (jail.preconditions)("p1", &cards, &targets("p3"), &mut game_state, &game_dict).is_err()
+    );
+    assert!(
+      (jail.preconditions)("p1", &cards, &targets("p0"), &mut game_state, &game_dict).is_err()
+    );
+    assert!(
+      (jail.preconditions)("p1", &cards, &targets("p2"), &mut game_state, &game_dict).is_ok()
+    );
+  }
+
+  #[test]
+  fn turn_actions_are_refused_outside_of_their_phase() {
+    let game_dict = game_dict();
+    let mut game_state = game_with_sheriff();
+    game_state.turn_phase = TurnPhase::Draw;
+    assert!(matches!(
+      game_state.end_play_phase("p0", &game_dict),
+      Err(RuleError::InvalidPhase)
+    ));
+    assert!(matches!(
+      game_state.discard_phase("p0", &Vec::new(), &game_dict),
+      Err(RuleError::InvalidPhase)
+    ));
+
+    game_state.turn_phase = TurnPhase::Play;
+    assert!(matches!(
+      game_state.draw_phase("p0", &Vec::new(), &Vec::new(), &game_dict),
+      Err(RuleError::InvalidPhase)
+    ));
+    assert!(matches!(
+      game_state.discard_phase("p0", &Vec::new(), &game_dict),
+      Err(RuleError::InvalidPhase)
+    ));
+    assert_eq!(game_state.turn_phase, TurnPhase::Play);
+  }
+
+  #[test]
+  fn the_turn_ends_once_the_hand_is_down_to_the_limit() {
+    let game_dict = game_dict();
+    let mut game_state = game_with_sheriff();
+    let hand: Vec<Card> = (0..6)
+      .map(|_| Card {
+        rank: CardRank::N2,
+        ..card(CardName::Bang, CardSuit::Clubs)
+      })
+      .collect();
+    game_state.player_data.get_mut("p0").unwrap().hand = hand.clone();
+
+    game_state.end_play_phase("p0", &game_dict).unwrap();
+    assert_eq!(game_state.turn_phase, TurnPhase::Discard);
+    assert!(matches!(
+      game_state.end_play_phase("p0", &game_dict),
+      Err(RuleError::HandLimitExceeded)
+    ));
+    // cards that are not in the hand can not be discarded
+    assert!(matches!(
+      game_state.discard_phase(
+        "p0",
+        &vec![Card {
+          rank: CardRank::N6,
+          ..card(CardName::Beer, CardSuit::Hearts)
+        }],
+        &game_dict
+      ),
+      Err(RuleError::CardNotInHand)
+    ));
+    // the hand does not go below the limit of four
+    assert!(matches!(
+      game_state.discard_phase("p0", &hand[..3].to_vec(), &game_dict),
+      Err(RuleError::InvalidSelection)
+    ));
+    assert_eq!(game_state.player_data["p0"].hand.len(), 6);
+
+    game_state
+      .discard_phase("p0", &hand[..2].to_vec(), &game_dict)
+      .unwrap();
+    assert_eq!(game_state.player_data["p0"].hand.len(), 4);
+    assert_eq!(game_state.get_active_player(), "p1");
+  }
+}
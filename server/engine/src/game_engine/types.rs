@@ -0,0 +1,463 @@
+use crate::{game_engine, shared_types};
+use nanorand::WyRand;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+pub type GameStates = HashMap<String, GameState>;
+
+#[derive(Clone)]
+pub struct GameState {
+  pub turn_index: usize,
+  /// the number of turns that have started since the game began
+  pub turn_count: usize,
+  /// the number of times the active player has used their ability this turn
+  pub ability_uses: u8,
+  pub expansions: Vec<shared_types::Expansion>,
+  /// the original characters of players who copied the ability of another (ex. Vera Custer)
+  pub copied_characters: HashMap<String, shared_types::Character>,
+  pub turn_phase: shared_types::TurnPhase,
+  pub player_order: Vec<String>,
+  /// every player in the seat they started the game in, including those who were eliminated
+  pub seating: Vec<String>,
+  /// eliminated players who returned to the table for a turn during Ghost Town
+  pub ghosts: Vec<String>,
+  pub player_data: HashMap<String, shared_types::PlayerData>,
+  pub deck: Vec<shared_types::Card>,
+  /// every card the game was dealt from, for opening a fresh deck once the cards run out
+  pub deck_list: Vec<shared_types::Card>,
+  pub discard: Vec<shared_types::Card>,
+  pub bangs_played: u8,
+  pub empty_deck_rule: shared_types::EmptyDeckRule,
+  /// the characters each player is choosing between before the game begins
+  pub character_choices: HashMap<String, Vec<shared_types::Character>>,
+  /// cards taken off the deck that are waiting on a player to choose between them
+  pub revealed_cards: Vec<shared_types::Card>,
+  /// the field card chosen by the player of the card being played, for cards such as Panic! and Cat Balou
+  pub field_selection: Option<shared_types::Card>,
+  /// the turn from which each green card in front of a player can be used
+  pub usable_from: HashMap<shared_types::Card, usize>,
+  /// the event cards left to reveal, with the next one at the end
+  pub event_deck: Vec<shared_types::EventCard>,
+  pub current_event: Option<shared_types::EventCard>,
+  /// the shop cards left to put up for sale, with the next one at the end
+  pub shop_deck: Vec<shared_types::ShopCard>,
+  /// the shop cards that can be bought
+  pub shop: Vec<shared_types::ShopCard>,
+  /// whether the active player is taking a turn right after their own (ex. Don Bell)
+  pub extra_turn: bool,
+  /// the seed the game was dealt from, which reproduces the exact game when reused
+  pub seed: u32,
+  /// the source of every shuffle and random pick of the game, started from the seed
+  pub rng: WyRand,
+  /// every action accepted since the game was dealt, for replaying it
+  pub log: game_engine::replay::GameLog,
+  /// whether the players may vote to take back the last action
+  pub take_backs: bool,
+  /// the names sent to clients of the cards that are not built into them
+  pub custom_cards: Vec<shared_types::CustomName>,
+  /// the names sent to clients of the characters that are not built into them
+  pub custom_characters: Vec<shared_types::CustomName>,
+  /// the game as it was before the last action taken by a player, while take backs are allowed
+  pub snapshot: Option<Box<GameState>>,
+  /// the players who voted to take back the last action
+  pub take_back_votes: Vec<String>,
+  // data for dynamic gameplay
+  pub event_stack: Vec<CardEvent>,
+  pub card_events: Vec<shared_types::CardName>,
+  pub trigger_queue: HashMap<String, shared_types::ResponseData>,
+  pub response_queue: HashMap<String, shared_types::ResponseData>,
+}
+impl GameState {
+  pub fn to_game_data(&self) -> shared_types::GameData {
+    shared_types::GameData {
+      turn_index: self.turn_index,
+      turn_phase: self.turn_phase.clone(),
+      card_events: self.card_events.clone(),
+      discard: self.discard.clone(),
+      player_order: self.player_order.clone(),
+      current_event: self.current_event.clone(),
+      shop: self.shop.clone(),
+      seed: self.seed,
+      take_backs: self.take_backs,
+      empty_deck_rule: self.empty_deck_rule.clone(),
+      custom_cards: self.custom_cards.clone(),
+      custom_characters: self.custom_characters.clone(),
+    }
+  }
+}
+
+/// The reason an action was refused by the rules of the game,
+/// sent to the player as an error code along with the details of the error
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum RuleError {
+  InvalidPhase,
+  HandLimitExceeded,
+  NotYourTurn,
+  CardNotInHand,
+  InvalidSelection,
+  AbilityUnavailable,
+  CardNotReady,
+  CardDisabled,
+  NotEnoughGold,
+  InvalidLog,
+  TakeBackUnavailable,
+  /// the target is further away than the card reaches
+  OutOfRange {
+    target: String,
+    distance: u8,
+    range: u8,
+  },
+  /// the card was played at a different number of players than it takes
+  WrongTargetCount {
+    expected: usize,
+    given: usize,
+  },
+  /// the target is not another living player at the table
+  InvalidTarget {
+    target: String,
+  },
+  BangLimitReached {
+    limit: u8,
+  },
+  /// the card can only be played in response to another card (ex. Missed!)
+  ResponseOnly,
+  /// the card heals a player who has not lost any health
+  FullHealth,
+  /// the card has no effect with this few players left (ex. Beer)
+  TooFewPlayers,
+  SheriffTargeted {
+    target: String,
+  },
+  EmptyHand {
+    target: String,
+  },
+  /// the target does not have the chosen field card in front of them
+  FieldCardMissing {
+    target: String,
+  },
+  /// the target already has a card with the same name in front of them
+  DuplicateEquipment {
+    target: String,
+    card: shared_types::CardName,
+  },
+  /// the card must be played along with another card to discard
+  ExtraDiscardRequired,
+  /// the target is still alive, for cards played on eliminated players (ex. Ghost)
+  NotEliminated {
+    target: String,
+  },
+}
+
+/// Messages produced by the game logic, paired with the id of the player who should receive them
+pub type GameMessages = Vec<(String, shared_types::ServerEvent)>;
+
+/// Games skip the draw unless the session owner chose otherwise
+impl Default for shared_types::EmptyDeckRule {
+  fn default() -> Self {
+    return shared_types::EmptyDeckRule::SkipDraw;
+  }
+}
+
+/// The cards clients know by their code, for reading codes back into card names
+const BUILT_IN_CARDS: [shared_types::CardName; 46] = {
+  use shared_types::CardName as C;
+  [
+    C::Bang,
+    C::Hatchet,
+    C::Missed,
+    C::Beer,
+    C::Saloon,
+    C::Stagecoach,
+    C::WellsFargo,
+    C::Panic,
+    C::CatBalou,
+    C::Duel,
+    C::Indians,
+    C::Gatling,
+    C::GeneralStore,
+    C::Punch,
+    C::Dodge,
+    C::Springfield,
+    C::Whisky,
+    C::Tequila,
+    C::Brawl,
+    C::RagTime,
+    C::Barrel,
+    C::Dynamite,
+    C::Jail,
+    C::Mustang,
+    C::Scope,
+    C::Volcanic,
+    C::Schofield,
+    C::Remington,
+    C::RevCarabine,
+    C::Winchester,
+    C::Hideout,
+    C::Silver,
+    C::Binocular,
+    C::PonyExpress,
+    C::Bible,
+    C::IronPlate,
+    C::Canteen,
+    C::Sombrero,
+    C::TenGallonHat,
+    C::BuffaloRifle,
+    C::CanCan,
+    C::Conestoga,
+    C::Derringer,
+    C::Howitzer,
+    C::Knife,
+    C::Pepperbox,
+  ]
+};
+
+/// Cards are sent to clients and written to game logs by their code
+impl From<shared_types::CardName> for u8 {
+  fn from(name: shared_types::CardName) -> u8 {
+    return match name {
+      shared_types::CardName::Custom(code) => code,
+      // SAFETY: a repr(u8) enum starts with its discriminant, the code of a built in card
+      _ => unsafe { *(&name as *const shared_types::CardName as *const u8) },
+    };
+  }
+}
+
+/// Codes that are not of a built in card are of a card named by the game
+impl From<u8> for shared_types::CardName {
+  fn from(code: u8) -> Self {
+    return BUILT_IN_CARDS
+      .iter()
+      .find(|name| u8::from((*name).clone()) == code)
+      .cloned()
+      .unwrap_or(shared_types::CardName::Custom(code));
+  }
+}
+
+/// The characters clients know by their code, for reading codes back into characters
+const BUILT_IN_CHARACTERS: [shared_types::Character; 39] = {
+  use shared_types::Character as C;
+  [
+    C::BartCassidy,
+    C::BlackJack,
+    C::CalamityJanet,
+    C::ElGringo,
+    C::JesseJones,
+    C::Jourdonnais,
+    C::KitCarlson,
+    C::LuckyDuke,
+    C::PaulRegret,
+    C::PedroRamirez,
+    C::RoseDoolan,
+    C::SidKetchum,
+    C::SlabTheKiller,
+    C::SuzyLafayette,
+    C::VultureSam,
+    C::WillyTheKid,
+    C::ApacheKid,
+    C::BelleStar,
+    C::BillNoface,
+    C::ChuckWengam,
+    C::DocHolyday,
+    C::ElenaFuente,
+    C::GregDigger,
+    C::HerbHunter,
+    C::JoseDelgado,
+    C::MollyStark,
+    C::PatBrennan,
+    C::PixiePete,
+    C::SeanMallory,
+    C::TequilaJoe,
+    C::VeraCuster,
+    C::DonBell,
+    C::DutchWill,
+    C::JackyMurieta,
+    C::JoshMcCloud,
+    C::MadamYto,
+    C::PrettyLuzena,
+    C::RaddieSnake,
+    C::SimeonPicos,
+  ]
+};
+
+/// Characters are sent to clients and written to game logs by their code
+impl From<shared_types::Character> for u8 {
+  fn from(character: shared_types::Character) -> u8 {
+    return match character {
+      shared_types::Character::Custom(code) => code,
+      // SAFETY: a repr(u8) enum starts with its discriminant, the code of a built in character
+      _ => unsafe { *(&character as *const shared_types::Character as *const u8) },
+    };
+  }
+}
+
+/// Codes that are not of a built in character are of a character named by the game
+impl From<u8> for shared_types::Character {
+  fn from(code: u8) -> Self {
+    return BUILT_IN_CHARACTERS
+      .iter()
+      .find(|character| u8::from((*character).clone()) == code)
+      .cloned()
+      .unwrap_or(shared_types::Character::Custom(code));
+  }
+}
+
+/// The result of a "draw!" check
+pub struct DrawCheck {
+  /// the card which decided the result
+  pub card: shared_types::Card,
+  pub passed: bool,
+  /// messages showing the flipped cards to the players
+  pub messages: GameMessages,
+}
+
+/// A card that was played and is waiting on responses from its targets before it is resolved
+#[derive(Debug, Clone)]
+pub struct CardEvent {
+  pub user_id: String,
+  pub card: shared_types::CardName,
+  /// the targets who have yet to be prompted, in seat order
+  pub targets: Vec<String>,
+  /// what each target is prompted with
+  pub trigger: EventTrigger,
+}
+
+pub type CharacterDictionary = HashMap<shared_types::Character, CharacterData>;
+pub type CardDictionary = HashMap<shared_types::CardName, CardData>;
+pub type ExpansionDictionary = HashMap<shared_types::Expansion, ExpansionData>;
+pub struct GameDictionary {
+  pub card_dict: CardDictionary,
+  pub character_dict: CharacterDictionary,
+  pub expansion_dict: ExpansionDictionary,
+  /// cards defined in the card definition file, which take the place of their built in rules
+  pub card_definitions: game_engine::card_definitions::CardDefinitions,
+  /// the names of the cards that clients only know by their code
+  pub card_names: HashMap<shared_types::CardName, String>,
+  /// the names of the characters that clients only know by their code
+  pub character_names: HashMap<shared_types::Character, String>,
+}
+
+/// The suit and rank of every copy of each card in a deck
+pub type DeckDistribution = Vec<(
+  shared_types::CardName,
+  Vec<(shared_types::CardSuit, shared_types::CardRank)>,
+)>;
+
+/// Everything an expansion adds on top of the base game
+pub struct ExpansionData {
+  /// the most players who can sit at the table with the expansion
+  pub max_players: usize,
+  /// the characters dealt only when the expansion is enabled
+  pub characters: Vec<shared_types::Character>,
+  /// the names of the cards of the expansion that clients only know by their code
+  pub card_names: Vec<(shared_types::CardName, &'static str)>,
+  /// the names of the characters of the expansion that clients only know by their code
+  pub character_names: Vec<(shared_types::Character, &'static str)>,
+  /// the cards shuffled into the deck when the expansion is enabled
+  pub deck_distribution: DeckDistribution,
+  /// the events shuffled into the event deck when the expansion is enabled
+  pub events: Vec<shared_types::EventCard>,
+  /// the event that is always revealed last (ex. High Noon)
+  pub final_event: Option<shared_types::EventCard>,
+  /// adds the data of the cards of the expansion to the card dictionary
+  pub insert_cards: fn(&mut CardDictionary),
+  /// adds the data of the characters of the expansion to the character dictionary
+  pub insert_characters: fn(&mut CharacterDictionary),
+}
+
+/// Details of the event that activated a character ability
+pub struct TriggerContext<'a> {
+  pub trigger: EventTrigger,
+  /// the other player involved in the event, such as the one who dealt the damage
+  pub source: Option<&'a str>,
+  /// the amount of health lost, for abilities that activate on damage
+  pub amount: u8,
+  /// the cards and targets chosen by the owner of the ability
+  pub cards: &'a Vec<shared_types::Card>,
+  pub targets: &'a Vec<String>,
+}
+
+/// Character Effects apply the ability of a character onto the GameState when one of its triggers occurs,
+/// returning the messages that should be relayed to the players
+pub type CharacterEffect =
+  fn(&str, &TriggerContext, &mut GameState, &GameDictionary) -> Result<GameMessages, RuleError>;
+
+/// Abilities which change the rules rather than reacting to an event (ex. Willy the Kid)
+/// have no triggers and are checked by the game logic they affect.
+pub struct CharacterData {
+  pub hp: u8,
+  pub triggers: Vec<EventTrigger>,
+  pub effect: CharacterEffect,
+  /// whether the player decides to use the ability, rather than it activating on its own
+  pub effect_optional: bool,
+  pub distance_modifier: DistanceModifier,
+}
+
+/// Card Preconditions should be game-logic based.
+/// Do not worry about the player having the Cards or any state-based logic
+pub type CardConditions = fn(
+  &str,
+  &Vec<shared_types::Card>,
+  &Vec<String>,
+  &mut GameState,
+  &GameDictionary,
+) -> Result<(), RuleError>;
+
+/// Card Effects apply the result of playing a card onto the GameState,
+/// returning the messages that should be relayed to the players
+pub type CardEffect =
+  fn(&str, &Vec<shared_types::Card>, &Vec<String>, &mut GameState, &GameDictionary) -> GameMessages;
+
+/// A function which makes modifications to a GameState as a result of game mechanics
+pub type GameStateUpdate =
+  fn(&str, &Vec<shared_types::Card>, &Vec<String>, &mut GameState, &GameDictionary) -> GameMessages;
+
+pub struct CardData {
+  pub color: CardColor,
+  /// the range of a weapon while it is equipped, `None` for cards that are not weapons
+  pub weapon_range: Option<u8>,
+  pub distance_modifier: DistanceModifier,
+
+  pub triggers: Vec<EventTrigger>,
+  pub preconditions: CardConditions,
+  pub effect: CardEffect,
+  pub update: GameStateUpdate,
+}
+
+/// Changes to the seat distance between players caused by a field card or character ability
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(default)]
+pub struct DistanceModifier {
+  /// increase to the distance at which other players see the owner (ex. Mustang)
+  pub defense: u8,
+  /// decrease to the distance at which the owner sees other players (ex. Scope)
+  pub offense: u8,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Eq, PartialEq)]
+pub enum CardColor {
+  Brown = 1,
+  Blue,
+  Green,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Eq, PartialEq)]
+pub enum EventTrigger {
+  /// the player is being shot at and could avoid the damage
+  Damage = 1,
+  /// the player must discard a Bang! or take damage
+  Challenge,
+  /// the player lost health
+  HealthLost,
+  /// another player was eliminated
+  PlayerEliminated,
+  /// the player has no cards left in their hand
+  HandEmpty,
+  /// the player takes their cards for the turn
+  DrawPhase,
+  /// the player chose to use their ability
+  Activated,
+  /// the player picks one of the revealed cards
+  Selection,
+  /// the player used cards out of turn in response to another card
+  Responded,
+}
@@ -0,0 +1,663 @@
+use crate::{game_engine, shared_types};
+
+// The cards and characters of Valley of Shadows are registered with the expansion,
+// so clients know them by the codes and names below instead of by their own
+pub const TOMAHAWK: shared_types::CardName = shared_types::CardName::Custom(47);
+pub const TORNADO: shared_types::CardName = shared_types::CardName::Custom(48);
+pub const LAST_CALL: shared_types::CardName = shared_types::CardName::Custom(49);
+pub const LEMAT: shared_types::CardName = shared_types::CardName::Custom(50);
+pub const GHOST: shared_types::CardName = shared_types::CardName::Custom(51);
+pub const RATTLESNAKE: shared_types::CardName = shared_types::CardName::Custom(52);
+pub const BLACK_FLOWER: shared_types::Character = shared_types::Character::Custom(40);
+pub const DER_SPOT_BURST_RINGER: shared_types::Character = shared_types::Character::Custom(41);
+pub const EVELYN_SHEBANG: shared_types::Character = shared_types::Character::Custom(42);
+pub const TUCO_FRANZISKANER: shared_types::Character = shared_types::Character::Custom(43);
+
+/// The characters that come with Valley of Shadows
+pub const CHARACTERS: [shared_types::Character; 4] = [
+  BLACK_FLOWER,
+  DER_SPOT_BURST_RINGER,
+  EVELYN_SHEBANG,
+  TUCO_FRANZISKANER,
+];
+
+/// The distance a Tomahawk can be thrown
+const TOMAHAWK_RANGE: u8 = 2;
+
+/// The number of cards each player draws after a Tornado
+const TORNADO_DRAW_COUNT: usize = 2;
+
+/// The extra cards Tuco Franziskaner draws with no Blue cards in front of him
+const TUCO_FRANZISKANER_EXTRA_DRAW_COUNT: usize = 2;
+
+/// Valley of Shadows
+///
+/// Eliminated players can return to the table as ghosts,
+/// and a Rattlesnake bites the player in front of whom it is put at the start of their turns.
+impl game_engine::types::GameState {
+  /// Whether any card a player holds can be used as a Bang,
+  /// which is the case during their own turn with a Lemat in play
+  pub fn is_lemat_ready(&self, player: &str) -> bool {
+    return self.has_equipment(player, LEMAT)
+      && self.is_field_active(player)
+      && self.player_order.get(self.turn_index).map(String::as_str) == Some(player);
+  }
+
+  /// The player loses a life point to the Rattlesnake in front of them on a draw check of a Spade
+  pub fn resolve_rattlesnake(
+    &mut self,
+    player: &str,
+    game_dict: &game_engine::types::GameDictionary,
+  ) -> game_engine::types::GameMessages {
+    let (bitten, mut messages) =
+      match self.draw_check(player, |card| card.suit != shared_types::CardSuit::Spades) {
+        Some(draw_check) => (!draw_check.passed, draw_check.messages),
+        None => (false, Vec::new()),
+      };
+    if bitten {
+      messages.extend(self.damage_player(player, 1, None, game_dict));
+    }
+    return messages;
+  }
+}
+
+/// What Valley of Shadows adds to the base game
+pub fn get_expansion_data() -> game_engine::types::ExpansionData {
+  game_engine::types::ExpansionData {
+    max_players: game_engine::expansions::BASE_MAX_PLAYERS,
+    characters: CHARACTERS.to_vec(),
+    card_names: vec![
+      (TOMAHAWK, "Tomahawk"),
+      (TORNADO, "Tornado"),
+      (LAST_CALL, "LastCall"),
+      (LEMAT, "Lemat"),
+      (GHOST, "Ghost"),
+      (RATTLESNAKE, "Rattlesnake"),
+    ],
+    character_names: vec![
+      (BLACK_FLOWER, "BlackFlower"),
+      (DER_SPOT_BURST_RINGER, "DerSpotBurstRinger"),
+      (EVELYN_SHEBANG, "EvelynShebang"),
+      (TUCO_FRANZISKANER, "TucoFranziskaner"),
+    ],
+    deck_distribution: get_deck_distribution(),
+    events: Vec::new(),
+    final_event: None,
+    insert_cards,
+    insert_characters,
+  }
+}
+
+/// The suit and rank of every card Valley of Shadows adds to the deck
+fn get_deck_distribution() -> game_engine::types::DeckDistribution {
+  use shared_types::CardRank as R;
+  use shared_types::CardSuit as S;
+  vec![
+    // Brown Cards
+    (TOMAHAWK, vec![(S::Diamonds, R::A)]),
+    (TORNADO, vec![(S::Clubs, R::N4), (S::Diamonds, R::K)]),
+    (LAST_CALL, vec![(S::Diamonds, R::N8)]),
+    // Blue Cards
+    (LEMAT, vec![(S::Diamonds, R::N4)]),
+    (GHOST, vec![(S::Spades, R::N9), (S::Spades, R::N10)]),
+    (RATTLESNAKE, vec![(S::Hearts, R::N7)]),
+  ]
+}
+
+/// Adds the cards of Valley of Shadows to the card dictionary
+pub fn insert_cards(card_dict: &mut game_engine::types::CardDictionary) {
+  //===============================
+  // Tomahawk
+  //===============================
+  // shoots a player within reach, without counting as the Bang of the turn
+  card_dict.insert(
+    TOMAHAWK,
+    game_engine::types::CardData {
+      color: game_engine::types::CardColor::Brown,
+      weapon_range: None,
+      distance_modifier: game_engine::types::DistanceModifier::default(),
+      triggers: vec![],
+      preconditions: |user_id, _cards, targets, game_state, game_dict| {
+        game_engine::data::check_target_count(targets, 1)?;
+        return game_state.check_range(user_id, &targets[0], TOMAHAWK_RANGE, game_dict);
+      },
+      effect: |user_id, cards, targets, game_state, game_dict| {
+        game_state.discard_cards(user_id, cards);
+        return game_state.shoot_players(
+          user_id,
+          &cards[0].name,
+          targets,
+          game_engine::types::EventTrigger::Damage,
+          game_dict,
+        );
+      },
+      update: game_engine::data::resolve_shot,
+    },
+  );
+  //===============================
+  // Tornado
+  //===============================
+  // every player discards a card from their hand, then draws two
+  card_dict.insert(
+    TORNADO,
+    game_engine::types::CardData {
+      color: game_engine::types::CardColor::Brown,
+      weapon_range: None,
+      distance_modifier: game_engine::types::DistanceModifier::default(),
+      triggers: vec![],
+      preconditions: game_engine::data::no_target_preconditions,
+      effect: |user_id, cards, _targets, game_state, _game_dict| {
+        game_state.discard_cards(user_id, cards);
+        let mut messages = Vec::new();
+        for player in game_state.player_order.clone() {
+          if let Some(card) = game_state.take_random_hand_card(&player) {
+            game_state.discard.push(card);
+          }
+          messages.extend(game_state.draw_cards(&player, TORNADO_DRAW_COUNT));
+        }
+        return messages;
+      },
+      update: |_user_id, _cards, _targets, _game_state, _game_dict| Vec::new(),
+    },
+  );
+  //===============================
+  // Last Call
+  //===============================
+  // a Beer that works even with two players left
+  card_dict.insert(
+    LAST_CALL,
+    game_engine::types::CardData {
+      color: game_engine::types::CardColor::Brown,
+      weapon_range: None,
+      distance_modifier: game_engine::types::DistanceModifier::default(),
+      triggers: vec![],
+      preconditions: |user_id, _cards, targets, game_state, _game_dict| {
+        game_engine::data::check_target_count(targets, 0)?;
+        match game_state.player_data.get(user_id) {
+          Some(player_data) if player_data.health < player_data.max_health => Ok(()),
+          _ => Err(game_engine::types::RuleError::FullHealth),
+        }
+      },
+      effect: |user_id, cards, _targets, game_state, _game_dict| {
+        game_state.discard_cards(user_id, cards);
+        game_state.heal_player(user_id, 1);
+        return Vec::new();
+      },
+      update: |_user_id, _cards, _targets, _game_state, _game_dict| Vec::new(),
+    },
+  );
+  //===============================
+  // Lemat
+  //===============================
+  // any card can be used as a Bang during the turn of its owner
+  card_dict.insert(
+    LEMAT,
+    game_engine::data::equipment_card_data(
+      Some(1),
+      game_engine::types::DistanceModifier::default(),
+    ),
+  );
+  //===============================
+  // Ghost
+  //===============================
+  // put in front of an eliminated player, who comes back to play as a ghost for as long as it stays there
+  card_dict.insert(
+    GHOST,
+    game_engine::types::CardData {
+      color: game_engine::types::CardColor::Blue,
+      weapon_range: None,
+      distance_modifier: game_engine::types::DistanceModifier::default(),
+      triggers: vec![],
+      preconditions: |_user_id, _cards, targets, game_state, _game_dict| {
+        game_engine::data::check_target_count(targets, 1)?;
+        if !game_state.seating.contains(&targets[0])
+          || game_state.player_order.contains(&targets[0])
+        {
+          return Err(game_engine::types::RuleError::NotEliminated {
+            target: targets[0].clone(),
+          });
+        }
+        return Ok(());
+      },
+      effect: |user_id, cards, targets, game_state, _game_dict| {
+        game_state.raise_ghost(&targets[0]);
+        place_on_target(user_id, cards, targets, game_state);
+        return game_state.get_update_messages();
+      },
+      update: |_user_id, _cards, _targets, _game_state, _game_dict| Vec::new(),
+    },
+  );
+  //===============================
+  // Rattlesnake
+  //===============================
+  // put in front of another player, who is bitten on a draw check of a Spade at the start of each of their turns
+  card_dict.insert(
+    RATTLESNAKE,
+    game_engine::types::CardData {
+      color: game_engine::types::CardColor::Blue,
+      weapon_range: None,
+      distance_modifier: game_engine::types::DistanceModifier::default(),
+      triggers: vec![],
+      preconditions: |user_id, cards, targets, game_state, game_dict| {
+        game_engine::data::check_target_count(targets, 1)?;
+        game_state.check_range(user_id, &targets[0], u8::MAX, game_dict)?;
+        return game_state.check_equipment(&targets[0], &cards[0]);
+      },
+      effect: |user_id, cards, targets, game_state, _game_dict| {
+        place_on_target(user_id, cards, targets, game_state);
+        return Vec::new();
+      },
+      update: |_user_id, _cards, _targets, _game_state, _game_dict| Vec::new(),
+    },
+  );
+}
+
+/// Adds the characters of Valley of Shadows to the character dictionary
+pub fn insert_characters(character_dict: &mut game_engine::types::CharacterDictionary) {
+  //===============================
+  // Black Flower
+  //===============================
+  character_dict.insert(
+    BLACK_FLOWER,
+    game_engine::types::CharacterData {
+      hp: 4,
+      triggers: vec![game_engine::types::EventTrigger::Activated],
+      effect_optional: true,
+      effect: |player, context, game_state, game_dict| {
+        // once a turn, discards a Club to shoot a Bang that does not count as the Bang of the turn
+        game_state.check_turn_owner(player)?;
+        game_state.check_phase(shared_types::TurnPhase::Play)?;
+        if game_state.ability_uses > 0 {
+          return Err(game_engine::types::RuleError::AbilityUnavailable);
+        }
+        let range = game_state.get_player_range(player, game_dict);
+        match game_state.player_data.get(player) {
+          Some(player_data)
+            if context.cards.len() == 1
+              && context.cards[0].suit == shared_types::CardSuit::Clubs
+              && player_data.has_in_hand(context.cards)
+              && context.targets.len() == 1
+              && game_state.is_in_range(player, &context.targets[0], range, game_dict) => {}
+          _ => return Err(game_engine::types::RuleError::InvalidSelection),
+        }
+        game_state.ability_uses += 1;
+        game_state.discard_cards(player, context.cards);
+        return Ok(game_state.shoot_players(
+          player,
+          &shared_types::CardName::Bang,
+          context.targets,
+          game_engine::types::EventTrigger::Damage,
+          game_dict,
+        ));
+      },
+      distance_modifier: game_engine::types::DistanceModifier::default(),
+    },
+  );
+  //===============================
+  // Der Spot Burst Ringer
+  //===============================
+  character_dict.insert(
+    DER_SPOT_BURST_RINGER,
+    game_engine::types::CharacterData {
+      hp: 4,
+      triggers: vec![game_engine::types::EventTrigger::Activated],
+      effect_optional: true,
+      effect: |player, context, game_state, game_dict| {
+        // once a turn, plays a Bang as a Gatling
+        game_state.check_turn_owner(player)?;
+        game_state.check_phase(shared_types::TurnPhase::Play)?;
+        if game_state.ability_uses > 0 {
+          return Err(game_engine::types::RuleError::AbilityUnavailable);
+        }
+        match game_state.player_data.get(player) {
+          Some(player_data)
+            if context.cards.len() == 1
+              && player_data.has_in_hand(context.cards)
+              && game_state.counts_as(
+                player,
+                &context.cards[0].name,
+                &shared_types::CardName::Bang,
+              ) => {}
+          _ => return Err(game_engine::types::RuleError::InvalidSelection),
+        }
+        game_state.ability_uses += 1;
+        game_state.discard_cards(player, context.cards);
+        let targets = game_state.get_affected_players(player, &context.cards[0]);
+        return Ok(game_state.shoot_players(
+          player,
+          &shared_types::CardName::Gatling,
+          &targets,
+          game_engine::types::EventTrigger::Damage,
+          game_dict,
+        ));
+      },
+      distance_modifier: game_engine::types::DistanceModifier::default(),
+    },
+  );
+  //===============================
+  // Evelyn Shebang
+  //===============================
+  character_dict.insert(
+    EVELYN_SHEBANG,
+    game_engine::types::CharacterData {
+      hp: 4,
+      triggers: vec![game_engine::types::EventTrigger::DrawPhase],
+      effect_optional: true,
+      effect: |player, context, game_state, game_dict| {
+        // may shoot a Bang at a different player within reach for each card she does not draw
+        let draw_count = game_state.get_draw_count(player);
+        let range = game_state.get_player_range(player, game_dict);
+        let mut targets = context.targets.clone();
+        targets.dedup();
+        if targets.len() != context.targets.len()
+          || targets.len() > draw_count
+          || targets
+            .iter()
+            .any(|target| !game_state.is_in_range(player, target, range, game_dict))
+        {
+          return Err(game_engine::types::RuleError::InvalidSelection);
+        }
+        let mut messages = game_state.draw_cards(player, draw_count - targets.len());
+        game_state.turn_phase = shared_types::TurnPhase::Play;
+        if !targets.is_empty() {
+          messages.extend(game_state.shoot_players(
+            player,
+            &shared_types::CardName::Bang,
+            &targets,
+            game_engine::types::EventTrigger::Damage,
+            game_dict,
+          ));
+        }
+        return Ok(messages);
+      },
+      distance_modifier: game_engine::types::DistanceModifier::default(),
+    },
+  );
+  //===============================
+  // Tuco Franziskaner
+  //===============================
+  character_dict.insert(
+    TUCO_FRANZISKANER,
+    game_engine::types::CharacterData {
+      hp: 5,
+      triggers: vec![game_engine::types::EventTrigger::DrawPhase],
+      effect_optional: false,
+      effect: |player, _context, game_state, game_dict| {
+        // draws two extra cards when he has no Blue cards in front of him
+        let has_blue_card = match game_state.player_data.get(player) {
+          Some(player_data) => {
+            player_data
+              .field
+              .iter()
+              .any(|card| match game_dict.card_dict.get(&card.name) {
+                Some(card_data) => card_data.color == game_engine::types::CardColor::Blue,
+                None => false,
+              })
+          }
+          None => false,
+        };
+        let mut draw_count = game_state.get_draw_count(player);
+        if !has_blue_card {
+          draw_count += TUCO_FRANZISKANER_EXTRA_DRAW_COUNT;
+        }
+        let messages = game_state.draw_cards(player, draw_count);
+        game_state.turn_phase = shared_types::TurnPhase::Play;
+        return Ok(messages);
+      },
+      distance_modifier: game_engine::types::DistanceModifier::default(),
+    },
+  );
+}
+
+/// Moves the played card from the hand of the user onto the field of the target
+fn place_on_target(
+  user_id: &str,
+  cards: &Vec<shared_types::Card>,
+  targets: &Vec<String>,
+  game_state: &mut game_engine::types::GameState,
+) {
+  if let Some(player_data) = game_state.player_data.get_mut(user_id) {
+    player_data.remove_cards(cards);
+  }
+  if let Some(player_data) = game_state.player_data.get_mut(&targets[0]) {
+    player_data.field.extend(cards.iter().cloned());
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::game_engine::test_utils::{card, game_dict, game_state};
+  use crate::game_engine::types::GameState;
+  use crate::shared_types::{Card, CardName, CardSuit, Character, TurnPhase};
+
+  fn set_character(game_state: &mut GameState, player: &str, character: Character) {
+    game_state.player_data.get_mut(player).unwrap().character = character;
+  }
+
+  fn give(game_state: &mut GameState, player: &str, card: Card) {
+    game_state
+      .player_data
+      .get_mut(player)
+      .unwrap()
+      .hand
+      .push(card);
+  }
+
+  fn equip(game_state: &mut GameState, player: &str, card: Card) {
+    game_state
+      .player_data
+      .get_mut(player)
+      .unwrap()
+      .field
+      .push(card);
+  }
+
+  #[test]
+  fn the_deck_only_has_the_cards_with_valley_of_shadows() {
+    let game_dict = game_dict();
+    let is_tornado = |card: &Card| card.name == TORNADO;
+    let base_deck = crate::game_engine::data::generate_deck(
+      &Vec::new(),
+      &game_dict,
+      &mut nanorand::WyRand::new_seed(0),
+    );
+    assert!(!base_deck.iter().any(is_tornado));
+    let deck = crate::game_engine::data::generate_deck(
+      &vec![shared_types::Expansion::ValleyOfShadows],
+      &game_dict,
+      &mut nanorand::WyRand::new_seed(0),
+    );
+    assert_eq!(deck.iter().filter(|card| is_tornado(card)).count(), 2);
+  }
+
+  #[test]
+  fn a_tomahawk_does_not_count_as_the_bang_of_the_turn() {
+    let game_dict = game_dict();
+    let mut game_state = game_state(5);
+    let tomahawk = card(TOMAHAWK, CardSuit::Diamonds);
+    let tomahawk_data = &game_dict.card_dict[&TOMAHAWK];
+    give(&mut game_state, "p0", tomahawk.clone());
+    assert!((tomahawk_data.preconditions)(
+      "p0",
+      &vec![tomahawk.clone()],
+      &vec![String::from("p2")],
+      &mut game_state,
+      &game_dict
+    )
+    .is_ok());
+    (tomahawk_data.effect)(
+      "p0",
+      &vec![tomahawk],
+      &vec![String::from("p2")],
+      &mut game_state,
+      &game_dict,
+    );
+    assert_eq!(game_state.bangs_played, 0);
+    assert!(game_state.player_data["p0"].hand.is_empty());
+  }
+
+  #[test]
+  fn a_tornado_swaps_a_card_of_every_hand_for_two_new_ones() {
+    let game_dict = game_dict();
+    let mut game_state = game_state(4);
+    game_state.deck =
+      crate::game_engine::data::generate_deck(&Vec::new(), &game_dict, &mut game_state.rng);
+    let tornado = card(TORNADO, CardSuit::Clubs);
+    give(&mut game_state, "p0", tornado.clone());
+    give(
+      &mut game_state,
+      "p1",
+      card(CardName::Beer, CardSuit::Hearts),
+    );
+    (game_dict.card_dict[&TORNADO].effect)(
+      "p0",
+      &vec![tornado],
+      &Vec::new(),
+      &mut game_state,
+      &game_dict,
+    );
+    for player in ["p0", "p1", "p2", "p3"].iter() {
+      assert_eq!(game_state.player_data[*player].hand.len(), 2);
+    }
+    assert_eq!(game_state.discard.len(), 2);
+  }
+
+  #[test]
+  fn last_call_heals_with_two_players_left() {
+    let game_dict = game_dict();
+    let mut game_state = game_state(2);
+    game_state.player_data.get_mut("p0").unwrap().health = 2;
+    let last_call = card(LAST_CALL, CardSuit::Diamonds);
+    give(&mut game_state, "p0", last_call.clone());
+    let last_call_data = &game_dict.card_dict[&LAST_CALL];
+    assert!((last_call_data.preconditions)(
+      "p0",
+      &vec![last_call.clone()],
+      &Vec::new(),
+      &mut game_state,
+      &game_dict
+    )
+    .is_ok());
+    (last_call_data.effect)(
+      "p0",
+      &vec![last_call],
+      &Vec::new(),
+      &mut game_state,
+      &game_dict,
+    );
+    assert_eq!(game_state.player_data["p0"].health, 3);
+  }
+
+  #[test]
+  fn a_lemat_turns_any_card_into_a_bang_during_its_owners_turn() {
+    let mut game_state = game_state(4);
+    assert!(!game_state.counts_as("p0", &CardName::Beer, &CardName::Bang));
+    equip(&mut game_state, "p0", card(LEMAT, CardSuit::Diamonds));
+    equip(&mut game_state, "p1", card(LEMAT, CardSuit::Diamonds));
+    assert!(game_state.counts_as("p0", &CardName::Beer, &CardName::Bang));
+    assert!(!game_state.counts_as("p1", &CardName::Beer, &CardName::Bang));
+  }
+
+  #[test]
+  fn a_ghost_stays_in_play_while_the_card_is_in_front_of_them() {
+    let game_dict = game_dict();
+    let mut game_state = game_state(4);
+    game_state.player_order.retain(|id| id != "p2");
+    game_state.player_data.get_mut("p2").unwrap().health = 0;
+    let ghost = card(GHOST, CardSuit::Spades);
+    give(&mut game_state, "p0", ghost.clone());
+    let ghost_data = &game_dict.card_dict[&GHOST];
+    assert!((ghost_data.preconditions)(
+      "p0",
+      &vec![ghost.clone()],
+      &vec![String::from("p1")],
+      &mut game_state,
+      &game_dict
+    )
+    .is_err());
+    (ghost_data.effect)(
+      "p0",
+      &vec![ghost],
+      &vec![String::from("p2")],
+      &mut game_state,
+      &game_dict,
+    );
+    assert!(game_state.player_order.contains(&String::from("p2")));
+
+    // the ghost is not laid to rest at the end of their turn
+    game_state.turn_index = 2;
+    game_state.end_play_phase("p2", &game_dict).unwrap();
+    assert!(game_state.player_order.contains(&String::from("p2")));
+    assert_eq!(game_state.get_active_player(), "p3");
+  }
+
+  #[test]
+  fn a_rattlesnake_bites_on_a_spade() {
+    let game_dict = game_dict();
+    let mut game_state = game_state(4);
+    equip(&mut game_state, "p1", card(RATTLESNAKE, CardSuit::Hearts));
+    game_state.turn_index = 1;
+    game_state.deck = vec![card(CardName::Beer, CardSuit::Spades)];
+    game_state.start_turn(&game_dict);
+    assert_eq!(game_state.player_data["p1"].health, 3);
+    assert!(game_state.has_equipment("p1", RATTLESNAKE));
+  }
+
+  #[test]
+  fn der_spot_burst_ringer_fires_a_bang_as_a_gatling() {
+    let game_dict = game_dict();
+    let mut game_state = game_state(4);
+    set_character(&mut game_state, "p0", DER_SPOT_BURST_RINGER);
+    give(&mut game_state, "p0", card(CardName::Bang, CardSuit::Clubs));
+    give(
+      &mut game_state,
+      "p0",
+      card(CardName::Bang, CardSuit::Spades),
+    );
+    game_state
+      .use_ability(
+        "p0",
+        &vec![card(CardName::Bang, CardSuit::Clubs)],
+        &Vec::new(),
+        &game_dict,
+      )
+      .unwrap();
+    assert!(game_state
+      .use_ability(
+        "p0",
+        &vec![card(CardName::Bang, CardSuit::Spades)],
+        &Vec::new(),
+        &game_dict,
+      )
+      .is_err());
+  }
+
+  #[test]
+  fn draw_phase_abilities() {
+    let game_dict = game_dict();
+    let mut game_state = game_state(4);
+    game_state.turn_phase = TurnPhase::Draw;
+    game_state.deck =
+      crate::game_engine::data::generate_deck(&Vec::new(), &game_dict, &mut game_state.rng);
+    set_character(&mut game_state, "p0", TUCO_FRANZISKANER);
+    game_state
+      .draw_phase("p0", &Vec::new(), &Vec::new(), &game_dict)
+      .unwrap();
+    assert_eq!(game_state.player_data["p0"].hand.len(), 4);
+
+    let mut game_state = crate::game_engine::test_utils::game_state(4);
+    game_state.turn_phase = TurnPhase::Draw;
+    game_state.deck =
+      crate::game_engine::data::generate_deck(&Vec::new(), &game_dict, &mut game_state.rng);
+    set_character(&mut game_state, "p0", EVELYN_SHEBANG);
+    assert!(game_state
+      .draw_phase("p0", &Vec::new(), &vec![String::from("p2")], &game_dict)
+      .is_err());
+    game_state
+      .draw_phase("p0", &Vec::new(), &vec![String::from("p1")], &game_dict)
+      .unwrap();
+    assert_eq!(game_state.player_data["p0"].hand.len(), 1);
+    assert_eq!(game_state.turn_phase, TurnPhase::Play);
+  }
+}
@@ -0,0 +1,7 @@
+//! The rules of the game, free of any networking.
+//! Players change a game through `GameState::apply`, which answers with the events for each client.
+// the rules keep the explicit returns and `&Vec` parameters the game engine was written with
+#![allow(clippy::needless_return, clippy::ptr_arg)]
+
+pub mod game_engine;
+pub mod shared_types;
@@ -0,0 +1,448 @@
+/**
+ * This file contains type defintions which are shared between the front and back end applications
+ */
+use serde::{Deserialize, Serialize};
+use serde_repr::{Deserialize_repr, Serialize_repr};
+use std::collections::HashMap;
+
+#[derive(Serialize, Deserialize, Clone, Default)]
+pub struct ServerEventData {
+  pub session_id: Option<String>,
+  pub client_id: Option<String>,
+  pub session_client_ids: Option<Vec<String>>,
+  pub game_data: Option<GameData>,
+  pub player_data: Option<PlayerData>,
+  pub roles: Option<HashMap<String, Role>>,
+  pub winner_ids: Option<Vec<String>>,
+  pub cards: Option<Vec<Card>>,
+  pub characters: Option<Vec<Character>>,
+  pub response_data: Option<ResponseData>,
+  pub effect_code: Option<EffectCode>,
+  pub expansions: Option<Vec<Expansion>>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct PlayerData {
+  pub health: u8,
+  pub max_health: u8,
+  pub hand: Vec<Card>,
+  pub field: Vec<Card>,
+  pub character: Character,
+  pub role: Role,
+  /// the gold nuggets the player has to spend in the shop
+  pub gold: u8,
+  /// the equipment bought from the shop that is in front of the player
+  pub equipment: Vec<ShopCard>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct GameData {
+  pub turn_index: usize,
+  pub turn_phase: TurnPhase,
+  pub player_order: Vec<String>,
+  pub card_events: Vec<CardName>,
+  pub discard: Vec<Card>,
+  /// the event card changing the rules of the game, if one has been revealed
+  pub current_event: Option<EventCard>,
+  /// the shop cards that can be bought
+  pub shop: Vec<ShopCard>,
+  /// the seed the game was dealt from, for reproducing it in bug reports
+  pub seed: u32,
+  /// whether the players may vote to take back the last action
+  pub take_backs: bool,
+  /// what happens once both the deck and the discard pile run out
+  pub empty_deck_rule: EmptyDeckRule,
+  /// the names of the cards in the game that are not built into the client
+  pub custom_cards: Vec<CustomName>,
+  /// the names of the characters in the game that are not built into the client
+  pub custom_characters: Vec<CustomName>,
+}
+
+/// The name of a card or character that clients only know by its code
+#[derive(Serialize, Deserialize, Debug, Clone, Eq, PartialEq)]
+pub struct CustomName {
+  pub code: u8,
+  pub name: String,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct ServerEvent {
+  pub event_code: ServerEventCode,
+  pub error_code: Option<ErrorCode>,
+  /// the cards, players and numbers the error is about
+  pub error_params: Option<ErrorParams>,
+  pub message: Option<String>,
+  pub data: Option<ServerEventData>,
+}
+
+#[derive(Deserialize_repr, Serialize_repr, Debug, Clone, Eq, Hash, PartialEq)]
+#[repr(u8)]
+pub enum EffectCode {
+  GeneralStore = 1,
+  None,
+  Gatling,
+  Indians,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Eq, Hash, PartialEq)]
+pub struct Card {
+  pub name: CardName,
+  pub suit: CardSuit,
+  pub rank: CardRank,
+}
+
+/// The codes are sent to clients and written to game logs, so a new card takes the next free code
+#[derive(Serialize, Deserialize, Debug, Clone, Eq, Hash, PartialEq)]
+#[serde(from = "u8", into = "u8")]
+#[repr(u8)]
+pub enum CardName {
+  // Brown Cards
+  Bang = 1,
+  Hatchet = 2,
+  Missed = 3,
+  Beer = 14,
+  Saloon = 15,
+  Stagecoach = 16,
+  WellsFargo = 17,
+  Panic = 18,
+  CatBalou = 19,
+  Duel = 20,
+  Indians = 21,
+  Gatling = 22,
+  GeneralStore = 23,
+  Punch = 28,
+  Dodge = 29,
+  Springfield = 30,
+  Whisky = 31,
+  Tequila = 32,
+  Brawl = 33,
+  RagTime = 34,
+  // Blue Cards
+  Barrel = 4,
+  Dynamite = 5,
+  Jail = 24,
+  Mustang = 7,
+  Scope = 8,
+  Volcanic = 9,
+  Schofield = 10,
+  Remington = 11,
+  RevCarabine = 12,
+  Winchester = 13,
+  Hideout = 35,
+  Silver = 36,
+  Binocular = 37,
+  // Green Cards
+  PonyExpress = 6,
+  Bible = 25,
+  IronPlate = 26,
+  Canteen = 27,
+  Sombrero = 38,
+  TenGallonHat = 39,
+  BuffaloRifle = 40,
+  CanCan = 41,
+  Conestoga = 42,
+  Derringer = 43,
+  Howitzer = 44,
+  Knife = 45,
+  Pepperbox = 46,
+  // cards clients only know by the code and name sent with the game (ex. Valley of Shadows)
+  Custom(u8),
+}
+
+#[derive(Deserialize_repr, Serialize_repr, Debug, Clone, Eq, Hash, PartialEq)]
+#[repr(u8)]
+pub enum CardSuit {
+  Clubs = 1,
+  Diamonds,
+  Hearts,
+  Spades,
+}
+
+#[derive(Deserialize_repr, Serialize_repr, Debug, Clone, Eq, Hash, PartialEq)]
+#[repr(u8)]
+pub enum CardRank {
+  N1 = 1,
+  N2,
+  N3,
+  N4,
+  N5,
+  N6,
+  N7,
+  N8,
+  N9,
+  N10,
+  J,
+  Q,
+  K,
+  A,
+}
+
+#[derive(Deserialize_repr, Serialize_repr, Clone)]
+#[repr(u8)]
+pub enum ServerEventCode {
+  // session_id, client_id, session_client_ids
+  ClientJoined = 1,
+  // client_id
+  ClientLeft,
+  GameStarted,
+  // session_id, session_client_ids
+  DataResponse,
+  // client_id
+  TurnStart,
+  LogicError,
+  // game_data, player_data
+  GameUpdate,
+  // client_id, roles
+  PlayerEliminated,
+  // roles, winner_ids
+  GameOver,
+  // client_id, cards
+  CardsRevealed,
+  // client_id, characters
+  CharacterChoices,
+  // game_data
+  DeckReshuffled,
+  // game_data
+  DeckEmpty,
+  // client_id, game_data, response_data, effect_code, cards
+  ResponseRequested,
+  // client_id, effect_code, cards
+  EffectStarted,
+  // client_id
+  DynamiteExploded,
+  // client_id, game_data
+  DynamitePassed,
+  // client_id, game_data
+  TurnSkipped,
+  // session_id, expansions
+  ExpansionsChanged,
+  // client_id, game_data
+  EventRevealed,
+  // client_id, game_data
+  CardBought,
+  // client_id
+  TakeBackVoted,
+}
+
+#[derive(Deserialize_repr, Serialize_repr, Debug, Clone, Eq, Hash, PartialEq)]
+#[repr(u8)]
+pub enum ErrorCode {
+  InvalidPhase = 1,
+  HandLimitExceeded,
+  NotYourTurn,
+  CardNotInHand,
+  InvalidSelection,
+  AbilityUnavailable,
+  CardNotReady,
+  CardDisabled,
+  NotEnoughGold,
+  InvalidLog,
+  TakeBackUnavailable,
+  // target_id, distance, range
+  OutOfRange,
+  // expected, given
+  WrongTargetCount,
+  // target_id
+  InvalidTarget,
+  // limit
+  BangLimitReached,
+  ResponseOnly,
+  FullHealth,
+  TooFewPlayers,
+  // target_id
+  SheriffTargeted,
+  // target_id
+  EmptyHand,
+  // target_id
+  FieldCardMissing,
+  // target_id, card
+  DuplicateEquipment,
+  ExtraDiscardRequired,
+  // target_id
+  NotEliminated,
+}
+
+/// The details of a rule error, for pointing out the cards and players involved
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct ErrorParams {
+  pub target_id: Option<String>,
+  pub card: Option<CardName>,
+  pub distance: Option<u8>,
+  pub range: Option<u8>,
+  pub expected: Option<usize>,
+  pub given: Option<usize>,
+  pub limit: Option<u8>,
+}
+
+#[derive(Deserialize_repr, Serialize_repr, Debug, Clone, Eq, Hash, PartialEq)]
+#[repr(u8)]
+pub enum TurnPhase {
+  Start = 1,
+  Draw,
+  Play,
+  Discard,
+  End,
+}
+
+#[derive(Deserialize)]
+pub struct ClientEvent {
+  pub event_code: ClientEventCode,
+  pub target_ids: Option<Vec<String>>,
+  pub cards: Option<Vec<Card>>,
+  pub session_id: Option<String>,
+  pub character: Option<Character>,
+  pub field_card: Option<Card>,
+  pub expansions: Option<Vec<Expansion>>,
+  pub shop_card: Option<ShopCard>,
+  /// the seed to deal a new game from, only honored from the session owner
+  pub seed: Option<u32>,
+  /// the contents of a downloaded game log to play back
+  pub game_log: Option<String>,
+  /// whether the players of a new game may vote to take back the last action, only honored from the session owner
+  pub take_backs: Option<bool>,
+  /// what a new game does once both the deck and the discard pile run out, only honored from the session owner
+  pub empty_deck_rule: Option<EmptyDeckRule>,
+}
+
+#[derive(Deserialize_repr)]
+#[repr(u8)]
+pub enum ClientEventCode {
+  // session_id
+  JoinSession = 1,
+  CreateSession,
+  LeaveSession,
+  DataRequest,
+  StartGame,
+  EndTurn,
+  PlayCard,
+  StateResponse,
+  DrawCards,
+  DiscardCards,
+  // cards, target_ids
+  UseAbility,
+  // character
+  ChooseCharacter,
+  // expansions
+  SetExpansions,
+  // shop_card
+  BuyCard,
+  // game_log, target_ids
+  LoadReplay,
+  ReplayStep,
+  VoteTakeBack,
+}
+
+#[derive(Deserialize_repr, Serialize_repr, Debug, Clone, Eq, Hash, PartialEq)]
+#[repr(u8)]
+pub enum Role {
+  Sheriff = 1,
+  Renegade,
+  Outlaw,
+  Deputy,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Eq, Hash, PartialEq)]
+#[serde(from = "u8", into = "u8")]
+#[repr(u8)]
+pub enum Character {
+  BartCassidy = 1,
+  BlackJack,
+  CalamityJanet,
+  ElGringo,
+  JesseJones,
+  Jourdonnais,
+  KitCarlson,
+  LuckyDuke,
+  PaulRegret,
+  PedroRamirez,
+  RoseDoolan,
+  SidKetchum,
+  SlabTheKiller,
+  SuzyLafayette,
+  VultureSam,
+  WillyTheKid,
+  // Dodge City
+  ApacheKid,
+  BelleStar,
+  BillNoface,
+  ChuckWengam,
+  DocHolyday,
+  ElenaFuente,
+  GregDigger,
+  HerbHunter,
+  JoseDelgado,
+  MollyStark,
+  PatBrennan,
+  PixiePete,
+  SeanMallory,
+  TequilaJoe,
+  VeraCuster,
+  // Gold Rush
+  DonBell,
+  DutchWill,
+  JackyMurieta,
+  JoshMcCloud,
+  MadamYto,
+  PrettyLuzena,
+  RaddieSnake,
+  SimeonPicos,
+  // characters clients only know by the code and name sent with the game (ex. Valley of Shadows)
+  Custom(u8),
+}
+
+#[derive(Deserialize_repr, Serialize_repr, Debug, Clone, Eq, Hash, PartialEq)]
+#[repr(u8)]
+pub enum Expansion {
+  DodgeCity = 1,
+  HighNoon,
+  FistfulOfCards,
+  GoldRush,
+  ValleyOfShadows,
+}
+
+/// What happens when a card must be drawn but both the deck and the discard pile are empty
+#[derive(Deserialize_repr, Serialize_repr, Debug, Clone, Eq, PartialEq)]
+#[repr(u8)]
+pub enum EmptyDeckRule {
+  /// the card is not drawn
+  SkipDraw = 1,
+  /// a fresh deck of the cards not in play is shuffled and play continues
+  NewDeck,
+}
+
+#[derive(Deserialize_repr, Serialize_repr, Debug, Clone, Eq, Hash, PartialEq)]
+#[repr(u8)]
+pub enum EventCard {
+  // High Noon
+  Thirst = 1,
+  Hangover,
+  Sermon,
+  Shootout,
+  TheDoctor,
+  GhostTown,
+  HighNoon,
+  // A Fistful of Cards
+  Ambush,
+  Lasso,
+  TheJudge,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Eq, Hash, PartialEq)]
+pub struct ResponseData {
+  pub cards: Vec<CardName>,
+  pub characters: Vec<Character>,
+}
+
+#[derive(Deserialize_repr, Serialize_repr, Debug, Clone, Eq, Hash, PartialEq)]
+#[repr(u8)]
+pub enum ShopCard {
+  // Brown
+  UnionPacific = 1,
+  Rum,
+  // Black
+  Belt,
+  Boots,
+  Calumet,
+  Horseshoe,
+  Pickaxe,
+}
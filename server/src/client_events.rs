@@ -0,0 +1,922 @@
+use crate::{data_types, session_types, ws::cleanup_session};
+use engine::game_engine::{actions, draft, replay, types};
+use engine::shared_types;
+use nanoid::nanoid;
+use serde_json::from_str;
+use std::collections::HashMap;
+use std::time::Duration;
+use warp::ws::Message;
+
+/// Handle the Client events from a given Session
+#[allow(
+  clippy::manual_map,
+  clippy::needless_borrow,
+  clippy::redundant_pattern_matching,
+  clippy::unnecessary_to_owned
+)]
+pub async fn handle_event(
+  client_id: &str,
+  event: &str,
+  clients: &data_types::SafeClients,
+  sessions: &data_types::SafeSessions,
+  game_states: &data_types::SafeGameStates,
+  game_dict: &data_types::SafeGameDictionary,
+) {
+  //======================================================
+  // Deserialize into Session Event object
+  //======================================================
+  let client_event: shared_types::ClientEvent = match from_str::<shared_types::ClientEvent>(event) {
+    Ok(obj) => obj,
+    Err(_) => {
+      eprintln!(
+        "[error] failed to parse ClientEvent struct from string: {}",
+        event
+      );
+      return;
+    }
+  };
+
+  match client_event.event_code {
+    shared_types::ClientEventCode::DataRequest => {
+      let session_id: String = match get_client_session_id(client_id, clients).await {
+        Some(s_id) => s_id,
+        None => return, // no session is ok
+      };
+
+      let mut server_event: shared_types::ServerEvent = shared_types::ServerEvent::from_event(
+        shared_types::ServerEventCode::DataResponse,
+        shared_types::ServerEventData {
+          session_id: Some(session_id.clone()),
+          ..Default::default()
+        },
+      );
+
+      if let Some(client) = clients.read().await.get(client_id) {
+        if let Some(data) = server_event.data.as_mut() {
+          if let Some(session) = sessions.read().await.get(&session_id) {
+            data.session_client_ids = Some(session.get_client_ids());
+          }
+          if let Some(game_state) = game_states.read().await.get(&session_id) {
+            data.game_data = Some(game_state.to_game_data());
+            data.player_data = match game_state.player_data.get(&client.id) {
+              Some(pd) => Some(pd.clone()),
+              None => None,
+            };
+          }
+        }
+        notify_client(&server_event, client);
+      }
+    }
+    shared_types::ClientEventCode::CreateSession => {
+      let session = &mut session_types::Session {
+        client_statuses: HashMap::new(),
+        owner: client_id.to_string(),
+        id: get_rand_session_id(),
+        expansions: Vec::new(),
+        games_started: 0,
+      };
+      session.insert_client(&client_id.to_string(), true);
+
+      sessions
+        .write()
+        .await
+        .insert(session.id.clone(), session.clone());
+
+      if let Some(client) = clients.write().await.get_mut(client_id) {
+        client.session_id = Some(session.id.clone());
+      }
+
+      if let Some(client) = clients.read().await.get(client_id) {
+        notify_client(
+          &shared_types::ServerEvent::from_event(
+            shared_types::ServerEventCode::ClientJoined,
+            shared_types::ServerEventData {
+              session_id: Some(session.id.clone()),
+              client_id: Some(client_id.to_string()),
+              session_client_ids: Some(session.get_client_ids()),
+              ..Default::default()
+            },
+          ),
+          &client,
+        );
+      }
+
+      println!(
+        "[event] created session :: session count: {}",
+        sessions.read().await.len()
+      );
+    }
+    shared_types::ClientEventCode::JoinSession => {
+      let session_id = match client_event.session_id {
+        Some(s_id) => s_id,
+        None => return, // no session was found on a session join request? ¯\(°_o)/¯
+      };
+
+      remove_client_from_current_session(client_id, clients, sessions, game_states).await;
+
+      if let Some(session) = sessions.write().await.get_mut(&session_id) {
+        if let Some(_) = game_states.read().await.get(&session_id) {
+          return; // do not allow clients to join an active game
+        }
+        insert_client_into_given_session(client_id, &clients, session).await;
+      } else {
+        if let Some(client) = clients.read().await.get(client_id) {
+          notify_client(
+            &shared_types::ServerEvent::from_error(&format!("Invalid SessionID: {}", session_id)),
+            &client,
+          );
+        }
+      }
+    }
+    shared_types::ClientEventCode::LeaveSession => {
+      remove_client_from_current_session(client_id, clients, sessions, game_states).await;
+    }
+    shared_types::ClientEventCode::SetExpansions => {
+      let expansions: Vec<shared_types::Expansion> = client_event.expansions.unwrap_or_default();
+
+      let session_id = match get_client_session_id(client_id, clients).await {
+        Some(s_id) => s_id,
+        None => return,
+      };
+      if game_states.read().await.contains_key(&session_id) {
+        return relay_messages(client_id, Err(types::RuleError::InvalidPhase), clients).await;
+      }
+
+      if let Some(session) = sessions.write().await.get_mut(&session_id) {
+        // only the owner of the session decides what is played
+        if session.owner != client_id {
+          return;
+        }
+        session.expansions = expansions;
+        notify_session(
+          &shared_types::ServerEvent::from_event(
+            shared_types::ServerEventCode::ExpansionsChanged,
+            shared_types::ServerEventData {
+              session_id: Some(session_id.clone()),
+              expansions: Some(session.expansions.clone()),
+              ..Default::default()
+            },
+          ),
+          session,
+          clients,
+        )
+        .await;
+      }
+    }
+    shared_types::ClientEventCode::StartGame => {
+      let session_id = match get_client_session_id(client_id, clients).await {
+        Some(s_id) => s_id,
+        None => return,
+      };
+
+      if let Some(session) = sessions.write().await.get_mut(&session_id) {
+        // only the owner of the session can deal a game from a seed of their choosing,
+        // or change how the game is played
+        let is_owner = session.owner == client_id;
+        let seed = client_event.seed.filter(|_| is_owner);
+        let take_backs = is_owner && client_event.take_backs.unwrap_or_default();
+        let empty_deck_rule = client_event
+          .empty_deck_rule
+          .filter(|_| is_owner)
+          .unwrap_or_default();
+        match types::GameState::new(
+          &session.get_client_ids(),
+          &session.expansions,
+          seed,
+          empty_deck_rule,
+          &game_dict.get(&session.expansions),
+        ) {
+          Ok(mut game_state) => {
+            game_state.take_backs = take_backs;
+            session.games_started += 1;
+            game_states
+              .write()
+              .await
+              .insert(session_id.clone(), game_state.clone());
+
+            // give each player the initial state of the game
+            for (player, player_data) in game_state.player_data.iter() {
+              if let Some(client) = clients.read().await.get(player) {
+                notify_client(
+                  &shared_types::ServerEvent::from_event(
+                    shared_types::ServerEventCode::GameStarted,
+                    shared_types::ServerEventData {
+                      session_client_ids: Some(session.get_client_ids()),
+                      game_data: Some(game_state.to_game_data()),
+                      player_data: Some(player_data.clone()),
+                      ..Default::default()
+                    },
+                  ),
+                  &client,
+                )
+              }
+            }
+            // let each player choose their character
+            relay_messages(
+              client_id,
+              Ok(game_state.get_character_choice_messages()),
+              clients,
+            )
+            .await;
+
+            // pick characters for those who have not chosen once the time is up
+            let game_number = session.games_started;
+            let (clients, sessions, game_states, game_dict) = (
+              clients.clone(),
+              sessions.clone(),
+              game_states.clone(),
+              game_dict.clone(),
+            );
+            tokio::spawn(async move {
+              tokio::time::delay_for(Duration::from_secs(draft::CHARACTER_DRAFT_TIMEOUT_SECS))
+                .await;
+              end_draft(
+                &session_id,
+                game_number,
+                &clients,
+                &sessions,
+                &game_states,
+                &game_dict,
+              )
+              .await;
+            });
+          }
+          Err(msg) => {
+            eprintln!("[error] {}", msg);
+            notify_session(
+              &shared_types::ServerEvent::from_error(&msg),
+              &session,
+              &clients,
+            )
+            .await;
+          }
+        }
+      }
+    }
+    shared_types::ClientEventCode::LoadReplay => {
+      let game_log: replay::GameLog = match from_str(&client_event.game_log.unwrap_or_default()) {
+        Ok(game_log) => game_log,
+        Err(_) => {
+          return relay_messages(client_id, Err(types::RuleError::InvalidLog), clients).await;
+        }
+      };
+      // follow the first player of the game unless the viewer picked one
+      let player = match client_event.target_ids.unwrap_or_default().first() {
+        Some(player) => player.clone(),
+        None => match game_log.client_ids.first() {
+          Some(player) => player.clone(),
+          None => return,
+        },
+      };
+
+      let result = replay::Replay::new(game_log);
+      if let Some(client) = clients.write().await.get_mut(client_id) {
+        match result {
+          Ok(replay) => {
+            client.replay = Some(session_types::ReplayViewer { player, replay });
+          }
+          Err(msg) => notify_client(&shared_types::ServerEvent::from_error(&msg), client),
+        }
+      }
+    }
+    shared_types::ClientEventCode::ReplayStep => {
+      if let Some(client) = clients.write().await.get_mut(client_id) {
+        let viewer = match client.replay.as_mut() {
+          Some(viewer) => viewer,
+          None => return,
+        };
+        // the viewer sees the turn as the player they follow saw it
+        let messages: Vec<shared_types::ServerEvent> = match viewer.replay.step_turn() {
+          Ok(events) => events
+            .into_iter()
+            .filter(|(player, _)| *player == viewer.player)
+            .map(|(_, message)| message)
+            .collect(),
+          Err(msg) => vec![shared_types::ServerEvent::from_error(&msg)],
+        };
+        for message in messages.iter() {
+          notify_client(message, client);
+        }
+      }
+    }
+    shared_types::ClientEventCode::VoteTakeBack => {
+      let session_id: String = match get_client_session_id(client_id, clients).await {
+        Some(s_id) => s_id,
+        None => return,
+      };
+
+      let session_client_ids = match sessions.read().await.get(&session_id) {
+        Some(session) => session.get_client_ids(),
+        None => return,
+      };
+      if let Some(game_state) = game_states.write().await.get_mut(&session_id) {
+        let mut result = game_state.vote_take_back(client_id);
+        // the restored game is sent the same way as a resumed session
+        if let Ok(messages) = result.as_mut() {
+          for (_, message) in messages.iter_mut() {
+            if let shared_types::ServerEventCode::DataResponse = message.event_code {
+              if let Some(data) = message.data.as_mut() {
+                data.session_id = Some(session_id.clone());
+                data.session_client_ids = Some(session_client_ids.clone());
+              }
+            }
+          }
+        }
+        relay_messages(client_id, result, clients).await;
+      }
+    }
+    _ => {
+      let action = match to_action(client_event) {
+        Some(action) => action,
+        None => return, // missing the data the game action needs
+      };
+
+      let session_id: String = match get_client_session_id(client_id, clients).await {
+        Some(s_id) => s_id,
+        None => return, // this action was not taken in an active session?
+      };
+
+      if let Some(game_state) = game_states.write().await.get_mut(&session_id) {
+        let result = game_state.apply(client_id, action, &game_dict.get(&game_state.expansions));
+        relay_messages(client_id, result, clients).await;
+      } else {
+        eprintln!("[error] session was not found with id: {}", session_id);
+      }
+    }
+  }
+}
+
+/// Translates the Client events that act on a game into the action they take,
+/// returning None for events that are missing the data the action needs
+fn to_action(client_event: shared_types::ClientEvent) -> Option<actions::Action> {
+  let cards = client_event.cards.unwrap_or_default();
+  // default to an empty vector for actions whose effects do not concern targets
+  let targets = client_event.target_ids.unwrap_or_default();
+  match client_event.event_code {
+    shared_types::ClientEventCode::DrawCards => Some(actions::Action::DrawCards { cards, targets }),
+    shared_types::ClientEventCode::PlayCard => Some(actions::Action::PlayCard {
+      cards,
+      targets,
+      field_card: client_event.field_card,
+    }),
+    // responding without any cards passes
+    shared_types::ClientEventCode::StateResponse => Some(actions::Action::Respond { cards }),
+    shared_types::ClientEventCode::DiscardCards => Some(actions::Action::DiscardCards { cards }),
+    shared_types::ClientEventCode::EndTurn => Some(actions::Action::EndTurn),
+    shared_types::ClientEventCode::UseAbility => {
+      Some(actions::Action::UseAbility { cards, targets })
+    }
+    shared_types::ClientEventCode::ChooseCharacter => Some(actions::Action::ChooseCharacter {
+      character: client_event.character?,
+    }),
+    shared_types::ClientEventCode::BuyCard => Some(actions::Action::BuyCard {
+      shop_card: client_event.shop_card?,
+    }),
+    _ => None,
+  }
+}
+
+/// Send an update to all clients in the session
+///
+/// Uses a Read lock on clients
+async fn notify_session(
+  game_update: &shared_types::ServerEvent,
+  session: &session_types::Session,
+  clients: &data_types::SafeClients,
+) {
+  for client_id in session.client_statuses.keys() {
+    if let Some(client) = clients.read().await.get(client_id) {
+      notify_client(game_update, client);
+    }
+  }
+}
+
+/// Picks a character for the players of a game who have not chosen one in time,
+/// unless the session has started another game since the draft began
+async fn end_draft(
+  session_id: &str,
+  game_number: u32,
+  clients: &data_types::SafeClients,
+  sessions: &data_types::SafeSessions,
+  game_states: &data_types::SafeGameStates,
+  game_dict: &data_types::SafeGameDictionary,
+) {
+  match sessions.read().await.get(session_id) {
+    Some(session) if session.games_started == game_number => {}
+    _ => return,
+  }
+  if let Some(game_state) = game_states.write().await.get_mut(session_id) {
+    if let Ok(messages) = game_state.apply(
+      actions::SERVER_PLAYER,
+      actions::Action::EndDraft,
+      &game_dict.get(&game_state.expansions),
+    ) {
+      relay_messages(actions::SERVER_PLAYER, Ok(messages), clients).await;
+    }
+  }
+}
+
+/// Send the results of a game action to the clients they are addressed to,
+/// or send the error back to the client who attempted the action
+async fn relay_messages(
+  sender_id: &str,
+  result: Result<types::GameMessages, types::RuleError>,
+  clients: &data_types::SafeClients,
+) {
+  match result {
+    Ok(messages) => {
+      for (client_id, message) in messages.iter() {
+        if let Some(client) = clients.read().await.get(client_id) {
+          notify_client(message, client);
+        }
+      }
+    }
+    Err(error) => {
+      println!("[ServerEventError] {}", error.get_message());
+      if let Some(client) = clients.read().await.get(sender_id) {
+        notify_client(&shared_types::ServerEvent::from_rule_error(&error), client);
+      }
+    }
+  }
+}
+
+/// Send an update to single clients
+fn notify_client(game_update: &shared_types::ServerEvent, client: &session_types::Client) {
+  let sender = match &client.sender {
+    Some(s) => s,
+    None => return eprintln!("[error] sender was lost for client: {}", client.id),
+  };
+  if let Err(e) = sender.send(Ok(Message::text(
+    serde_json::to_string(game_update).unwrap(),
+  ))) {
+    eprintln!(
+      "[error] failed to send message to {} with err: {}",
+      client.id, e,
+    );
+  }
+}
+
+/// Removes a client from the session that they currently exist under
+async fn remove_client_from_current_session(
+  client_id: &str,
+  clients: &data_types::SafeClients,
+  sessions: &data_types::SafeSessions,
+  game_states: &data_types::SafeGameStates,
+) {
+  let session_id: String = match get_client_session_id(client_id, clients).await {
+    Some(s_id) => s_id,
+    None => return, // client did not exist in any session
+  };
+
+  let mut session_empty: bool = false;
+  if let Some(session) = sessions.write().await.get_mut(&session_id) {
+    // notify all clients in the sessions that the client will be leaving
+    notify_session(
+      &shared_types::ServerEvent::from_event(
+        shared_types::ServerEventCode::ClientLeft,
+        shared_types::ServerEventData {
+          client_id: Some(client_id.to_string()),
+          ..Default::default()
+        },
+      ),
+      session,
+      clients,
+    )
+    .await;
+    // remove the client from the session
+    session.remove_client(client_id);
+    // revoke the client's copy of the session_id
+    if let Some(client) = clients.write().await.get_mut(client_id) {
+      client.session_id = None;
+    }
+    // checks the statuses to see if any users are still active
+    session_empty = session.get_clients_with_active_status(true).is_empty();
+    // if the session is not empty, make someone else the owner
+    if !session_empty {
+      set_new_session_owner(session, clients, &session.get_client_ids()[0]);
+    }
+  }
+  // clean up the session from the map if it is empty
+  // * we cannot do this in the scope above because because we are already holding a mutable reference to a session within the map
+  if session_empty {
+    cleanup_session(&session_id, sessions, game_states).await;
+  }
+}
+
+/// Takes a mutable session reference in order to add a client to a given session
+///
+/// Uses a Read lock for Clients
+async fn insert_client_into_given_session(
+  client_id: &str,
+  clients: &data_types::SafeClients,
+  session: &mut session_types::Session,
+) {
+  // add client to session
+  session.insert_client(client_id, true);
+  // update session_id of client
+  if let Some(client) = clients.write().await.get_mut(client_id) {
+    client.session_id = Some(session.id.clone());
+  }
+  // notify all clients in the session that the client has joined
+  notify_session(
+    &shared_types::ServerEvent::from_event(
+      shared_types::ServerEventCode::ClientJoined,
+      shared_types::ServerEventData {
+        session_id: Some(session.id.clone()),
+        client_id: Some(client_id.to_string()),
+        session_client_ids: Some(session.get_client_ids()),
+        ..Default::default()
+      },
+    ),
+    session,
+    clients,
+  )
+  .await;
+}
+
+fn set_new_session_owner(
+  session: &mut session_types::Session,
+  _clients: &data_types::SafeClients,
+  client_id: &str,
+) {
+  session.owner = client_id.to_string();
+  // notify_all_clients(
+  //   &ServerEvent {
+  //     event_code: ServerEventCode::SessionOwnerChange,
+  //     session_id: Some(session.id.clone()),
+  //     client_id: Some(client_id.clone()),
+  //     session_client_ids: None,
+  //   },
+  //   &session,
+  //   &clients,
+  // );
+}
+
+/// Gets a random new session 1 that is 5 characters long
+/// This should almost ensure session uniqueness when dealing with a sizeable number of sessions
+fn get_rand_session_id() -> String {
+  let alphabet: [char; 26] = [
+    'A', 'B', 'C', 'D', 'E', 'F', 'G', 'H', 'I', 'J', 'K', 'L', 'M', 'N', 'O', 'P', 'Q', 'R', 'S',
+    'T', 'U', 'V', 'W', 'X', 'Y', 'Z',
+  ];
+  nanoid!(5, &alphabet)
+}
+
+/// pull the session id off of a client
+async fn get_client_session_id(
+  client_id: &str,
+  clients: &data_types::SafeClients,
+) -> Option<String> {
+  if let Some(client) = &clients.read().await.get(client_id) {
+    client.session_id.clone()
+  } else {
+    None
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use engine::game_engine::{data, expansions, test_utils};
+  use serde_json::{json, Value};
+  use std::sync::Arc;
+  use tokio::sync::{mpsc, RwLock};
+
+  type ClientReceiver = mpsc::UnboundedReceiver<Result<Message, warp::Error>>;
+
+  const SESSION_ID: &str = "TESTS";
+
+  /// A server with connected clients "p0" through "p3" playing a game in the same session
+  struct TestServer {
+    clients: data_types::SafeClients,
+    sessions: data_types::SafeSessions,
+    game_states: data_types::SafeGameStates,
+    game_dict: data_types::SafeGameDictionary,
+    receivers: HashMap<String, ClientReceiver>,
+  }
+
+  impl TestServer {
+    /// Starts the server at the draw phase of the first player, "p0"
+    fn new() -> TestServer {
+      let player_order: Vec<String> = (0..4).map(|i| format!("p{}", i)).collect();
+
+      let mut clients = HashMap::new();
+      let mut receivers = HashMap::new();
+      let mut session = session_types::Session {
+        id: SESSION_ID.to_string(),
+        owner: player_order[0].clone(),
+        client_statuses: HashMap::new(),
+        expansions: Vec::new(),
+        games_started: 1,
+      };
+      for id in player_order.iter() {
+        let (sender, receiver) = mpsc::unbounded_channel();
+        clients.insert(
+          id.clone(),
+          session_types::Client {
+            id: id.clone(),
+            session_id: Some(SESSION_ID.to_string()),
+            sender: Some(sender),
+            replay: None,
+          },
+        );
+        receivers.insert(id.clone(), receiver);
+        session.insert_client(id, true);
+      }
+
+      let mut game_state = test_utils::game_state(player_order.len());
+      game_state.turn_phase = shared_types::TurnPhase::Draw;
+      game_state.deck =
+        data::generate_deck(&Vec::new(), &test_utils::game_dict(), &mut game_state.rng);
+      for player_data in game_state.player_data.values_mut() {
+        player_data.hand.push(mustang());
+      }
+
+      TestServer {
+        clients: Arc::new(RwLock::new(clients)),
+        sessions: Arc::new(RwLock::new(
+          vec![(SESSION_ID.to_string(), session)]
+            .into_iter()
+            .collect(),
+        )),
+        game_states: Arc::new(RwLock::new(
+          vec![(SESSION_ID.to_string(), game_state)]
+            .into_iter()
+            .collect(),
+        )),
+        game_dict: Arc::new(expansions::GameDictionaries::new(HashMap::new())),
+        receivers,
+      }
+    }
+
+    async fn send(&self, client_id: &str, event: Value) {
+      handle_event(
+        client_id,
+        &event.to_string(),
+        &self.clients,
+        &self.sessions,
+        &self.game_states,
+        &self.game_dict,
+      )
+      .await;
+    }
+
+    /// Takes all of the messages that a client has been sent so far
+    fn received(&mut self, client_id: &str) -> Vec<Value> {
+      let mut messages = Vec::new();
+      if let Some(receiver) = self.receivers.get_mut(client_id) {
+        while let Ok(Ok(message)) = receiver.try_recv() {
+          messages.push(serde_json::from_str(message.to_str().unwrap()).unwrap());
+        }
+      }
+      messages
+    }
+
+    /// Whether the client was sent an error with the given code
+    fn received_error(&mut self, client_id: &str, error_code: shared_types::ErrorCode) -> bool {
+      let error_code = serde_json::to_value(error_code).unwrap();
+      self
+        .received(client_id)
+        .iter()
+        .any(|message| message["error_code"] == error_code)
+    }
+
+    async fn game_state(&self) -> types::GameState {
+      self.game_states.read().await[SESSION_ID].clone()
+    }
+  }
+
+  fn mustang() -> shared_types::Card {
+    shared_types::Card {
+      name: shared_types::CardName::Mustang,
+      suit: shared_types::CardSuit::Hearts,
+      rank: shared_types::CardRank::N8,
+    }
+  }
+
+  fn client_event(event_code: u8) -> Value {
+    json!({ "event_code": event_code })
+  }
+
+  const START_GAME: u8 = 5;
+  const END_TURN: u8 = 6;
+  const PLAY_CARD: u8 = 7;
+  const STATE_RESPONSE: u8 = 8;
+  const DRAW_CARDS: u8 = 9;
+  const LOAD_REPLAY: u8 = 15;
+  const REPLAY_STEP: u8 = 16;
+  const VOTE_TAKE_BACK: u8 = 17;
+
+  #[tokio::test]
+  async fn only_the_active_player_can_draw() {
+    let mut server = TestServer::new();
+
+    server.send("p1", client_event(DRAW_CARDS)).await;
+    assert!(server.received_error("p1", shared_types::ErrorCode::NotYourTurn));
+    assert_eq!(server.game_state().await.player_data["p1"].hand.len(), 1);
+
+    server.send("p0", client_event(DRAW_CARDS)).await;
+    let game_state = server.game_state().await;
+    assert_eq!(game_state.turn_phase, shared_types::TurnPhase::Play);
+    assert_eq!(game_state.player_data["p0"].hand.len(), 3);
+  }
+
+  #[tokio::test]
+  async fn only_the_active_player_can_end_the_turn() {
+    let mut server = TestServer::new();
+    server.send("p0", client_event(DRAW_CARDS)).await;
+
+    for player in ["p1", "p2", "p3"].iter() {
+      server.send(player, client_event(END_TURN)).await;
+      assert!(server.received_error(player, shared_types::ErrorCode::NotYourTurn));
+    }
+    assert_eq!(server.game_state().await.turn_index, 0);
+
+    server.send("p0", client_event(END_TURN)).await;
+    let game_state = server.game_state().await;
+    assert_eq!(game_state.turn_index, 1);
+    assert_eq!(game_state.turn_phase, shared_types::TurnPhase::Draw);
+    let turn_start = serde_json::to_value(shared_types::ServerEventCode::TurnStart).unwrap();
+    for player in ["p0", "p1", "p2", "p3"].iter() {
+      assert!(server
+        .received(player)
+        .iter()
+        .any(|message| message["event_code"] == turn_start
+          && message["data"]["client_id"] == json!("p1")));
+    }
+  }
+
+  #[tokio::test]
+  async fn only_the_active_player_can_play_cards() {
+    let mut server = TestServer::new();
+    server.send("p0", client_event(DRAW_CARDS)).await;
+
+    let mut play_mustang = client_event(PLAY_CARD);
+    play_mustang["cards"] = json!([mustang()]);
+
+    server.send("p1", play_mustang.clone()).await;
+    assert!(server.received_error("p1", shared_types::ErrorCode::NotYourTurn));
+    assert!(server.game_state().await.player_data["p1"].field.is_empty());
+
+    server.send("p0", play_mustang).await;
+    assert_eq!(
+      server.game_state().await.player_data["p0"].field,
+      vec![mustang()]
+    );
+  }
+
+  #[tokio::test]
+  async fn only_pending_responders_can_respond() {
+    let mut server = TestServer::new();
+    let mut respond = client_event(STATE_RESPONSE);
+    respond["cards"] = json!([mustang()]);
+
+    server.send("p2", respond.clone()).await;
+    assert!(server.received_error("p2", shared_types::ErrorCode::InvalidPhase));
+
+    if let Some(game_state) = server.game_states.write().await.get_mut(SESSION_ID) {
+      game_state.turn_phase = shared_types::TurnPhase::Play;
+      game_state.response_queue.insert(
+        String::from("p1"),
+        shared_types::ResponseData {
+          cards: vec![shared_types::CardName::Missed],
+          characters: Vec::new(),
+        },
+      );
+    }
+
+    server.send("p2", respond).await;
+    assert!(server.received_error("p2", shared_types::ErrorCode::NotYourTurn));
+
+    let mut play_mustang = client_event(PLAY_CARD);
+    play_mustang["cards"] = json!([mustang()]);
+    server.send("p0", play_mustang).await;
+    assert!(server.received_error("p0", shared_types::ErrorCode::InvalidPhase));
+  }
+
+  #[tokio::test]
+  async fn a_viewer_steps_through_a_replay_as_the_player_they_follow() {
+    let mut server = TestServer::new();
+    let client_ids: Vec<String> = (0..4).map(|i| format!("q{}", i)).collect();
+    let game_dict = server.game_dict.get(&Vec::new());
+    let mut game_state = types::GameState::new(
+      &client_ids,
+      &Vec::new(),
+      Some(3),
+      shared_types::EmptyDeckRule::default(),
+      &game_dict,
+    )
+    .unwrap();
+    game_state
+      .apply(
+        actions::SERVER_PLAYER,
+        actions::Action::EndDraft,
+        &game_dict,
+      )
+      .unwrap();
+
+    let mut load_replay = client_event(LOAD_REPLAY);
+    load_replay["game_log"] = json!("not a log");
+    server.send("p1", load_replay.clone()).await;
+    assert!(server.received_error("p1", shared_types::ErrorCode::InvalidLog));
+
+    load_replay["game_log"] = json!(serde_json::to_string(&game_state.log).unwrap());
+    load_replay["target_ids"] = json!(["q2"]);
+    server.send("p1", load_replay).await;
+    server.send("p1", client_event(REPLAY_STEP)).await;
+
+    let expected: Vec<Value> = game_state.log.entries[0]
+      .events
+      .iter()
+      .filter(|(player, _)| player == "q2")
+      .map(|(_, message)| serde_json::to_value(message).unwrap())
+      .collect();
+    assert!(!expected.is_empty());
+    assert_eq!(server.received("p1"), expected);
+  }
+
+  #[tokio::test]
+  async fn the_restored_game_is_sent_once_everyone_votes_to_take_back() {
+    let mut server = TestServer::new();
+    if let Some(game_state) = server.game_states.write().await.get_mut(SESSION_ID) {
+      game_state.take_backs = true;
+    }
+    server.send("p0", client_event(DRAW_CARDS)).await;
+    assert_eq!(server.game_state().await.player_data["p0"].hand.len(), 3);
+
+    for player in ["p0", "p1", "p2", "p3"].iter() {
+      server.send(player, client_event(VOTE_TAKE_BACK)).await;
+    }
+    let game_state = server.game_state().await;
+    assert_eq!(game_state.turn_phase, shared_types::TurnPhase::Draw);
+    assert_eq!(game_state.player_data["p0"].hand.len(), 1);
+
+    let data_response = serde_json::to_value(shared_types::ServerEventCode::DataResponse).unwrap();
+    assert!(server.received("p1").iter().any(|message| {
+      message["event_code"] == data_response && message["data"]["session_id"] == json!(SESSION_ID)
+    }));
+  }
+
+  #[tokio::test]
+  async fn only_the_session_owner_chooses_the_empty_deck_rule() {
+    let new_deck = serde_json::to_value(shared_types::EmptyDeckRule::NewDeck).unwrap();
+    let mut start_game = client_event(START_GAME);
+    start_game["empty_deck_rule"] = new_deck;
+
+    let server = TestServer::new();
+    server.send("p1", start_game.clone()).await;
+    let game_state = server.game_state().await;
+    assert_eq!(
+      game_state.empty_deck_rule,
+      shared_types::EmptyDeckRule::SkipDraw
+    );
+
+    server.send("p0", start_game).await;
+    let game_state = server.game_state().await;
+    assert_eq!(
+      game_state.empty_deck_rule,
+      shared_types::EmptyDeckRule::NewDeck
+    );
+    assert_eq!(
+      game_state.log.empty_deck_rule,
+      shared_types::EmptyDeckRule::NewDeck
+    );
+  }
+
+  #[tokio::test]
+  async fn the_draft_timer_of_an_earlier_game_is_ignored() {
+    let server = TestServer::new();
+    let client_ids: Vec<String> = (0..4).map(|i| format!("p{}", i)).collect();
+    let game_state = types::GameState::new(
+      &client_ids,
+      &Vec::new(),
+      Some(0),
+      shared_types::EmptyDeckRule::default(),
+      &server.game_dict.get(&Vec::new()),
+    )
+    .unwrap();
+    assert!(game_state.is_drafting());
+    server
+      .game_states
+      .write()
+      .await
+      .insert(SESSION_ID.to_string(), game_state);
+
+    // the session is on its first game, so the timer of a game before it has gone stale
+    let end_draft_of = |game_number| {
+      end_draft(
+        SESSION_ID,
+        game_number,
+        &server.clients,
+        &server.sessions,
+        &server.game_states,
+        &server.game_dict,
+      )
+    };
+    end_draft_of(0).await;
+    assert!(server.game_state().await.is_drafting());
+
+    end_draft_of(1).await;
+    assert!(!server.game_state().await.is_drafting());
+  }
+}
@@ -1,4 +1,5 @@
-use crate::{game_engine, session_types};
+use crate::session_types;
+use engine::game_engine;
 use std::sync::Arc;
 use tokio::sync::RwLock;
 
@@ -7,4 +8,5 @@ pub type SafeResource<T> = Arc<RwLock<T>>;
 pub type SafeClients = SafeResource<session_types::Clients>;
 pub type SafeSessions = SafeResource<session_types::Sessions>;
 pub type SafeGameStates = SafeResource<game_engine::types::GameStates>;
-pub type SafeGameDictionary = Arc<game_engine::types::GameDictionary>;
+/// the dictionaries of games, composed for the expansions each game is played with
+pub type SafeGameDictionary = Arc<game_engine::expansions::GameDictionaries>;
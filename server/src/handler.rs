@@ -30,6 +30,25 @@ pub async fn ws_handler(
     }
 }
 
+/// Sends the log of a finished game as a JSON file, for replaying it later
+///
+/// The log reveals every hand, so it is only handed out once the game is over
+pub async fn log_handler(
+    session_id: String,
+    game_states: data_types::SafeGameStates,
+) -> Result<impl Reply> {
+    match game_states.read().await.get(&session_id) {
+        Some(game_state) if game_state.get_winner_ids().is_some() => Ok(warp::reply::with_header(
+            warp::reply::json(&game_state.log),
+            "Content-Disposition",
+            format!("attachment; filename=\"game-{}.json\"", session_id),
+        )
+        .into_response()),
+        Some(_) => Ok(StatusCode::CONFLICT.into_response()),
+        None => Err(warp::reject::not_found()),
+    }
+}
+
 /// Health Check Endpoint used to verify the service is live
 pub async fn health_handler() -> Result<impl Reply> {
     Ok(StatusCode::OK)
@@ -1,14 +1,14 @@
+use engine::game_engine;
 use std::collections::HashMap;
 use std::env;
 use std::sync::Arc;
 use tokio::sync::RwLock;
 use warp::Filter;
 
+mod client_events;
 mod data_types;
-mod game_engine;
 mod handler;
 mod session_types;
-mod shared_types;
 mod ws;
 
 #[tokio::main]
@@ -16,13 +16,49 @@ async fn main() {
     let clients: data_types::SafeClients = Arc::new(RwLock::new(HashMap::new()));
     let sessions: data_types::SafeSessions = Arc::new(RwLock::new(HashMap::new()));
     let game_states: data_types::SafeGameStates = Arc::new(RwLock::new(HashMap::new()));
-    let game_dict: data_types::SafeGameDictionary = Arc::new(game_engine::types::GameDictionary {
-        card_dict: game_engine::data::get_card_dictionary(),
-        character_dict: game_engine::data::get_character_dictionary(),
-    });
+
+    // house rule cards defined outside of the code take the place of the built in ones
+    let full_dict = game_engine::expansions::get_game_dictionary(
+        &game_engine::expansions::get_registered_expansions(),
+        &HashMap::new(),
+    );
+    // a file that was asked for has to be there, while the default one is optional
+    let chosen_path = env::var("CARD_DEFINITIONS").ok();
+    let card_definitions_path = chosen_path
+        .as_deref()
+        .unwrap_or(game_engine::card_definitions::DEFAULT_CARD_DEFINITIONS_PATH);
+    let card_definitions = match game_engine::card_definitions::read_card_definitions(
+        chosen_path.as_deref(),
+        &full_dict,
+    ) {
+        Ok(card_definitions) => card_definitions,
+        Err(errors) => {
+            for error in errors.iter() {
+                eprintln!(
+                    "[boot] could not load the card definitions in {}: {}",
+                    card_definitions_path, error
+                );
+            }
+            std::process::exit(1);
+        }
+    };
+    println!(
+        "[boot] loaded {} card definitions from {}",
+        card_definitions.len(),
+        card_definitions_path
+    );
+
+    let game_dict: data_types::SafeGameDictionary = Arc::new(
+        game_engine::expansions::GameDictionaries::new(card_definitions),
+    );
 
     let health_route = warp::path!("health").and_then(handler::health_handler);
 
+    let log_game_states = game_states.clone();
+    let log_route = warp::path!("log" / String)
+        .and(warp::any().map(move || log_game_states.clone()))
+        .and_then(handler::log_handler);
+
     let ws_route = warp::path("ws")
         .and(warp::ws())
         .and(warp::path::param())
@@ -33,7 +69,7 @@ async fn main() {
         .and(warp::any().map(move || game_dict.clone()))
         .and_then(handler::ws_handler);
 
-    let routes = health_route.or(ws_route).with(
+    let routes = health_route.or(log_route).or(ws_route).with(
         warp::cors()
             .allow_any_origin()
             .allow_headers(vec!["Content-Type"])
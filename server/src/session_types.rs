@@ -1,3 +1,4 @@
+use engine::{game_engine, shared_types};
 use std::collections::HashMap;
 use tokio::sync::mpsc;
 use warp::ws::Message;
@@ -11,6 +12,15 @@ pub struct Client {
   pub id: String,
   pub session_id: Option<String>,
   pub sender: Option<mpsc::UnboundedSender<std::result::Result<Message, warp::Error>>>,
+  /// the game the client is watching being played back, if any
+  pub replay: Option<ReplayViewer>,
+}
+
+/// A replay along with the player whose messages the viewer is shown
+#[derive(Debug, Clone)]
+pub struct ReplayViewer {
+  pub player: String,
+  pub replay: game_engine::replay::Replay,
 }
 
 // Data Stored for a Game Sessions
@@ -19,8 +29,13 @@ pub struct Session {
   pub id: String,
   pub owner: String,
   pub client_statuses: HashMap<String, bool>,
+  /// the expansions that games in this session are played with
+  pub expansions: Vec<shared_types::Expansion>,
+  /// the number of games started in this session, which tells a game apart from the ones before it
+  pub games_started: u32,
 }
 impl Session {
+  #[allow(dead_code)]
   pub fn get_client_count(&self) -> usize {
     self.client_statuses.len()
   }
@@ -31,8 +46,7 @@ impl Session {
     self
       .client_statuses
       .clone()
-      .into_iter()
-      .map(|(id, _)| id)
+      .into_keys()
       .collect::<Vec<String>>()
   }
   pub fn remove_client(&mut self, id: &str) {
@@ -51,7 +65,7 @@ impl Session {
       .collect::<Vec<String>>()
   }
   pub fn set_client_active_status(&mut self, id: &str, is_active: bool) {
-    if self.client_statuses.get(id).is_some() {
+    if self.client_statuses.contains_key(id) {
       self.client_statuses.insert(id.to_string(), is_active);
     } else {
       println!(
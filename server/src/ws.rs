@@ -1,4 +1,4 @@
-use crate::{data_types, game_engine, session_types};
+use crate::{client_events, data_types, session_types};
 use futures::{FutureExt, StreamExt};
 use tokio::sync::mpsc;
 use warp::ws::{Message, WebSocket};
@@ -49,11 +49,12 @@ pub async fn client_connection(
             id: id.clone(),
             sender: Some(client_sender),
             session_id: get_client_session_id(&id, &sessions).await,
+            replay: None,
         },
     );
 
     if let Some(client) = clients.read().await.get(&id) {
-        handle_client_connect(&client, &sessions).await;
+        handle_client_connect(client, &sessions).await;
     }
     //======================================================
     // Synchronously wait for messages from the
@@ -114,7 +115,7 @@ async fn handle_client_msg(
         // Game Session Related Events
         //======================================================
         _ => {
-            game_engine::handle_event(id, message, clients, sessions, game_states, game_dict).await;
+            client_events::handle_event(id, message, clients, sessions, game_states, game_dict).await;
         }
     }
 }
@@ -165,7 +166,7 @@ async fn get_client_session_id(
             return Some(session.id.clone());
         }
     }
-    return None;
+    None
 }
 
 /// Remove a sessions and the possible game state that accompanies it
@@ -9,7 +9,7 @@ from pathlib import Path
 ROOT_DEPTH = 2  # relies on being in directory /shared/scripts
 ROOT_DIR = str(Path(__file__).resolve().parents[ROOT_DEPTH]).replace('\\', '/')
 
-EVENT_TYPES_RUST_PATH = ROOT_DIR + '/server/src/shared_types.rs'
+EVENT_TYPES_RUST_PATH = ROOT_DIR + '/server/engine/src/shared_types.rs'
 EVENT_TYPES_TYPESCRIPT_PATH = ROOT_DIR + '/frontend/src/utils/shared-types.ts'
 
 
@@ -34,6 +34,8 @@ def convert_rust_syntax_to_ts(rust_string: str):
         ('Vec', 'Array'),
         ('u8', 'number'),
         ('usize', 'number'),
+        ('u32', 'number'),
+        ('bool', 'boolean'),
         ('HashSet', 'Array'),
         ('HashMap', 'Record'),
         (';', ''),
@@ -64,10 +66,15 @@ def convert_rust_syntax_to_ts(rust_string: str):
     return ts_string
 
 
+def is_tuple_variant(line: str):  # enum variants holding data are only known by their codes in TypeScript
+    stripped = line.strip()
+    return stripped[:1].isupper() and '(' in stripped and stripped.endswith('),')
+
+
 def parse_rust_to_ts():  # Parse into TypeScript module
     with open(EVENT_TYPES_RUST_PATH, 'r') as rust_f:
         lines = []
-        for line in [x for x in rust_f.readlines() if not x.startswith(('#', 'use'))]:  # ignore annotations
+        for line in [x for x in rust_f.readlines() if not x.startswith(('#', 'use')) and not is_tuple_variant(x)]:  # ignore annotations
             lines.append(convert_rust_syntax_to_ts(line))
 
         print('Preparing to write:\n_______________________')